@@ -10,11 +10,16 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{BosonOperator, BosonProduct, HermitianBosonProduct, ModeIndex, OperateOnBosons};
+use super::{
+    boson_hamiltonian_terms, bosonic_commutator_entries_on_row, bosonic_entries_on_row,
+    BosonOperator, BosonProduct, HermitianBosonProduct, ModeIndex, OperateOnBosons,
+    ToBosonSparseMatrixOperator, ToBosonSparseMatrixSuperOperator,
+};
 use crate::{
     GetValue, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError,
     StruqtureVersion, SymmetricIndex,
 };
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -225,6 +230,28 @@ impl<'a> OperateOnModes<'a> for BosonHamiltonian {
 
 impl<'a> OperateOnBosons<'a> for BosonHamiltonian {}
 
+impl<'a> ToBosonSparseMatrixOperator<'a> for BosonHamiltonian {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        bosonic_entries_on_row(&boson_hamiltonian_terms(self)?, row, cutoffs)
+    }
+}
+
+impl<'a> ToBosonSparseMatrixSuperOperator<'a> for BosonHamiltonian {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        bosonic_commutator_entries_on_row(&boson_hamiltonian_terms(self)?, row, cutoffs)
+    }
+}
+
 /// Implements the default function (Default trait) of BosonHamiltonian (an empty BosonHamiltonian).
 ///
 impl Default for BosonHamiltonian {
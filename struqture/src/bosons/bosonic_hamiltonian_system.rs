@@ -10,11 +10,16 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{BosonHamiltonian, BosonSystem, HermitianBosonProduct, ModeIndex, OperateOnBosons};
+use super::{
+    BosonHamiltonian, BosonSystem, HermitianBosonProduct, ModeIndex, OperateOnBosons,
+    ToBosonSparseMatrixOperator, ToBosonSparseMatrixSuperOperator,
+};
 use crate::{OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -189,6 +194,29 @@ impl<'a> OperateOnModes<'a> for BosonHamiltonianSystem {
 
 impl<'a> OperateOnBosons<'a> for BosonHamiltonianSystem {}
 
+impl<'a> ToBosonSparseMatrixOperator<'a> for BosonHamiltonianSystem {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.hamiltonian.sparse_matrix_entries_on_row(row, cutoffs)
+    }
+}
+
+impl<'a> ToBosonSparseMatrixSuperOperator<'a> for BosonHamiltonianSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.hamiltonian
+            .sparse_matrix_superoperator_entries_on_row(row, cutoffs)
+    }
+}
+
 /// Functions for the BosonHamiltonianSystem
 ///
 impl BosonHamiltonianSystem {
@@ -10,8 +10,15 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{BosonProduct, OperateOnBosons};
-use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, StruqtureError, StruqtureVersion};
+use super::{
+    bosonic_lindblad_entries_on_row, BosonProduct, OperateOnBosons,
+    ToBosonSparseMatrixSuperOperator,
+};
+use crate::{
+    complex_value, ModeIndex, OperateOnDensityMatrix, OperateOnModes, StruqtureError,
+    StruqtureVersion,
+};
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -189,6 +196,22 @@ impl<'a> OperateOnModes<'a> for BosonLindbladNoiseOperator {
 
 impl<'a> OperateOnBosons<'a> for BosonLindbladNoiseOperator {}
 
+impl<'a> ToBosonSparseMatrixSuperOperator<'a> for BosonLindbladNoiseOperator {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        let mut terms: Vec<(BosonProduct, BosonProduct, Complex64)> =
+            Vec::with_capacity(self.len());
+        for ((left, right), rate) in self.iter() {
+            terms.push((left.clone(), right.clone(), complex_value(rate)?));
+        }
+        bosonic_lindblad_entries_on_row(&terms, row, cutoffs)
+    }
+}
+
 /// Implements the default function (Default trait) of BosonLindbladNoiseOperator (an empty BosonLindbladNoiseOperator).
 ///
 impl Default for BosonLindbladNoiseOperator {
@@ -10,11 +10,13 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{BosonLindbladNoiseOperator, OperateOnBosons};
+use super::{BosonLindbladNoiseOperator, OperateOnBosons, ToBosonSparseMatrixSuperOperator};
 use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::iter::{FromIterator, IntoIterator};
 use std::{
     fmt::{self, Write},
@@ -185,6 +187,18 @@ impl<'a> OperateOnModes<'a> for BosonLindbladNoiseSystem {
 
 impl<'a> OperateOnBosons<'a> for BosonLindbladNoiseSystem {}
 
+impl<'a> ToBosonSparseMatrixSuperOperator<'a> for BosonLindbladNoiseSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_superoperator_entries_on_row(row, cutoffs)
+    }
+}
+
 /// Functions for the BosonLindbladNoiseSystem.
 ///
 impl BosonLindbladNoiseSystem {
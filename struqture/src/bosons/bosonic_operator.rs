@@ -10,13 +10,19 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{BosonHamiltonian, OperateOnBosons};
+use super::{
+    boson_operator_terms, bosonic_commutator_entries_on_row, bosonic_entries_on_row,
+    BosonHamiltonian, OperateOnBosons, ToBosonSparseMatrixOperator,
+    ToBosonSparseMatrixSuperOperator,
+};
 use crate::bosons::BosonProduct;
 use crate::{
-    GetValue, ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError,
-    StruqtureVersion, SymmetricIndex,
+    commutation, mode_exchange_sign, parse_ladder_operators, GetValue, ModeIndex,
+    OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError, StruqtureVersion,
+    SymmetricIndex,
 };
 use itertools::Itertools;
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -186,6 +192,28 @@ impl<'a> OperateOnModes<'a> for BosonOperator {
 
 impl<'a> OperateOnBosons<'a> for BosonOperator {}
 
+impl<'a> ToBosonSparseMatrixOperator<'a> for BosonOperator {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        bosonic_entries_on_row(&boson_operator_terms(self)?, row, cutoffs)
+    }
+}
+
+impl<'a> ToBosonSparseMatrixSuperOperator<'a> for BosonOperator {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        bosonic_commutator_entries_on_row(&boson_operator_terms(self)?, row, cutoffs)
+    }
+}
+
 /// Implements the default function (Default trait) of BosonOperator (an empty BosonOperator).
 ///
 impl Default for BosonOperator {
@@ -222,6 +250,94 @@ impl BosonOperator {
             internal_map: HashMap::with_capacity(capacity),
         }
     }
+
+    /// Creates the normal ordered BosonOperator of a sequence of creation and annihilation operators.
+    ///
+    /// The operators are multiplied from left to right, creators and annihilators acting on the same mode
+    /// are exchanged using the bosonic commutation relations, e.g. `a1 c0 a0 c2` or `a0c0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `operators` - The sequence of operators, `c` for creators and `a` for annihilators followed by the mode index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The normal ordered BosonOperator equal to the product of the operators.
+    /// * `Err(StruqtureError::FromStringFailed)` - The sequence of operators could not be parsed.
+    pub fn normal_order(operators: &str) -> Result<Self, StruqtureError> {
+        let mut normal_ordered = BosonOperator::with_capacity(1);
+        normal_ordered.add_operator_product(BosonProduct::new([], [])?, 1.0.into())?;
+        for (is_creator, mode) in parse_ladder_operators(operators)? {
+            let product = if is_creator {
+                BosonProduct::new([mode], [])?
+            } else {
+                BosonProduct::new([], [mode])?
+            };
+            let mut factor = BosonOperator::with_capacity(1);
+            factor.add_operator_product(product, 1.0.into())?;
+            normal_ordered = normal_ordered * factor;
+        }
+        Ok(normal_ordered)
+    }
+
+    /// Computes the commutator `self * other - other * self`.
+    ///
+    /// Pairs of BosonProducts acting on disjoint modes commute and are only multiplied once.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The BosonOperator to commute with.
+    ///
+    /// # Returns
+    ///
+    /// * `BosonOperator` - The commutator of self and other.
+    pub fn commutator(&self, other: &BosonOperator) -> BosonOperator {
+        self.commutation(other, -1.0)
+    }
+
+    /// Computes the anticommutator `self * other + other * self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The BosonOperator to anticommute with.
+    ///
+    /// # Returns
+    ///
+    /// * `BosonOperator` - The anticommutator of self and other.
+    pub fn anticommutator(&self, other: &BosonOperator) -> BosonOperator {
+        self.commutation(other, 1.0)
+    }
+
+    /// Computes `self * other + sign * other * self` product by product.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The BosonOperator on the right-hand side.
+    /// * `sign` - The sign of the reversed product.
+    ///
+    /// # Returns
+    ///
+    /// * `BosonOperator` - The resulting BosonOperator.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn commutation(&self, other: &BosonOperator, sign: f64) -> BosonOperator {
+        commutation(
+            self,
+            other,
+            sign,
+            BosonOperator::new(),
+            |left, right| mode_exchange_sign(left, right, false),
+            |products| {
+                Ok(products
+                    .into_iter()
+                    .map(|product| (product, CalculatorComplex::from(1.0)))
+                    .collect())
+            },
+        )
+        .expect("Internal bug in add_operator_product")
+    }
 }
 
 impl From<BosonHamiltonian> for BosonOperator {
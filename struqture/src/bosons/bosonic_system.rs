@@ -10,12 +10,16 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{BosonOperator, OperateOnBosons};
+use super::{
+    BosonOperator, OperateOnBosons, ToBosonSparseMatrixOperator, ToBosonSparseMatrixSuperOperator,
+};
 use crate::bosons::BosonProduct;
 use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -181,6 +185,29 @@ impl<'a> OperateOnModes<'a> for BosonSystem {
 
 impl<'a> OperateOnBosons<'a> for BosonSystem {}
 
+impl<'a> ToBosonSparseMatrixOperator<'a> for BosonSystem {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator.sparse_matrix_entries_on_row(row, cutoffs)
+    }
+}
+
+impl<'a> ToBosonSparseMatrixSuperOperator<'a> for BosonSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_superoperator_entries_on_row(row, cutoffs)
+    }
+}
+
 /// Functions for the BosonSystem
 ///
 impl BosonSystem {
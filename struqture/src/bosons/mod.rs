@@ -37,9 +37,21 @@ pub use bosonic_open_system::BosonLindbladOpenSystem;
 pub use bosonic_operator::BosonOperator;
 pub use bosonic_system::BosonSystem;
 
-use crate::{ModeIndex, OperateOnDensityMatrix, SymmetricIndex};
+#[cfg(feature = "rayon")]
+use crate::coo_from_rows_parallel;
+use crate::iterative_solvers::steady_state;
+use crate::time_evolution::{krylov_evolution, runge_kutta_45_evolution};
+use crate::{
+    add_entry, apply_rows, check_density_matrix_length, complex_value, coo_from_rows,
+    csc_from_rows, csr_from_rows, dense_dimension, dense_from_rows, CooSparseMatrix,
+    CscSparseMatrix, CsrSparseMatrix, ModeIndex, OperateOnDensityMatrix, OperateOnModes,
+    StruqtureError, SymmetricIndex,
+};
 pub use bosonic_indices::{BosonProduct, HermitianBosonProduct};
+use ndarray::Array2;
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
+use std::collections::HashMap;
 
 /// Signal Trait for specifying that a type can be used a bosonic index.
 ///
@@ -94,3 +106,837 @@ where
     &'a Self: IntoIterator,
 {
 }
+
+/// Occupation cutoff used to truncate the bosonic Fock space when constructing matrices.
+///
+/// The cutoff is the maximal occupation number of a mode, so a mode with cutoff `n_max`
+/// is represented by the `n_max + 1` Fock states |0>, ..., |n_max>.
+///
+/// # Example
+/// ```
+/// use struqture::bosons::BosonCutoff;
+///
+/// let global = BosonCutoff::Global(3);
+/// let per_mode = BosonCutoff::PerMode(vec![3, 1]);
+/// assert_eq!(global.mode_cutoffs(2), vec![3, 3]);
+/// assert_eq!(per_mode.mode_cutoffs(2), vec![3, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BosonCutoff {
+    /// The same maximal occupation for every mode.
+    Global(usize),
+    /// The maximal occupation of each mode, the length determines the number of modes.
+    PerMode(Vec<usize>),
+}
+
+impl BosonCutoff {
+    /// Returns the maximal occupation of each mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of modes used for a global cutoff.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The maximal occupation of each mode.
+    pub fn mode_cutoffs(&self, number_modes: usize) -> Vec<usize> {
+        match self {
+            BosonCutoff::Global(cutoff) => vec![*cutoff; number_modes],
+            BosonCutoff::PerMode(cutoffs) => cutoffs.clone(),
+        }
+    }
+}
+
+impl From<usize> for BosonCutoff {
+    fn from(cutoff: usize) -> Self {
+        BosonCutoff::Global(cutoff)
+    }
+}
+
+impl From<Vec<usize>> for BosonCutoff {
+    fn from(cutoffs: Vec<usize>) -> Self {
+        BosonCutoff::PerMode(cutoffs)
+    }
+}
+
+/// Trait for constructing the sparse matrix superoperator of bosonic objects in the truncated Fock basis.
+///
+/// Mode `j` with maximal occupation `n_max_j` has the local dimension `d_j = n_max_j + 1`.
+/// The basis state with occupations (n_0, n_1, ...) has the index `n_0 + d_0 * (n_1 + d_1 * (n_2 + ...))`,
+/// so mode 0 is the fastest changing index.
+/// Creators and annihilators act as b^† |n> = sqrt(n + 1) |n + 1> and b |n> = sqrt(n) |n - 1>,
+/// matrix elements leading out of the truncated space are dropped.
+///
+/// The superoperator for the operator O is defined as the Matrix S so that
+/// `flatten(-i [O, p]) = S flatten(p)` where `[,]` is the commutator, `p` is a matrix
+/// and `flatten` flattens a matrix into a vector in row-major form.
+/// For noise and open systems the Lindblad terms are included in the same row-major convention.
+pub trait ToBosonSparseMatrixSuperOperator<'a>: OperateOnModes<'a> + PartialEq + Clone {
+    /// Constructs the sparse matrix representation of the superoperator in COO representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The occupation cutoff, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_coo(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CooSparseMatrix, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dimension(&cutoffs);
+        coo_from_rows(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in COO representation, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToBosonSparseMatrixSuperOperator::sparse_matrix_superoperator_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The occupation cutoff, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_superoperator_coo_parallel(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dimension(&cutoffs);
+        coo_from_rows_parallel(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in the truncated Fock space in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csr(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        csr_from_rows(truncated_dimension(&cutoffs).pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in the truncated Fock space in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csc(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        csc_from_rows(truncated_dimension(&cutoffs).pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the dense matrix representation of the superoperator in the truncated Fock space.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_superoperator(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dense_dimension(&cutoffs, 2)?;
+        dense_from_rows(dimension, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix entries for one row of the sparse matrix superoperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row for which to get the superoperator entries.
+    /// * `cutoffs` - The maximal occupation of each mode, must cover all modes Self acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError>;
+
+    /// Applies the superoperator in the truncated Fock space to a flattened density matrix without constructing its matrix.
+    ///
+    /// The density matrix is flattened in row-major form, as for the sparse matrix representation of the superoperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix the superoperator is applied to.
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix after the application of the superoperator.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length of the density matrix is not the square of the dimension of the truncated Fock space.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+        cutoff: &BosonCutoff,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        check_density_matrix_length(density_matrix.len(), truncated_dimension(&cutoffs))?;
+        apply_rows(density_matrix, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Evolves a flattened density matrix under the superoperator in the truncated Fock space with the adaptive RK45 method.
+    ///
+    /// The density matrix p(t) solves `d/dt flatten(p) = S flatten(p)` for the superoperator S of
+    /// [ToBosonSparseMatrixSuperOperator::sparse_matrix_superoperator_coo], with `flatten` the row-major flattening.
+    /// The equation is integrated with the Dormand-Prince method, applying the superoperator
+    /// without constructing its matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    /// * `time` - The evolution time.
+    /// * `tolerance` - The (positive) tolerance of the local error of each integration step, relative to the magnitude of the entries.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length of the density matrix is not the square of the dimension of the truncated Fock space.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix(
+        &'a self,
+        density_matrix: &[Complex64],
+        cutoff: &BosonCutoff,
+        time: f64,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        runge_kutta_45_evolution(
+            |state| self.apply_superoperator(state, cutoff),
+            density_matrix,
+            time,
+            tolerance,
+        )
+    }
+
+    /// Evolves a flattened density matrix under the superoperator in the truncated Fock space with Krylov subspace exponentiation.
+    ///
+    /// The density matrix p(t) = exp(t S) p(0) is computed for the superoperator S of
+    /// [ToBosonSparseMatrixSuperOperator::sparse_matrix_superoperator_coo], with p flattened in row-major form.
+    /// The exponential is approximated in Krylov subspaces constructed by applying the superoperator
+    /// without constructing its matrix, splitting the evolution time into steps to reach the tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    /// * `time` - The evolution time.
+    /// * `krylov_dimension` - The maximal dimension of the Krylov subspaces.
+    /// * `tolerance` - The (positive) tolerance of the error relative to the norm of the density matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length of the density matrix is not the square of the dimension of the truncated Fock space.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix_krylov(
+        &'a self,
+        density_matrix: &[Complex64],
+        cutoff: &BosonCutoff,
+        time: f64,
+        krylov_dimension: usize,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        check_density_matrix_length(density_matrix.len(), truncated_dimension(&cutoffs))?;
+        krylov_evolution(
+            |state| self.apply_superoperator(state, cutoff),
+            density_matrix,
+            time,
+            krylov_dimension,
+            tolerance,
+        )
+    }
+
+    /// Returns the steady state of the superoperator in the truncated Fock space.
+    ///
+    /// The steady state is the density matrix p with `S flatten(p) = 0` and `Tr(p) = 1` for the
+    /// superoperator S of [ToBosonSparseMatrixSuperOperator::sparse_matrix_superoperator_coo].
+    /// Adding the rank-one term |v><t| to S, with the maximally mixed state v and the trace functional t,
+    /// removes the null space of S, and the resulting linear system `(S + |v><t|) flatten(p) = v`
+    /// is solved with the restarted GMRES method, applying the superoperator without constructing its matrix.
+    /// The steady state is assumed to be unique.
+    ///
+    /// The steady state is returned as a dense matrix, so the dimension of the Hilbert space
+    /// is limited to [crate::MAXIMAL_DENSE_DIMENSION] like for [ToBosonSparseMatrixOperator::dense_matrix].
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    /// * `tolerance` - The tolerance of the residual of the linear system relative to the norm of v.
+    /// * `maximal_iterations` - The maximal number of applications of the superoperator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense steady-state density matrix.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(StruqtureError::NoConvergence)` - The solver did not converge within the maximal number of iterations.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn steady_state(
+        &'a self,
+        cutoff: &BosonCutoff,
+        tolerance: f64,
+        maximal_iterations: usize,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dense_dimension(&cutoffs, 1)?;
+        steady_state(
+            |vector| self.apply_superoperator(vector, cutoff),
+            dimension,
+            tolerance,
+            maximal_iterations,
+        )
+    }
+}
+
+/// Trait for constructing the sparse matrix of bosonic operators in the truncated Fock basis.
+///
+/// Mode `j` with maximal occupation `n_max_j` has the local dimension `d_j = n_max_j + 1`.
+/// The basis state with occupations (n_0, n_1, ...) has the index `n_0 + d_0 * (n_1 + d_1 * (n_2 + ...))`,
+/// so mode 0 is the fastest changing index.
+/// Creators and annihilators act as b^† |n> = sqrt(n + 1) |n + 1> and b |n> = sqrt(n) |n - 1>,
+/// matrix elements leading out of the truncated space are dropped.
+///
+/// # Example
+/// ```
+/// use struqture::prelude::*;
+/// use qoqo_calculator::CalculatorComplex;
+/// use struqture::bosons::{BosonCutoff, BosonProduct, BosonSystem, ToBosonSparseMatrixOperator};
+/// use num_complex::Complex64;
+///
+/// let mut system = BosonSystem::new(Some(1));
+/// system.set(BosonProduct::new([0], []).unwrap(), CalculatorComplex::from(1.0)).unwrap();
+///
+/// // b^† maps |0> onto |1> and |1> onto sqrt(2) |2>
+/// let (values, (rows, columns)) = system.sparse_matrix_coo(&BosonCutoff::Global(2)).unwrap();
+/// assert_eq!(rows, vec![1, 2]);
+/// assert_eq!(columns, vec![0, 1]);
+/// assert_eq!(values, vec![Complex64::new(1.0, 0.0), Complex64::new(2.0_f64.sqrt(), 0.0)]);
+/// ```
+pub trait ToBosonSparseMatrixOperator<'a>: ToBosonSparseMatrixSuperOperator<'a> {
+    /// Constructs the sparse matrix representation of Self as a COO matrix with a given cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The occupation cutoff, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_coo(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CooSparseMatrix, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dimension(&cutoffs);
+        coo_from_rows(dimension, |row| {
+            self.sparse_matrix_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self as a COO matrix with a given cutoff, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToBosonSparseMatrixOperator::sparse_matrix_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The occupation cutoff, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_coo_parallel(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dimension(&cutoffs);
+        coo_from_rows_parallel(dimension, |row| {
+            self.sparse_matrix_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self in the truncated Fock space in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csr(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        csr_from_rows(truncated_dimension(&cutoffs), |row| {
+            self.sparse_matrix_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self in the truncated Fock space in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csc(
+        &'a self,
+        cutoff: &BosonCutoff,
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        csc_from_rows(truncated_dimension(&cutoffs), |row| {
+            self.sparse_matrix_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the dense matrix representation of Self in the truncated Fock space.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The maximal occupation of the bosonic modes, globally or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_matrix(&'a self, cutoff: &BosonCutoff) -> Result<Array2<Complex64>, StruqtureError> {
+        let cutoffs =
+            checked_mode_cutoffs(cutoff, self.number_modes(), self.current_number_modes())?;
+        let dimension = truncated_dense_dimension(&cutoffs, 1)?;
+        dense_from_rows(dimension, |row| {
+            self.sparse_matrix_entries_on_row(row, &cutoffs)
+        })
+    }
+
+    /// Constructs the sparse matrix entries for one row of the sparse matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row for which to get the entries.
+    /// * `cutoffs` - The maximal occupation of each mode, must cover all modes Self acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        cutoffs: &[usize],
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError>;
+}
+
+/// Resolves a BosonCutoff into the maximal occupation of each mode and checks that all modes are covered.
+///
+/// # Arguments
+///
+/// * `cutoff` - The occupation cutoff, either global or per mode.
+/// * `number_modes` - The number of modes used for a global cutoff.
+/// * `current_number_modes` - The number of modes the object acts on.
+///
+/// # Returns
+///
+/// * `Ok(Vec<usize>)` - The maximal occupation of each mode.
+/// * `Err(StruqtureError::NumberModesExceeded)` - The per-mode cutoff covers fewer modes than the object acts on.
+pub(crate) fn checked_mode_cutoffs(
+    cutoff: &BosonCutoff,
+    number_modes: usize,
+    current_number_modes: usize,
+) -> Result<Vec<usize>, StruqtureError> {
+    let cutoffs = cutoff.mode_cutoffs(number_modes);
+    if cutoffs.len() < current_number_modes {
+        return Err(StruqtureError::NumberModesExceeded);
+    }
+    Ok(cutoffs)
+}
+
+/// Returns the dimension of the truncated Fock space.
+///
+/// # Arguments
+///
+/// * `cutoffs` - The maximal occupation of each mode.
+///
+/// # Returns
+///
+/// * `usize` - The product of the local dimensions `n_max_j + 1`.
+pub(crate) fn truncated_dimension(cutoffs: &[usize]) -> usize {
+    cutoffs.iter().map(|cutoff| cutoff + 1).product()
+}
+
+/// Returns the dimension of a dense matrix acting on `copies` copies of the truncated Fock space.
+///
+/// # Arguments
+///
+/// * `cutoffs` - The maximal occupation of each mode.
+/// * `copies` - The number of copies of the truncated Fock space (1 for operators, 2 for superoperators).
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The dimension of the dense matrix.
+/// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+/// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+fn truncated_dense_dimension(cutoffs: &[usize], copies: u32) -> Result<usize, StruqtureError> {
+    dense_dimension(
+        cutoffs.iter().map(|cutoff| cutoff.saturating_add(1)),
+        copies,
+        0,
+        cutoffs.len(),
+    )
+}
+
+/// Applies a BosonProduct or its hermitian conjugate to a truncated Fock basis state.
+///
+/// # Arguments
+///
+/// * `product` - The BosonProduct P to apply.
+/// * `state` - The index of the basis state.
+/// * `cutoffs` - The maximal occupation of each mode.
+/// * `adjoint` - Whether to apply the hermitian conjugate P^† instead of P.
+///
+/// # Returns
+///
+/// * `Ok(Some((usize, f64)))` - The index of the resulting basis state and the matrix element.
+/// * `Ok(None)` - The product annihilates the basis state or leads out of the truncated space.
+/// * `Err(StruqtureError::NumberModesExceeded)` - The product acts on a mode without a cutoff.
+pub(crate) fn apply_boson_product(
+    product: &BosonProduct,
+    state: usize,
+    cutoffs: &[usize],
+    adjoint: bool,
+) -> Result<Option<(usize, f64)>, StruqtureError> {
+    if product.current_number_modes() > cutoffs.len() {
+        return Err(StruqtureError::NumberModesExceeded);
+    }
+    let mut occupations: Vec<usize> = Vec::with_capacity(cutoffs.len());
+    let mut remainder = state;
+    for cutoff in cutoffs {
+        occupations.push(remainder % (cutoff + 1));
+        remainder /= cutoff + 1;
+    }
+    // Operators on different modes commute, annihilators act before creators in both P and P^†
+    let (annihilated, created) = if adjoint {
+        (product.creators(), product.annihilators())
+    } else {
+        (product.annihilators(), product.creators())
+    };
+    // Accumulating the squared matrix element keeps n = sqrt(n) * sqrt(n) exact
+    let mut factor_squared: usize = 1;
+    for mode in annihilated {
+        let occupation = &mut occupations[*mode];
+        if *occupation == 0 {
+            return Ok(None);
+        }
+        factor_squared *= *occupation;
+        *occupation -= 1;
+    }
+    for mode in created {
+        let occupation = &mut occupations[*mode];
+        if *occupation == cutoffs[*mode] {
+            return Ok(None);
+        }
+        *occupation += 1;
+        factor_squared *= *occupation;
+    }
+    let mut new_state = 0;
+    for (occupation, cutoff) in occupations.iter().zip(cutoffs.iter()).rev() {
+        new_state = new_state * (cutoff + 1) + occupation;
+    }
+    Ok(Some((new_state, (factor_squared as f64).sqrt())))
+}
+
+/// Collects the terms of a BosonOperator as (P, adjoint, value) for the sparse matrix construction.
+///
+/// # Arguments
+///
+/// * `operator` - The BosonOperator to collect the terms of.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(BosonProduct, bool, Complex64)>)` - The terms value * P of the operator.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn boson_operator_terms(
+    operator: &BosonOperator,
+) -> Result<Vec<(BosonProduct, bool, Complex64)>, StruqtureError> {
+    operator
+        .iter()
+        .map(|(product, value)| Ok((product.clone(), false, complex_value(value)?)))
+        .collect()
+}
+
+/// Collects the terms of a BosonHamiltonian as (P, adjoint, value) for the sparse matrix construction.
+///
+/// Every key K with value v stands for v * K + v^* * K^†, where the hermitian conjugate is only
+/// added when K is not naturally hermitian.
+///
+/// # Arguments
+///
+/// * `hamiltonian` - The BosonHamiltonian to collect the terms of.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(BosonProduct, bool, Complex64)>)` - The terms of the hamiltonian.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn boson_hamiltonian_terms(
+    hamiltonian: &BosonHamiltonian,
+) -> Result<Vec<(BosonProduct, bool, Complex64)>, StruqtureError> {
+    let mut terms: Vec<(BosonProduct, bool, Complex64)> = Vec::with_capacity(2 * hamiltonian.len());
+    for (hermitian_product, value) in hamiltonian.iter() {
+        let product = BosonProduct::new(
+            hermitian_product.creators().copied(),
+            hermitian_product.annihilators().copied(),
+        )?;
+        let value = complex_value(value)?;
+        if !hermitian_product.is_natural_hermitian() {
+            terms.push((product.clone(), true, value.conj()));
+        }
+        terms.push((product, false, value));
+    }
+    Ok(terms)
+}
+
+/// Constructs the entries of one row of the sparse matrix of a sum of bosonic terms.
+///
+/// # Arguments
+///
+/// * `terms` - The terms (P, adjoint, value) representing value * P or value * P^† if adjoint is true.
+/// * `row` - The row for which to get the entries.
+/// * `cutoffs` - The maximal occupation of each mode.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+/// * `Err(StruqtureError::NumberModesExceeded)` - A product acts on a mode without a cutoff.
+pub(crate) fn bosonic_entries_on_row(
+    terms: &[(BosonProduct, bool, Complex64)],
+    row: usize,
+    cutoffs: &[usize],
+) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(terms.len());
+    for (product, adjoint, value) in terms {
+        // <row| T = (T^† |row>)^†, the matrix elements are real
+        if let Some((column, element)) = apply_boson_product(product, row, cutoffs, !adjoint)? {
+            add_entry(&mut entries, column, *value * element);
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    Ok(entries)
+}
+
+/// Constructs the entries of one row of the commutator superoperator -i [O, p] of a sum of bosonic terms.
+///
+/// # Arguments
+///
+/// * `terms` - The terms (P, adjoint, value) representing value * P or value * P^† if adjoint is true.
+/// * `row` - The row for which to get the superoperator entries.
+/// * `cutoffs` - The maximal occupation of each mode.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+/// * `Err(StruqtureError::NumberModesExceeded)` - A product acts on a mode without a cutoff.
+pub(crate) fn bosonic_commutator_entries_on_row(
+    terms: &[(BosonProduct, bool, Complex64)],
+    row: usize,
+    cutoffs: &[usize],
+) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+    let dimension = truncated_dimension(cutoffs);
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(2 * terms.len());
+    let left_index = row.div_euclid(dimension);
+    let right_index = row % dimension;
+    let constant_prefactor = Complex64::new(0.0, -1.0);
+    for (product, adjoint, value) in terms {
+        // -i O p => -i O.kron(I) flatten(p) uses the row left_index of O
+        if let Some((column, element)) =
+            apply_boson_product(product, left_index, cutoffs, !adjoint)?
+        {
+            add_entry(
+                &mut entries,
+                column * dimension + right_index,
+                constant_prefactor * *value * element,
+            );
+        }
+        // i p O => i I.kron(O.T) flatten(p) uses the column right_index of O
+        if let Some((column, element)) =
+            apply_boson_product(product, right_index, cutoffs, *adjoint)?
+        {
+            add_entry(
+                &mut entries,
+                left_index * dimension + column,
+                -constant_prefactor * *value * element,
+            );
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    Ok(entries)
+}
+
+/// Constructs the entries of one row of the Lindblad superoperator of bosonic noise terms.
+///
+/// Each term ((L, R), rate) contributes rate * (L p R^† - 1/2 R^† L p - 1/2 p R^† L).
+///
+/// # Arguments
+///
+/// * `terms` - The noise terms (L, R, rate).
+/// * `row` - The row for which to get the superoperator entries.
+/// * `cutoffs` - The maximal occupation of each mode.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+/// * `Err(StruqtureError::NumberModesExceeded)` - A product acts on a mode without a cutoff.
+pub(crate) fn bosonic_lindblad_entries_on_row(
+    terms: &[(BosonProduct, BosonProduct, Complex64)],
+    row: usize,
+    cutoffs: &[usize],
+) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+    let dimension = truncated_dimension(cutoffs);
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(3 * terms.len());
+    let left_index = row.div_euclid(dimension);
+    let right_index = row % dimension;
+    for (left, right, rate) in terms {
+        // L p R^† uses the row left_index of L and the row right_index of R (conjugated, the elements are real)
+        if let (Some((column_left, element_left)), Some((column_right, element_right))) = (
+            apply_boson_product(left, left_index, cutoffs, true)?,
+            apply_boson_product(right, right_index, cutoffs, true)?,
+        ) {
+            add_entry(
+                &mut entries,
+                column_left * dimension + column_right,
+                *rate * element_left * element_right,
+            );
+        }
+        // - 1/2 R^† L p uses the row left_index of R^† L: <left_index| R^† = (R |left_index>)^†
+        if let Some((intermediate, element_right)) =
+            apply_boson_product(right, left_index, cutoffs, false)?
+        {
+            if let Some((column, element_left)) =
+                apply_boson_product(left, intermediate, cutoffs, true)?
+            {
+                add_entry(
+                    &mut entries,
+                    column * dimension + right_index,
+                    *rate * (-0.5 * element_left * element_right),
+                );
+            }
+        }
+        // - 1/2 p R^† L uses the column right_index of R^† L
+        if let Some((intermediate, element_left)) =
+            apply_boson_product(left, right_index, cutoffs, false)?
+        {
+            if let Some((column, element_right)) =
+                apply_boson_product(right, intermediate, cutoffs, true)?
+            {
+                add_entry(
+                    &mut entries,
+                    left_index * dimension + column,
+                    *rate * (-0.5 * element_left * element_right),
+                );
+            }
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    Ok(entries)
+}
@@ -0,0 +1,407 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    FermionHamiltonian, FermionHamiltonianSystem, FermionOperator, FermionProduct, FermionSystem,
+};
+use crate::spins::{
+    PauliProduct, SpinHamiltonian, SpinHamiltonianSystem, SpinOperator, SpinSystem,
+};
+use crate::{
+    GetValue, ModeIndex, OperateOnDensityMatrix, OperateOnModes, SpinIndex, StruqtureError,
+    SymmetricIndex,
+};
+use qoqo_calculator::CalculatorComplex;
+use std::collections::{HashMap, HashSet};
+
+/// Trait for encodings of fermionic modes into qubits (spins).
+///
+/// An encoding is defined by the two Majorana operators of each fermionic mode j,
+/// γ_{2j} = c_j + c_j^† and γ_{2j+1} = i(c_j^† - c_j), which are represented by single PauliProducts.
+/// The creation and annihilation operators are then given by c_j^† = (γ_{2j} - iγ_{2j+1})/2 and c_j = (γ_{2j} + iγ_{2j+1})/2.
+///
+/// Optionally, a mode ordering can be provided when encoding operators. It maps the fermionic modes (keys) onto
+/// the positions in the encoding (values) and has to be a permutation of its keys.
+/// Modes that are not in the keys of the mapping keep their position.
+///
+/// # Example
+///
+/// ```
+/// use struqture::prelude::*;
+/// use struqture::fermions::{BravyiKitaevEncoding, FermionOperator, FermionProduct, FermionQubitEncoding};
+/// use struqture::spins::PauliProduct;
+/// use qoqo_calculator::CalculatorComplex;
+///
+/// let mut fo = FermionOperator::new();
+/// fo.add_operator_product(FermionProduct::new([1], [1]).unwrap(), CalculatorComplex::from(1.0)).unwrap();
+/// let so = BravyiKitaevEncoding.encode_fermion_operator(&fo, 4, None).unwrap();
+///
+/// assert_eq!(so.get(&PauliProduct::new()), &CalculatorComplex::from(0.5));
+/// assert_eq!(so.get(&PauliProduct::new().z(0).z(1)), &CalculatorComplex::from(-0.5));
+/// ```
+pub trait FermionQubitEncoding {
+    /// Returns the Majorana operators of a fermionic mode in the encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The position of the fermionic mode in the encoding.
+    /// * `number_modes` - The total number of fermionic modes (and qubits) of the encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `(PauliProduct, PauliProduct)` - The Majorana operators γ_{2j} and γ_{2j+1} of the mode.
+    fn majorana_operators(&self, mode: usize, number_modes: usize) -> (PauliProduct, PauliProduct);
+
+    /// Encodes a FermionProduct into a SpinOperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `product` - The FermionProduct to encode.
+    /// * `number_modes` - The total number of fermionic modes (and qubits) of the encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SpinOperator)` - The encoded FermionProduct.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - Index of the FermionProduct exceeds the number of modes of the encoding.
+    fn encode_fermion_product(
+        &self,
+        product: &FermionProduct,
+        number_modes: usize,
+    ) -> Result<SpinOperator, StruqtureError> {
+        if product.current_number_modes() > number_modes {
+            return Err(StruqtureError::NumberModesExceeded);
+        }
+        let mut spin_operator = SpinOperator::new();
+        spin_operator.set(PauliProduct::new(), CalculatorComplex::from(1.0))?;
+        for creator in product.creators() {
+            let (gamma_even, gamma_odd) = self.majorana_operators(*creator, number_modes);
+            let mut single_mode = SpinOperator::with_capacity(2);
+            single_mode.set(gamma_even, CalculatorComplex::from(0.5))?;
+            single_mode.add_operator_product(gamma_odd, CalculatorComplex::new(0.0, -0.5))?;
+            spin_operator = spin_operator * single_mode;
+        }
+        for annihilator in product.annihilators() {
+            let (gamma_even, gamma_odd) = self.majorana_operators(*annihilator, number_modes);
+            let mut single_mode = SpinOperator::with_capacity(2);
+            single_mode.set(gamma_even, CalculatorComplex::from(0.5))?;
+            single_mode.add_operator_product(gamma_odd, CalculatorComplex::new(0.0, 0.5))?;
+            spin_operator = spin_operator * single_mode;
+        }
+        Ok(spin_operator)
+    }
+
+    /// Encodes a FermionOperator into a SpinOperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator` - The FermionOperator to encode.
+    /// * `number_modes` - The total number of fermionic modes (and qubits) of the encoding.
+    /// * `mode_ordering` - The optional mapping of the fermionic modes onto their positions in the encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SpinOperator)` - The encoded FermionOperator.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - Index of the (reordered) FermionOperator exceeds the number of modes of the encoding.
+    /// * `Err(StruqtureError::RemappingFailed)` - The mode ordering is not a permutation of its keys.
+    fn encode_fermion_operator(
+        &self,
+        operator: &FermionOperator,
+        number_modes: usize,
+        mode_ordering: Option<&HashMap<usize, usize>>,
+    ) -> Result<SpinOperator, StruqtureError> {
+        check_mode_ordering(mode_ordering)?;
+        let mut spin_operator = SpinOperator::new();
+        for (product, value) in operator.iter() {
+            let (product, value) = reorder_product(product, value.clone(), mode_ordering)?;
+            for (pauli_product, coefficient) in
+                self.encode_fermion_product(&product, number_modes)?
+            {
+                spin_operator.add_operator_product(pauli_product, coefficient * value.clone())?;
+            }
+        }
+        Ok(spin_operator)
+    }
+
+    /// Encodes a FermionHamiltonian into a SpinHamiltonian.
+    ///
+    /// # Arguments
+    ///
+    /// * `hamiltonian` - The FermionHamiltonian to encode.
+    /// * `number_modes` - The total number of fermionic modes (and qubits) of the encoding.
+    /// * `mode_ordering` - The optional mapping of the fermionic modes onto their positions in the encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SpinHamiltonian)` - The encoded FermionHamiltonian.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - Index of the (reordered) FermionHamiltonian exceeds the number of modes of the encoding.
+    /// * `Err(StruqtureError::RemappingFailed)` - The mode ordering is not a permutation of its keys.
+    fn encode_fermion_hamiltonian(
+        &self,
+        hamiltonian: &FermionHamiltonian,
+        number_modes: usize,
+        mode_ordering: Option<&HashMap<usize, usize>>,
+    ) -> Result<SpinHamiltonian, StruqtureError> {
+        check_mode_ordering(mode_ordering)?;
+        let mut spin_hamiltonian = SpinHamiltonian::new();
+        for (hermitian_product, value) in hamiltonian.iter() {
+            // The encoded hermitian conjugate has the conjugated coefficients of the encoded product,
+            // as PauliProducts are hermitian. Only the real parts of the coefficients survive.
+            let prefactor = if hermitian_product.is_natural_hermitian() {
+                1.0
+            } else {
+                2.0
+            };
+            let product = FermionProduct::get_key(hermitian_product);
+            let (product, value) = reorder_product(&product, value.clone(), mode_ordering)?;
+            for (pauli_product, coefficient) in
+                self.encode_fermion_product(&product, number_modes)?
+            {
+                spin_hamiltonian.add_operator_product(
+                    pauli_product,
+                    (coefficient * value.clone()).re * prefactor,
+                )?;
+            }
+        }
+        Ok(spin_hamiltonian)
+    }
+
+    /// Encodes a FermionSystem into a SpinSystem.
+    ///
+    /// The number of modes of the FermionSystem is used as the number of modes of the encoding
+    /// and the number of spins of the SpinSystem is set to the number of modes of the FermionSystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - The FermionSystem to encode.
+    /// * `mode_ordering` - The optional mapping of the fermionic modes onto their positions in the encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SpinSystem)` - The encoded FermionSystem.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - Index of the (reordered) FermionSystem exceeds the number of modes of the FermionSystem.
+    /// * `Err(StruqtureError::RemappingFailed)` - The mode ordering is not a permutation of its keys.
+    fn encode_fermion_system(
+        &self,
+        system: &FermionSystem,
+        mode_ordering: Option<&HashMap<usize, usize>>,
+    ) -> Result<SpinSystem, StruqtureError> {
+        let operator =
+            self.encode_fermion_operator(system.operator(), system.number_modes(), mode_ordering)?;
+        SpinSystem::from_operator(operator, system.number_modes)
+    }
+
+    /// Encodes a FermionHamiltonianSystem into a SpinHamiltonianSystem.
+    ///
+    /// The number of modes of the FermionHamiltonianSystem is used as the number of modes of the encoding
+    /// and the number of spins of the SpinHamiltonianSystem is set to the number of modes of the FermionHamiltonianSystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - The FermionHamiltonianSystem to encode.
+    /// * `mode_ordering` - The optional mapping of the fermionic modes onto their positions in the encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SpinHamiltonianSystem)` - The encoded FermionHamiltonianSystem.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - Index of the (reordered) FermionHamiltonianSystem exceeds the number of modes of the FermionHamiltonianSystem.
+    /// * `Err(StruqtureError::RemappingFailed)` - The mode ordering is not a permutation of its keys.
+    fn encode_fermion_hamiltonian_system(
+        &self,
+        system: &FermionHamiltonianSystem,
+        mode_ordering: Option<&HashMap<usize, usize>>,
+    ) -> Result<SpinHamiltonianSystem, StruqtureError> {
+        let hamiltonian = self.encode_fermion_hamiltonian(
+            system.hamiltonian(),
+            system.number_modes(),
+            mode_ordering,
+        )?;
+        SpinHamiltonianSystem::from_hamiltonian(hamiltonian, system.number_modes)
+    }
+}
+
+/// The Jordan-Wigner encoding.
+///
+/// Qubit j stores the occupation of mode j, with the Majorana operators
+/// γ_{2j} = Z_0...Z_{j-1} X_j and γ_{2j+1} = Z_0...Z_{j-1} Y_j.
+/// The transformations of [crate::mappings::JordanWignerFermionToSpin] use this encoding without a mode ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JordanWignerEncoding;
+
+impl FermionQubitEncoding for JordanWignerEncoding {
+    // From trait
+    fn majorana_operators(&self, mode: usize, number_modes: usize) -> (PauliProduct, PauliProduct) {
+        majorana_operators_from_sets(
+            mode,
+            &[],
+            &(0..mode).collect::<Vec<usize>>(),
+            &[],
+            number_modes,
+        )
+    }
+}
+
+/// The parity encoding.
+///
+/// Qubit j stores the parity of the modes 0 to j, with the Majorana operators
+/// γ_{2j} = X_{n-1}...X_{j+1} X_j Z_{j-1} and γ_{2j+1} = X_{n-1}...X_{j+1} Y_j.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParityEncoding;
+
+impl FermionQubitEncoding for ParityEncoding {
+    // From trait
+    fn majorana_operators(&self, mode: usize, number_modes: usize) -> (PauliProduct, PauliProduct) {
+        let update_set: Vec<usize> = (mode + 1..number_modes).collect();
+        let parity_set: Vec<usize> = if mode > 0 { vec![mode - 1] } else { vec![] };
+        majorana_operators_from_sets(mode, &update_set, &parity_set, &parity_set, number_modes)
+    }
+}
+
+/// The Bravyi-Kitaev encoding.
+///
+/// Qubit j stores the partial sum of occupations given by the Fenwick tree of the modes,
+/// which for 2^k modes coincides with the original Bravyi-Kitaev transformation.
+/// The Majorana operators are γ_{2j} = X_{U(j)} X_j Z_{P(j)} and γ_{2j+1} = X_{U(j)} Y_j Z_{R(j)},
+/// where U(j) is the update set, P(j) the parity set and R(j) the remainder set of mode j.
+/// The Pauli weight of the encoded creators and annihilators is logarithmic in the number of modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BravyiKitaevEncoding;
+
+impl FermionQubitEncoding for BravyiKitaevEncoding {
+    // From trait
+    fn majorana_operators(&self, mode: usize, number_modes: usize) -> (PauliProduct, PauliProduct) {
+        // The Fenwick tree uses one-based indices i = mode + 1
+        let lowest_bit = |index: usize| index & index.wrapping_neg();
+
+        // Qubits whose partial sums contain the occupation of the mode
+        let mut update_set: Vec<usize> = Vec::new();
+        let mut index = mode + 1;
+        index += lowest_bit(index);
+        while index <= number_modes {
+            update_set.push(index - 1);
+            index += lowest_bit(index);
+        }
+
+        // Qubits whose partial sums give the parity of all modes below the mode
+        let mut parity_set: Vec<usize> = Vec::new();
+        // Qubits whose partial sums give the parity of the modes stored together with the mode
+        let mut flip_set: Vec<usize> = Vec::new();
+        let flip_boundary = mode + 1 - lowest_bit(mode + 1);
+        let mut index = mode;
+        while index > 0 {
+            parity_set.push(index - 1);
+            if index > flip_boundary {
+                flip_set.push(index - 1);
+            }
+            index -= lowest_bit(index);
+        }
+        majorana_operators_from_sets(mode, &update_set, &parity_set, &flip_set, number_modes)
+    }
+}
+
+/// Constructs the Majorana operators of a mode from its update, parity and flip set.
+///
+/// # Arguments
+///
+/// * `mode` - The position of the fermionic mode in the encoding.
+/// * `update_set` - The qubits that need to be flipped when the occupation of the mode changes.
+/// * `parity_set` - The qubits that store the parity of all modes below the mode.
+/// * `flip_set` - The qubits that determine together with qubit `mode` the occupation of the mode.
+/// * `number_modes` - The total number of fermionic modes (and qubits) of the encoding.
+///
+/// # Returns
+///
+/// * `(PauliProduct, PauliProduct)` - The Majorana operators γ_{2j} = X_U X_j Z_P and γ_{2j+1} = X_U Y_j Z_{P\F}.
+fn majorana_operators_from_sets(
+    mode: usize,
+    update_set: &[usize],
+    parity_set: &[usize],
+    flip_set: &[usize],
+    number_modes: usize,
+) -> (PauliProduct, PauliProduct) {
+    let mut gamma_even = PauliProduct::with_capacity(number_modes);
+    let mut gamma_odd = PauliProduct::with_capacity(number_modes);
+    for qubit in update_set {
+        gamma_even = gamma_even.x(*qubit);
+        gamma_odd = gamma_odd.x(*qubit);
+    }
+    gamma_even = gamma_even.x(mode);
+    gamma_odd = gamma_odd.y(mode);
+    for qubit in parity_set {
+        gamma_even = gamma_even.z(*qubit);
+        if !flip_set.contains(qubit) {
+            gamma_odd = gamma_odd.z(*qubit);
+        }
+    }
+    (gamma_even, gamma_odd)
+}
+
+/// Checks that a mode ordering is a permutation of its keys.
+///
+/// # Arguments
+///
+/// * `mode_ordering` - The optional mapping of the fermionic modes onto their positions in the encoding.
+///
+/// # Returns
+///
+/// * `Ok(())` - The mode ordering is valid.
+/// * `Err(StruqtureError::RemappingFailed)` - The mode ordering is not a permutation of its keys.
+fn check_mode_ordering(
+    mode_ordering: Option<&HashMap<usize, usize>>,
+) -> Result<(), StruqtureError> {
+    if let Some(mapping) = mode_ordering {
+        let mut positions: HashSet<usize> = HashSet::with_capacity(mapping.len());
+        let mut keys: Vec<&usize> = mapping.keys().collect();
+        keys.sort();
+        for key in keys {
+            if !positions.insert(mapping[key]) {
+                return Err(StruqtureError::RemappingFailed { key: *key });
+            }
+        }
+        // Modes that are not in the mapping keep their position, so no mode may be mapped onto them
+        for position in mapping.values() {
+            if !mapping.contains_key(position) {
+                return Err(StruqtureError::RemappingFailed { key: *position });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reorders the modes of a FermionProduct according to a mode ordering.
+///
+/// # Arguments
+///
+/// * `product` - The FermionProduct to reorder.
+/// * `value` - The coefficient of the FermionProduct.
+/// * `mode_ordering` - The optional mapping of the fermionic modes onto their positions in the encoding.
+///
+/// # Returns
+///
+/// * `Ok((FermionProduct, CalculatorComplex))` - The reordered FermionProduct and its coefficient including the sign of the reordering.
+/// * `Err(StruqtureError::IndicesContainDoubles)` - Two modes of the product are mapped onto the same position.
+fn reorder_product(
+    product: &FermionProduct,
+    value: CalculatorComplex,
+    mode_ordering: Option<&HashMap<usize, usize>>,
+) -> Result<(FermionProduct, CalculatorComplex), StruqtureError> {
+    match mode_ordering {
+        None => Ok((product.clone(), value)),
+        Some(mapping) => {
+            let position = |mode: &usize| *mapping.get(mode).unwrap_or(mode);
+            FermionProduct::create_valid_pair(
+                product.creators().map(position),
+                product.annihilators().map(position),
+                value,
+            )
+        }
+    }
+}
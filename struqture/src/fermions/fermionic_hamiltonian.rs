@@ -11,12 +11,17 @@
 // limitations under the License.
 
 use super::{
-    FermionOperator, FermionProduct, HermitianFermionProduct, ModeIndex, OperateOnFermions,
+    checked_fock_state_dimension, fermion_hamiltonian_terms, fermion_product_expectation,
+    fermion_product_trace, fermionic_commutator_entries_on_row, fermionic_entries_on_row,
+    hermitian_term_expectation, FermionOperator, FermionProduct, HermitianFermionProduct,
+    ModeIndex, OperateOnFermions, ToFermionSparseMatrixOperator,
+    ToFermionSparseMatrixSuperOperator,
 };
 use crate::{
     GetValue, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError,
     StruqtureVersion, SymmetricIndex,
 };
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -231,6 +236,34 @@ impl<'a> OperateOnModes<'a> for FermionHamiltonian {
 
 impl<'a> OperateOnFermions<'a> for FermionHamiltonian {}
 
+impl<'a> ToFermionSparseMatrixOperator<'a> for FermionHamiltonian {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        Ok(fermionic_entries_on_row(
+            &fermion_hamiltonian_terms(self)?,
+            row,
+        ))
+    }
+}
+
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionHamiltonian {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        Ok(fermionic_commutator_entries_on_row(
+            &fermion_hamiltonian_terms(self)?,
+            row,
+            2usize.pow(number_modes as u32),
+        ))
+    }
+}
+
 /// Implements the default function (Default trait) of FermionHamiltonian (an empty FermionHamiltonian).
 ///
 impl Default for FermionHamiltonian {
@@ -267,6 +300,72 @@ impl FermionHamiltonian {
             internal_map: HashMap::with_capacity(capacity),
         }
     }
+
+    /// Returns the expectation value <psi|H|psi> of the FermionHamiltonian H for a state vector.
+    ///
+    /// The state is given in the occupation-number basis of the sparse matrix representation
+    /// and the expectation value is evaluated term by term without constructing the matrix of H.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state vector psi.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorFloat)` - The expectation value of the FermionHamiltonian.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the state is not 2^N for N at least the number of modes of the FermionHamiltonian.
+    pub fn expectation_value(
+        &self,
+        state: &[Complex64],
+    ) -> Result<CalculatorFloat, StruqtureError> {
+        checked_fock_state_dimension(state.len(), 1, self.current_number_modes())?;
+        let mut expectation = CalculatorFloat::from(0.0);
+        for (hermitian_product, value) in self.iter() {
+            let product = FermionProduct::new(
+                hermitian_product.creators().copied(),
+                hermitian_product.annihilators().copied(),
+            )?;
+            expectation += hermitian_term_expectation(
+                value,
+                fermion_product_expectation(&product, state),
+                hermitian_product.is_natural_hermitian(),
+            );
+        }
+        Ok(expectation)
+    }
+
+    /// Returns the expectation value Tr(p H) of the FermionHamiltonian H for a density matrix.
+    ///
+    /// The density matrix p is assumed to be hermitian.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The density matrix p in the occupation-number basis flattened in row-major form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorFloat)` - The expectation value of the FermionHamiltonian.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of the FermionHamiltonian.
+    pub fn expectation_value_density_matrix(
+        &self,
+        density_matrix: &[Complex64],
+    ) -> Result<CalculatorFloat, StruqtureError> {
+        let dimension =
+            checked_fock_state_dimension(density_matrix.len(), 2, self.current_number_modes())?;
+        let mut expectation = CalculatorFloat::from(0.0);
+        for (hermitian_product, value) in self.iter() {
+            let product = FermionProduct::new(
+                hermitian_product.creators().copied(),
+                hermitian_product.annihilators().copied(),
+            )?;
+            expectation += hermitian_term_expectation(
+                value,
+                fermion_product_trace(&product, density_matrix, dimension),
+                hermitian_product.is_natural_hermitian(),
+            );
+        }
+        Ok(expectation)
+    }
 }
 
 impl TryFrom<FermionOperator> for FermionHamiltonian {
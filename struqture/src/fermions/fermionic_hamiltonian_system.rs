@@ -12,11 +12,14 @@
 
 use super::{
     FermionHamiltonian, FermionSystem, HermitianFermionProduct, ModeIndex, OperateOnFermions,
+    ToFermionSparseMatrixOperator, ToFermionSparseMatrixSuperOperator,
 };
 use crate::{OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -190,6 +193,28 @@ impl<'a> OperateOnModes<'a> for FermionHamiltonianSystem {
 
 impl<'a> OperateOnFermions<'a> for FermionHamiltonianSystem {}
 
+impl<'a> ToFermionSparseMatrixOperator<'a> for FermionHamiltonianSystem {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.hamiltonian.sparse_matrix_entries_on_row(row)
+    }
+}
+
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionHamiltonianSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.hamiltonian
+            .sparse_matrix_superoperator_entries_on_row(row, number_modes)
+    }
+}
+
 /// Functions for the FermionHamiltonianSystem
 ///
 impl FermionHamiltonianSystem {
@@ -263,6 +288,41 @@ impl FermionHamiltonianSystem {
             }),
         }
     }
+
+    /// Returns the expectation value <psi|H|psi> of the FermionHamiltonianSystem for a state vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state vector psi in the occupation-number basis.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorFloat)` - The expectation value of the FermionHamiltonianSystem.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the state is not 2^N for N at least the number of modes of the FermionHamiltonianSystem.
+    pub fn expectation_value(
+        &self,
+        state: &[Complex64],
+    ) -> Result<CalculatorFloat, StruqtureError> {
+        self.hamiltonian.expectation_value(state)
+    }
+
+    /// Returns the expectation value Tr(p H) of the FermionHamiltonianSystem for a density matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The density matrix p in the occupation-number basis flattened in row-major form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorFloat)` - The expectation value of the FermionHamiltonianSystem.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of the FermionHamiltonianSystem.
+    pub fn expectation_value_density_matrix(
+        &self,
+        density_matrix: &[Complex64],
+    ) -> Result<CalculatorFloat, StruqtureError> {
+        self.hamiltonian
+            .expectation_value_density_matrix(density_matrix)
+    }
 }
 
 /// Implements the negative sign function of FermionHamiltonianSystem.
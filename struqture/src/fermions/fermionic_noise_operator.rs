@@ -10,8 +10,15 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{FermionProduct, OperateOnFermions};
-use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, StruqtureError, StruqtureVersion};
+use super::{
+    fermionic_lindblad_entries_on_row, FermionProduct, OperateOnFermions,
+    ToFermionSparseMatrixSuperOperator,
+};
+use crate::{
+    complex_value, ModeIndex, OperateOnDensityMatrix, OperateOnModes, StruqtureError,
+    StruqtureVersion,
+};
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -198,6 +205,26 @@ impl<'a> OperateOnModes<'a> for FermionLindbladNoiseOperator {
 
 impl<'a> OperateOnFermions<'a> for FermionLindbladNoiseOperator {}
 
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionLindbladNoiseOperator {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        let mut terms: Vec<(FermionProduct, FermionProduct, Complex64)> =
+            Vec::with_capacity(self.len());
+        for ((left, right), rate) in self.iter() {
+            terms.push((left.clone(), right.clone(), complex_value(rate)?));
+        }
+        Ok(fermionic_lindblad_entries_on_row(
+            &terms,
+            row,
+            2usize.pow(number_modes as u32),
+        ))
+    }
+}
+
 /// Implements the default function (Default trait) of FermionLindbladNoiseOperator (an empty FermionLindbladNoiseOperator).
 ///
 impl Default for FermionLindbladNoiseOperator {
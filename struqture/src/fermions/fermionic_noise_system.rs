@@ -10,11 +10,13 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{FermionLindbladNoiseOperator, OperateOnFermions};
+use super::{FermionLindbladNoiseOperator, OperateOnFermions, ToFermionSparseMatrixSuperOperator};
 use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::iter::{FromIterator, IntoIterator};
 use std::{
     fmt::{self, Write},
@@ -184,6 +186,18 @@ impl<'a> OperateOnModes<'a> for FermionLindbladNoiseSystem {
 
 impl<'a> OperateOnFermions<'a> for FermionLindbladNoiseSystem {}
 
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionLindbladNoiseSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_superoperator_entries_on_row(row, number_modes)
+    }
+}
+
 /// Functions for the FermionLindbladNoiseSystem.
 ///
 impl FermionLindbladNoiseSystem {
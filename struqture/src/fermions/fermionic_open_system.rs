@@ -10,10 +10,18 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{FermionHamiltonianSystem, FermionLindbladNoiseSystem};
-use crate::{OpenSystem, OperateOnDensityMatrix, OperateOnModes, StruqtureError};
+use super::{
+    FermionHamiltonianSystem, FermionLindbladNoiseSystem, FermionOperator, FermionProduct,
+    ToFermionSparseMatrixSuperOperator,
+};
+use crate::{
+    ConjugationTrait, GetValue, OpenSystem, OperateOnDensityMatrix, OperateOnModes, StruqtureError,
+    SymmetricIndex,
+};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorFloat;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::ops;
 
@@ -123,6 +131,32 @@ impl<'a> OperateOnModes<'a> for FermionLindbladOpenSystem {
     }
 }
 
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionLindbladOpenSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        let mut system_row = self
+            .system
+            .sparse_matrix_superoperator_entries_on_row(row, number_modes)?;
+        let noise_row = self
+            .noise
+            .sparse_matrix_superoperator_entries_on_row(row, number_modes)?;
+        for (key, val) in noise_row.into_iter() {
+            match system_row.get_mut(&key) {
+                Some(x) => *x += val,
+                None => {
+                    system_row.insert(key, val);
+                }
+            }
+        }
+        system_row.retain(|_, val| *val != Complex64::new(0.0, 0.0));
+        Ok(system_row)
+    }
+}
+
 /// Functions for the FermionLindbladOpenSystem
 ///
 impl FermionLindbladOpenSystem {
@@ -141,6 +175,44 @@ impl FermionLindbladOpenSystem {
             noise: FermionLindbladNoiseSystem::new(number_modes),
         }
     }
+
+    /// Returns the effective non-hermitian Hamiltonian of the open system.
+    ///
+    /// The effective Hamiltonian H_eff = H - i/2 sum_ij gamma_ij R_j^† L_i generates the deterministic
+    /// evolution between quantum jumps in quantum-trajectory methods.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionOperator` - The effective non-hermitian Hamiltonian.
+    ///
+    /// # Panics
+    ///
+    /// * Internal bug in add_operator_product.
+    pub fn effective_hamiltonian(&self) -> FermionOperator {
+        let mut effective = FermionOperator::new();
+        for (hermitian_product, value) in self.system.hamiltonian().iter() {
+            let product = FermionProduct::get_key(hermitian_product);
+            if !hermitian_product.is_natural_hermitian() {
+                let (conjugate, sign) = product.hermitian_conjugate();
+                effective
+                    .add_operator_product(conjugate, value.conjugate() * sign)
+                    .expect("Internal bug in add_operator_product");
+            }
+            effective
+                .add_operator_product(product, value.clone())
+                .expect("Internal bug in add_operator_product");
+        }
+        for ((left, right), rate) in self.noise.iter() {
+            let (right_conjugate, sign) = right.hermitian_conjugate();
+            let coefficient = rate.clone() * Complex64::new(0.0, -0.5 * sign);
+            for (product, product_sign) in right_conjugate * left.clone() {
+                effective
+                    .add_operator_product(product, coefficient.clone() * product_sign)
+                    .expect("Internal bug in add_operator_product");
+            }
+        }
+        effective
+    }
 }
 
 /// Implements the negative sign function of FermionLindbladOpenSystem.
@@ -10,13 +10,20 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{FermionHamiltonian, OperateOnFermions};
+use super::{
+    checked_fock_state_dimension, fermion_operator_terms, fermion_product_expectation,
+    fermion_product_trace, fermionic_commutator_entries_on_row, fermionic_entries_on_row,
+    FermionHamiltonian, OperateOnFermions, ToFermionSparseMatrixOperator,
+    ToFermionSparseMatrixSuperOperator,
+};
 use crate::fermions::FermionProduct;
 use crate::{
-    GetValue, ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError,
-    StruqtureVersion, SymmetricIndex,
+    commutation, mode_exchange_sign, parse_ladder_operators, GetValue, ModeIndex,
+    OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError, StruqtureVersion,
+    SymmetricIndex,
 };
 // use itertools::Itertools;
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -186,6 +193,34 @@ impl<'a> OperateOnModes<'a> for FermionOperator {
 
 impl<'a> OperateOnFermions<'a> for FermionOperator {}
 
+impl<'a> ToFermionSparseMatrixOperator<'a> for FermionOperator {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        Ok(fermionic_entries_on_row(
+            &fermion_operator_terms(self)?,
+            row,
+        ))
+    }
+}
+
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionOperator {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        Ok(fermionic_commutator_entries_on_row(
+            &fermion_operator_terms(self)?,
+            row,
+            2usize.pow(number_modes as u32),
+        ))
+    }
+}
+
 /// Implements the default function (Default trait) of FermionOperator (an empty FermionOperator).
 ///
 impl Default for FermionOperator {
@@ -222,6 +257,143 @@ impl FermionOperator {
             internal_map: HashMap::with_capacity(capacity),
         }
     }
+
+    /// Creates the normal ordered FermionOperator of a sequence of creation and annihilation operators.
+    ///
+    /// The operators are multiplied from left to right, creators and annihilators acting on the same mode
+    /// are exchanged using the fermionic commutation relations, e.g. `a1 c0 a0 c2` or `a0c0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `operators` - The sequence of operators, `c` for creators and `a` for annihilators followed by the mode index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The normal ordered FermionOperator equal to the product of the operators.
+    /// * `Err(StruqtureError::FromStringFailed)` - The sequence of operators could not be parsed.
+    pub fn normal_order(operators: &str) -> Result<Self, StruqtureError> {
+        let mut normal_ordered = FermionOperator::with_capacity(1);
+        normal_ordered.add_operator_product(FermionProduct::new([], [])?, 1.0.into())?;
+        for (is_creator, mode) in parse_ladder_operators(operators)? {
+            let product = if is_creator {
+                FermionProduct::new([mode], [])?
+            } else {
+                FermionProduct::new([], [mode])?
+            };
+            let mut factor = FermionOperator::with_capacity(1);
+            factor.add_operator_product(product, 1.0.into())?;
+            normal_ordered = normal_ordered * factor;
+        }
+        Ok(normal_ordered)
+    }
+
+    /// Computes the commutator `self * other - other * self`.
+    ///
+    /// Pairs of FermionProducts acting on disjoint modes are not multiplied twice, as they either commute or anticommute depending on their parity.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The FermionOperator to commute with.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionOperator` - The commutator of self and other.
+    pub fn commutator(&self, other: &FermionOperator) -> FermionOperator {
+        self.commutation(other, -1.0)
+    }
+
+    /// Computes the anticommutator `self * other + other * self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The FermionOperator to anticommute with.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionOperator` - The anticommutator of self and other.
+    pub fn anticommutator(&self, other: &FermionOperator) -> FermionOperator {
+        self.commutation(other, 1.0)
+    }
+
+    /// Computes `self * other + sign * other * self` product by product.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The FermionOperator on the right-hand side.
+    /// * `sign` - The sign of the reversed product.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionOperator` - The resulting FermionOperator.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn commutation(&self, other: &FermionOperator, sign: f64) -> FermionOperator {
+        commutation(
+            self,
+            other,
+            sign,
+            FermionOperator::new(),
+            |left, right| mode_exchange_sign(left, right, true),
+            |products| {
+                Ok(products
+                    .into_iter()
+                    .map(|(product, coefficient)| (product, CalculatorComplex::from(coefficient)))
+                    .collect())
+            },
+        )
+        .expect("Internal bug in add_operator_product")
+    }
+
+    /// Returns the expectation value <psi|O|psi> of the FermionOperator O for a state vector.
+    ///
+    /// The state is given in the occupation-number basis of the sparse matrix representation
+    /// and the expectation value is evaluated term by term without constructing the matrix of O.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state vector psi.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorComplex)` - The expectation value of the FermionOperator.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the state is not 2^N for N at least the number of modes of the FermionOperator.
+    pub fn expectation_value(
+        &self,
+        state: &[Complex64],
+    ) -> Result<CalculatorComplex, StruqtureError> {
+        checked_fock_state_dimension(state.len(), 1, self.current_number_modes())?;
+        let mut expectation = CalculatorComplex::from(0.0);
+        for (product, value) in self.iter() {
+            expectation += value.clone() * fermion_product_expectation(product, state);
+        }
+        Ok(expectation)
+    }
+
+    /// Returns the expectation value Tr(p O) of the FermionOperator O for a density matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The density matrix p in the occupation-number basis flattened in row-major form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorComplex)` - The expectation value of the FermionOperator.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of the FermionOperator.
+    pub fn expectation_value_density_matrix(
+        &self,
+        density_matrix: &[Complex64],
+    ) -> Result<CalculatorComplex, StruqtureError> {
+        let dimension =
+            checked_fock_state_dimension(density_matrix.len(), 2, self.current_number_modes())?;
+        let mut expectation = CalculatorComplex::from(0.0);
+        for (product, value) in self.iter() {
+            expectation +=
+                value.clone() * fermion_product_trace(product, density_matrix, dimension);
+        }
+        Ok(expectation)
+    }
 }
 
 impl From<FermionHamiltonian> for FermionOperator {
@@ -10,12 +10,17 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{FermionOperator, OperateOnFermions};
+use super::{
+    FermionOperator, OperateOnFermions, ToFermionSparseMatrixOperator,
+    ToFermionSparseMatrixSuperOperator,
+};
 use crate::fermions::FermionProduct;
 use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -180,6 +185,28 @@ impl<'a> OperateOnModes<'a> for FermionSystem {
 
 impl<'a> OperateOnFermions<'a> for FermionSystem {}
 
+impl<'a> ToFermionSparseMatrixOperator<'a> for FermionSystem {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator.sparse_matrix_entries_on_row(row)
+    }
+}
+
+impl<'a> ToFermionSparseMatrixSuperOperator<'a> for FermionSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_superoperator_entries_on_row(row, number_modes)
+    }
+}
+
 /// Functions for the FermionSystem
 ///
 impl FermionSystem {
@@ -257,6 +284,41 @@ impl FermionSystem {
             }),
         }
     }
+
+    /// Returns the expectation value <psi|O|psi> of the FermionSystem for a state vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state vector psi in the occupation-number basis.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorComplex)` - The expectation value of the FermionSystem.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the state is not 2^N for N at least the number of modes of the FermionSystem.
+    pub fn expectation_value(
+        &self,
+        state: &[Complex64],
+    ) -> Result<CalculatorComplex, StruqtureError> {
+        self.operator.expectation_value(state)
+    }
+
+    /// Returns the expectation value Tr(p O) of the FermionSystem for a density matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The density matrix p in the occupation-number basis flattened in row-major form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorComplex)` - The expectation value of the FermionSystem.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of the FermionSystem.
+    pub fn expectation_value_density_matrix(
+        &self,
+        density_matrix: &[Complex64],
+    ) -> Result<CalculatorComplex, StruqtureError> {
+        self.operator
+            .expectation_value_density_matrix(density_matrix)
+    }
 }
 
 /// Implements the negative sign function of FermionSystem.
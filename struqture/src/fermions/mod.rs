@@ -19,6 +19,7 @@
 //!
 //! Open Quantum Systems should be represented using [crate::fermions::FermionLindbladOpenSystem].
 
+mod fermion_qubit_encodings;
 mod fermionic_hamiltonian;
 mod fermionic_hamiltonian_system;
 mod fermionic_indices;
@@ -27,8 +28,12 @@ mod fermionic_noise_system;
 mod fermionic_open_system;
 mod fermionic_operator;
 mod fermionic_system;
+mod spinful_layout;
 use std::str::FromStr;
 
+pub use fermion_qubit_encodings::{
+    BravyiKitaevEncoding, FermionQubitEncoding, JordanWignerEncoding, ParityEncoding,
+};
 pub use fermionic_hamiltonian::FermionHamiltonian;
 pub use fermionic_hamiltonian_system::FermionHamiltonianSystem;
 pub use fermionic_noise_operator::FermionLindbladNoiseOperator;
@@ -36,10 +41,22 @@ pub use fermionic_noise_system::FermionLindbladNoiseSystem;
 pub use fermionic_open_system::FermionLindbladOpenSystem;
 pub use fermionic_operator::FermionOperator;
 pub use fermionic_system::FermionSystem;
+pub use spinful_layout::{FermionSpin, SpinfulLayout, SpinfulOrdering};
 
-use crate::{ModeIndex, OperateOnDensityMatrix, SymmetricIndex};
+#[cfg(feature = "rayon")]
+use crate::coo_from_rows_parallel;
+use crate::iterative_solvers::steady_state;
+use crate::time_evolution::{krylov_evolution, runge_kutta_45_evolution};
+use crate::{
+    add_entry, apply_rows, complex_value, coo_from_rows, csc_from_rows, csr_from_rows,
+    dense_dimension, dense_from_rows, CooSparseMatrix, CscSparseMatrix, CsrSparseMatrix, ModeIndex,
+    OperateOnDensityMatrix, OperateOnModes, StruqtureError, SymmetricIndex,
+};
 pub use fermionic_indices::{FermionProduct, HermitianFermionProduct};
-use qoqo_calculator::CalculatorComplex;
+use ndarray::Array2;
+use num_complex::Complex64;
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use std::collections::HashMap;
 
 /// Signal Trait for specifying that a type can be used a fermionic index.
 ///
@@ -94,3 +111,854 @@ where
     &'a Self: IntoIterator,
 {
 }
+
+/// Trait for constructing the sparse matrix superoperator of fermionic objects in the occupation-number basis.
+///
+/// The basis state with index `n` is the Fock state in which mode `j` is occupied if bit `j` of `n` is set.
+/// The standard fermionic sign convention is used: c_j^† and c_j pick up the sign (-1)^{n_0 + ... + n_{j-1}}
+/// when acting on a basis state.
+///
+/// The superoperator for the operator O is defined as the Matrix S so that
+/// `flatten(-i [O, p]) = S flatten(p)` where `[,]` is the commutator, `p` is a matrix
+/// and `flatten` flattens a matrix into a vector in row-major form.
+/// For noise and open systems the Lindblad terms are included in the same row-major convention.
+pub trait ToFermionSparseMatrixSuperOperator<'a>: OperateOnModes<'a> + PartialEq + Clone {
+    /// Constructs the sparse matrix representation of the superoperator in COO representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix in COO form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_coo(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CooSparseMatrix, StruqtureError> {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        let dimension = 2usize.pow(number_modes as u32);
+        coo_from_rows(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_modes)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in COO representation, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToFermionSparseMatrixSuperOperator::sparse_matrix_superoperator_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix in COO form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_superoperator_coo_parallel(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        let dimension = 2usize.pow(number_modes as u32);
+        coo_from_rows_parallel(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_modes)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csr(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        csr_from_rows(4usize.pow(number_modes as u32), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_modes)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csc(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        csc_from_rows(4usize.pow(number_modes as u32), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_modes)
+        })
+    }
+
+    /// Constructs the dense matrix representation of the superoperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the dense matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_superoperator(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let number_modes = number_modes.unwrap_or_else(|| self.number_modes());
+        if number_modes < self.current_number_modes() {
+            return Err(StruqtureError::NumberModesExceeded);
+        }
+        let dimension = fock_dense_dimension(number_modes, 2)?;
+        dense_from_rows(dimension, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_modes)
+        })
+    }
+
+    /// Constructs the sparse matrix entries for one row of the sparse matrix superoperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row for which to get the superoperator entries.
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix entries.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        number_modes: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError>;
+
+    /// Applies the superoperator to a flattened density matrix without constructing its matrix.
+    ///
+    /// The density matrix is flattened in row-major form, as for the sparse matrix representation of the superoperator,
+    /// and the number of modes is inferred from its length.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix the superoperator is applied to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix after the application of the superoperator.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let dimension = checked_fock_state_dimension(density_matrix.len(), 2, self.number_modes())?;
+        let number_modes = dimension.trailing_zeros() as usize;
+        apply_rows(density_matrix, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_modes)
+        })
+    }
+
+    /// Evolves a flattened density matrix under the superoperator with the adaptive RK45 method.
+    ///
+    /// The density matrix p(t) solves `d/dt flatten(p) = S flatten(p)` for the superoperator S of
+    /// [ToFermionSparseMatrixSuperOperator::sparse_matrix_superoperator_coo], with `flatten` the row-major flattening.
+    /// The equation is integrated with the Dormand-Prince method, applying the superoperator
+    /// without constructing its matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `time` - The evolution time.
+    /// * `tolerance` - The (positive) tolerance of the local error of each integration step, relative to the magnitude of the entries.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix(
+        &'a self,
+        density_matrix: &[Complex64],
+        time: f64,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        runge_kutta_45_evolution(
+            |state| self.apply_superoperator(state),
+            density_matrix,
+            time,
+            tolerance,
+        )
+    }
+
+    /// Evolves a flattened density matrix under the superoperator with Krylov subspace exponentiation.
+    ///
+    /// The density matrix p(t) = exp(t S) p(0) is computed for the superoperator S of
+    /// [ToFermionSparseMatrixSuperOperator::sparse_matrix_superoperator_coo], with p flattened in row-major form.
+    /// The exponential is approximated in Krylov subspaces constructed by applying the superoperator
+    /// without constructing its matrix, splitting the evolution time into steps to reach the tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `time` - The evolution time.
+    /// * `krylov_dimension` - The maximal dimension of the Krylov subspaces.
+    /// * `tolerance` - The (positive) tolerance of the error relative to the norm of the density matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length of the density matrix is not 4^N for N at least the number of modes of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix_krylov(
+        &'a self,
+        density_matrix: &[Complex64],
+        time: f64,
+        krylov_dimension: usize,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        checked_fock_state_dimension(density_matrix.len(), 2, self.number_modes())?;
+        krylov_evolution(
+            |state| self.apply_superoperator(state),
+            density_matrix,
+            time,
+            krylov_dimension,
+            tolerance,
+        )
+    }
+
+    /// Returns the steady state of the superoperator.
+    ///
+    /// The steady state is the density matrix p with `S flatten(p) = 0` and `Tr(p) = 1` for the
+    /// superoperator S of [ToFermionSparseMatrixSuperOperator::sparse_matrix_superoperator_coo].
+    /// Adding the rank-one term |v><t| to S, with the maximally mixed state v and the trace functional t,
+    /// removes the null space of S, and the resulting linear system `(S + |v><t|) flatten(p) = v`
+    /// is solved with the restarted GMRES method, applying the superoperator without constructing its matrix.
+    /// The steady state is assumed to be unique.
+    ///
+    /// The steady state is returned as a dense matrix, so the dimension 2^N of the Hilbert space
+    /// is limited to [crate::MAXIMAL_DENSE_DIMENSION] like for [ToFermionSparseMatrixOperator::dense_matrix].
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The tolerance of the residual of the linear system relative to the norm of v.
+    /// * `maximal_iterations` - The maximal number of applications of the superoperator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense steady-state density matrix for the number of modes of Self.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(StruqtureError::NoConvergence)` - The solver did not converge within the maximal number of iterations.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn steady_state(
+        &'a self,
+        tolerance: f64,
+        maximal_iterations: usize,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let dimension = fock_dense_dimension(self.number_modes(), 1)?;
+        steady_state(
+            |vector| self.apply_superoperator(vector),
+            dimension,
+            tolerance,
+            maximal_iterations,
+        )
+    }
+}
+
+/// Trait for constructing the sparse matrix of fermionic operators in the occupation-number basis.
+///
+/// The basis state with index `n` is the Fock state in which mode `j` is occupied if bit `j` of `n` is set.
+/// The standard fermionic sign convention is used: c_j^† and c_j pick up the sign (-1)^{n_0 + ... + n_{j-1}}
+/// when acting on a basis state.
+///
+/// # Example
+/// ```
+/// use struqture::prelude::*;
+/// use qoqo_calculator::CalculatorComplex;
+/// use struqture::fermions::{FermionProduct, FermionSystem, ToFermionSparseMatrixOperator};
+/// use num_complex::Complex64;
+///
+/// let mut system = FermionSystem::new(Some(2));
+/// system.set(FermionProduct::new([1], [0]).unwrap(), CalculatorComplex::from(1.0)).unwrap();
+///
+/// // c_1^† c_0 maps |n_0=1, n_1=0> (index 1) onto |n_0=0, n_1=1> (index 2)
+/// let (values, (rows, columns)) = system.sparse_matrix_coo(None).unwrap();
+/// assert_eq!(values, vec![Complex64::new(1.0, 0.0)]);
+/// assert_eq!(rows, vec![2]);
+/// assert_eq!(columns, vec![1]);
+/// ```
+pub trait ToFermionSparseMatrixOperator<'a>: ToFermionSparseMatrixSuperOperator<'a> {
+    /// Constructs the sparse matrix representation of Self as a COO matrix with a given number of modes.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix in COO form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_coo(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CooSparseMatrix, StruqtureError> {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        let dimension = 2usize.pow(number_modes as u32);
+        coo_from_rows(dimension, |row| self.sparse_matrix_entries_on_row(row))
+    }
+
+    /// Constructs the sparse matrix representation of Self as a COO matrix with a given number of modes, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToFermionSparseMatrixOperator::sparse_matrix_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix in COO form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_coo_parallel(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        let dimension = 2usize.pow(number_modes as u32);
+        coo_from_rows_parallel(dimension, |row| self.sparse_matrix_entries_on_row(row))
+    }
+
+    /// Constructs the sparse matrix representation of Self with a given number of modes in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csr(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        csr_from_rows(2usize.pow(number_modes as u32), |row| {
+            self.sparse_matrix_entries_on_row(row)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self with a given number of modes in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csc(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let number_modes = match number_modes {
+            None => self.number_modes(),
+            Some(num_modes) => {
+                if num_modes < self.current_number_modes() {
+                    return Err(StruqtureError::NumberModesExceeded);
+                }
+                num_modes
+            }
+        };
+        csc_from_rows(2usize.pow(number_modes as u32), |row| {
+            self.sparse_matrix_entries_on_row(row)
+        })
+    }
+
+    /// Constructs the dense matrix representation of Self with a given number of modes.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_modes` - The number of fermionic modes for which to construct the dense matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The number of modes is smaller than the number of modes Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_matrix(
+        &'a self,
+        number_modes: Option<usize>,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let number_modes = number_modes.unwrap_or_else(|| self.number_modes());
+        if number_modes < self.current_number_modes() {
+            return Err(StruqtureError::NumberModesExceeded);
+        }
+        let dimension = fock_dense_dimension(number_modes, 1)?;
+        dense_from_rows(dimension, |row| self.sparse_matrix_entries_on_row(row))
+    }
+
+    /// Constructs the sparse matrix entries for one row of the sparse matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row for which to get the entries.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError>;
+}
+
+/// Applies a FermionProduct or its hermitian conjugate to an occupation-number basis state.
+///
+/// # Arguments
+///
+/// * `product` - The FermionProduct P to apply.
+/// * `state` - The index of the basis state, bit `j` is the occupation of mode `j`.
+/// * `adjoint` - Whether to apply the hermitian conjugate P^† instead of P.
+///
+/// # Returns
+///
+/// * `Some((usize, f64))` - The index of the resulting basis state and the fermionic sign.
+/// * `None` - The product annihilates the basis state.
+pub(crate) fn apply_fermion_product(
+    product: &FermionProduct,
+    state: usize,
+    adjoint: bool,
+) -> Option<(usize, f64)> {
+    // P = c^†_{c_1}...c^†_{c_n} c_{a_1}...c_{a_m} acts from right to left,
+    // P^† = c^†_{a_m}...c^†_{a_1} c_{c_n}...c_{c_1} as well
+    let operators: Vec<(usize, bool)> = if adjoint {
+        product
+            .creators()
+            .map(|mode| (*mode, false))
+            .chain(product.annihilators().map(|mode| (*mode, true)))
+            .collect()
+    } else {
+        product
+            .annihilators()
+            .rev()
+            .map(|mode| (*mode, false))
+            .chain(product.creators().rev().map(|mode| (*mode, true)))
+            .collect()
+    };
+    let mut state = state;
+    let mut sign = 1.0;
+    for (mode, create) in operators {
+        let occupied = (state >> mode) & 1 == 1;
+        if occupied == create {
+            return None;
+        }
+        if (state & ((1usize << mode) - 1)).count_ones() % 2 == 1 {
+            sign = -sign;
+        }
+        state ^= 1usize << mode;
+    }
+    Some((state, sign))
+}
+
+/// Checks the length of a state and returns the dimension of the Fock space of the fermionic modes.
+///
+/// # Arguments
+///
+/// * `length` - The length of the state.
+/// * `copies` - The number of copies of the Fock space (1 for state vectors, 2 for flattened density matrices).
+/// * `number_modes` - The minimal number of fermionic modes of the Fock space.
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The dimension of the Fock space of the fermionic modes.
+/// * `Err(StruqtureError::MissmatchedStateLengthModes)` - The length is not 2^(copies * N) for N at least number_modes.
+pub(crate) fn checked_fock_state_dimension(
+    length: usize,
+    copies: u32,
+    number_modes: usize,
+) -> Result<usize, StruqtureError> {
+    crate::spins::checked_state_dimension(length, copies, number_modes).map_err(|_| {
+        StruqtureError::MissmatchedStateLengthModes {
+            length,
+            number_modes,
+        }
+    })
+}
+
+/// Returns the dimension of a dense matrix acting on `copies` copies of the Fock space of `number_modes` fermionic modes.
+///
+/// # Arguments
+///
+/// * `number_modes` - The number of fermionic modes of the Fock space.
+/// * `copies` - The number of copies of the Fock space (1 for operators, 2 for superoperators).
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The dimension of the dense matrix.
+/// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+/// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+fn fock_dense_dimension(number_modes: usize, copies: u32) -> Result<usize, StruqtureError> {
+    dense_dimension(
+        std::iter::repeat(2).take(number_modes),
+        copies,
+        0,
+        number_modes,
+    )
+}
+
+/// Returns the expectation value <psi|P|psi> of a FermionProduct P in the occupation-number basis.
+///
+/// # Arguments
+///
+/// * `product` - The FermionProduct P.
+/// * `state` - The state vector psi in the occupation-number basis.
+///
+/// # Returns
+///
+/// * `Complex64` - The expectation value of the product.
+pub(crate) fn fermion_product_expectation(
+    product: &FermionProduct,
+    state: &[Complex64],
+) -> Complex64 {
+    state
+        .iter()
+        .enumerate()
+        .filter_map(|(column, entry)| {
+            apply_fermion_product(product, column, false)
+                .map(|(row, sign)| state[row].conj() * sign * entry)
+        })
+        .sum()
+}
+
+/// Returns the trace Tr(p P) of a FermionProduct P with a density matrix p in the occupation-number basis.
+///
+/// # Arguments
+///
+/// * `product` - The FermionProduct P.
+/// * `density_matrix` - The density matrix p flattened in row-major form.
+/// * `dimension` - The dimension of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Complex64` - The trace of the product of the density matrix and the FermionProduct.
+pub(crate) fn fermion_product_trace(
+    product: &FermionProduct,
+    density_matrix: &[Complex64],
+    dimension: usize,
+) -> Complex64 {
+    // Tr(p P) = sum_c P[r, c] p[c, r] with P |c> = sign |r>
+    (0..dimension)
+        .filter_map(|column| {
+            apply_fermion_product(product, column, false)
+                .map(|(row, sign)| density_matrix[column * dimension + row] * sign)
+        })
+        .sum()
+}
+
+/// Returns the expectation value of the hermitian term v * K + v^* * K^† from the expectation value of K.
+///
+/// The hermitian conjugate is only included when K is not naturally hermitian.
+/// The expectation value of K^† is the complex conjugate of the one of K for state vectors and hermitian density matrices.
+///
+/// # Arguments
+///
+/// * `value` - The coefficient v of the term.
+/// * `expectation` - The expectation value of K.
+/// * `natural_hermitian` - Whether K is naturally hermitian.
+///
+/// # Returns
+///
+/// * `CalculatorFloat` - The real expectation value of the hermitian term.
+pub(crate) fn hermitian_term_expectation(
+    value: &CalculatorComplex,
+    expectation: Complex64,
+    natural_hermitian: bool,
+) -> CalculatorFloat {
+    let real = value.re.clone() * expectation.re - value.im.clone() * expectation.im;
+    if natural_hermitian {
+        real
+    } else {
+        real * 2.0
+    }
+}
+
+/// Collects the terms of a FermionOperator as (P, adjoint, value) for the sparse matrix construction.
+///
+/// # Arguments
+///
+/// * `operator` - The FermionOperator to collect the terms of.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(FermionProduct, bool, Complex64)>)` - The terms value * P of the operator.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn fermion_operator_terms(
+    operator: &FermionOperator,
+) -> Result<Vec<(FermionProduct, bool, Complex64)>, StruqtureError> {
+    operator
+        .iter()
+        .map(|(product, value)| Ok((product.clone(), false, complex_value(value)?)))
+        .collect()
+}
+
+/// Collects the terms of a FermionHamiltonian as (P, adjoint, value) for the sparse matrix construction.
+///
+/// Every key K with value v stands for v * K + v^* * K^†, where the hermitian conjugate is only
+/// added when K is not naturally hermitian.
+///
+/// # Arguments
+///
+/// * `hamiltonian` - The FermionHamiltonian to collect the terms of.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(FermionProduct, bool, Complex64)>)` - The terms of the hamiltonian.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn fermion_hamiltonian_terms(
+    hamiltonian: &FermionHamiltonian,
+) -> Result<Vec<(FermionProduct, bool, Complex64)>, StruqtureError> {
+    let mut terms: Vec<(FermionProduct, bool, Complex64)> =
+        Vec::with_capacity(2 * hamiltonian.len());
+    for (hermitian_product, value) in hamiltonian.iter() {
+        let product = FermionProduct::new(
+            hermitian_product.creators().copied(),
+            hermitian_product.annihilators().copied(),
+        )?;
+        let value = complex_value(value)?;
+        if !hermitian_product.is_natural_hermitian() {
+            terms.push((product.clone(), true, value.conj()));
+        }
+        terms.push((product, false, value));
+    }
+    Ok(terms)
+}
+
+/// Constructs the entries of one row of the sparse matrix of a sum of fermionic terms.
+///
+/// # Arguments
+///
+/// * `terms` - The terms (P, adjoint, value) representing value * P or value * P^† if adjoint is true.
+/// * `row` - The row for which to get the entries.
+///
+/// # Returns
+///
+/// * `HashMap<usize, Complex64>` - The non-zero entries of the row, indexed by column.
+pub(crate) fn fermionic_entries_on_row(
+    terms: &[(FermionProduct, bool, Complex64)],
+    row: usize,
+) -> HashMap<usize, Complex64> {
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(terms.len());
+    for (product, adjoint, value) in terms {
+        // <row| T = (T^† |row>)^†, the fermionic signs are real
+        if let Some((column, sign)) = apply_fermion_product(product, row, !adjoint) {
+            add_entry(&mut entries, column, *value * sign);
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    entries
+}
+
+/// Constructs the entries of one row of the commutator superoperator -i [O, p] of a sum of fermionic terms.
+///
+/// # Arguments
+///
+/// * `terms` - The terms (P, adjoint, value) representing value * P or value * P^† if adjoint is true.
+/// * `row` - The row for which to get the superoperator entries.
+/// * `dimension` - The dimension of the Hilbert space.
+///
+/// # Returns
+///
+/// * `HashMap<usize, Complex64>` - The non-zero entries of the row, indexed by column.
+pub(crate) fn fermionic_commutator_entries_on_row(
+    terms: &[(FermionProduct, bool, Complex64)],
+    row: usize,
+    dimension: usize,
+) -> HashMap<usize, Complex64> {
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(2 * terms.len());
+    let left_index = row.div_euclid(dimension);
+    let right_index = row % dimension;
+    let constant_prefactor = Complex64::new(0.0, -1.0);
+    for (product, adjoint, value) in terms {
+        // -i O p => -i O.kron(I) flatten(p) uses the row left_index of O
+        if let Some((column, sign)) = apply_fermion_product(product, left_index, !adjoint) {
+            add_entry(
+                &mut entries,
+                column * dimension + right_index,
+                constant_prefactor * *value * sign,
+            );
+        }
+        // i p O => i I.kron(O.T) flatten(p) uses the column right_index of O
+        if let Some((column, sign)) = apply_fermion_product(product, right_index, *adjoint) {
+            add_entry(
+                &mut entries,
+                left_index * dimension + column,
+                -constant_prefactor * *value * sign,
+            );
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    entries
+}
+
+/// Constructs the entries of one row of the Lindblad superoperator of fermionic noise terms.
+///
+/// Each term ((L, R), rate) contributes rate * (L p R^† - 1/2 R^† L p - 1/2 p R^† L).
+///
+/// # Arguments
+///
+/// * `terms` - The noise terms (L, R, rate).
+/// * `row` - The row for which to get the superoperator entries.
+/// * `dimension` - The dimension of the Hilbert space.
+///
+/// # Returns
+///
+/// * `HashMap<usize, Complex64>` - The non-zero entries of the row, indexed by column.
+pub(crate) fn fermionic_lindblad_entries_on_row(
+    terms: &[(FermionProduct, FermionProduct, Complex64)],
+    row: usize,
+    dimension: usize,
+) -> HashMap<usize, Complex64> {
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(3 * terms.len());
+    let left_index = row.div_euclid(dimension);
+    let right_index = row % dimension;
+    for (left, right, rate) in terms {
+        // L p R^† uses the row left_index of L and the row right_index of R (conjugated, the signs are real)
+        if let (Some((column_left, sign_left)), Some((column_right, sign_right))) = (
+            apply_fermion_product(left, left_index, true),
+            apply_fermion_product(right, right_index, true),
+        ) {
+            add_entry(
+                &mut entries,
+                column_left * dimension + column_right,
+                *rate * sign_left * sign_right,
+            );
+        }
+        // - 1/2 R^† L p uses the row left_index of R^† L: <left_index| R^† = (R |left_index>)^†
+        if let Some((intermediate, sign_right)) = apply_fermion_product(right, left_index, false) {
+            if let Some((column, sign_left)) = apply_fermion_product(left, intermediate, true) {
+                add_entry(
+                    &mut entries,
+                    column * dimension + right_index,
+                    *rate * (-0.5 * sign_left * sign_right),
+                );
+            }
+        }
+        // - 1/2 p R^† L uses the column right_index of R^† L
+        if let Some((intermediate, sign_left)) = apply_fermion_product(left, right_index, false) {
+            if let Some((column, sign_right)) = apply_fermion_product(right, intermediate, true) {
+                add_entry(
+                    &mut entries,
+                    left_index * dimension + column,
+                    *rate * (-0.5 * sign_left * sign_right),
+                );
+            }
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    entries
+}
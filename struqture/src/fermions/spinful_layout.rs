@@ -0,0 +1,324 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{FermionHamiltonian, FermionOperator, FermionProduct, HermitianFermionProduct};
+use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnState, StruqtureError, SymmetricIndex};
+use qoqo_calculator::CalculatorComplex;
+
+/// Spin projection of a spin-1/2 fermion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FermionSpin {
+    /// Spin up, σ = 0.
+    Up,
+    /// Spin down, σ = 1.
+    Down,
+}
+
+/// Implements the format function (Display trait) of FermionSpin.
+///
+impl std::fmt::Display for FermionSpin {
+    /// Formats the FermionSpin using the given formatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to use.
+    ///
+    /// # Returns
+    ///
+    /// * `std::fmt::Result` - The formatted FermionSpin.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FermionSpin::Up => write!(f, "↑"),
+            FermionSpin::Down => write!(f, "↓"),
+        }
+    }
+}
+
+/// Ordering of the spin-orbital modes in a [SpinfulLayout].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpinfulOrdering {
+    /// The two spin modes of each orbital are neighbours, (i, σ) is mode 2i + σ.
+    Interleaved,
+    /// All spin-up modes come before all spin-down modes, (i, σ) is mode i + σ n for n orbitals.
+    Blocked,
+}
+
+/// Layout of the fermionic modes of spinful orbitals.
+///
+/// Maps an orbital i and a spin projection σ onto a single fermionic mode and back.
+/// The interleaved ordering is the one used by [crate::models::fermi_hubbard].
+///
+/// # Example
+///
+/// ```
+/// use struqture::fermions::{FermionSpin, SpinfulLayout, SpinfulOrdering};
+///
+/// let layout = SpinfulLayout::new(3, SpinfulOrdering::Blocked);
+/// assert_eq!(layout.mode(1, FermionSpin::Down).unwrap(), 4);
+/// assert_eq!(layout.orbital_spin(4).unwrap(), (1, FermionSpin::Down));
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpinfulLayout {
+    /// The number of spatial orbitals.
+    number_orbitals: usize,
+    /// The ordering of the spin-orbital modes.
+    ordering: SpinfulOrdering,
+}
+
+impl SpinfulLayout {
+    /// Creates a new SpinfulLayout.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_orbitals` - The number of spatial orbitals, each orbital has two modes.
+    /// * `ordering` - The ordering of the spin-orbital modes.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new SpinfulLayout.
+    pub fn new(number_orbitals: usize, ordering: SpinfulOrdering) -> Self {
+        SpinfulLayout {
+            number_orbitals,
+            ordering,
+        }
+    }
+
+    /// Returns the number of spatial orbitals of the layout.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of orbitals.
+    pub fn number_orbitals(&self) -> usize {
+        self.number_orbitals
+    }
+
+    /// Returns the number of fermionic modes of the layout.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of modes, twice the number of orbitals.
+    pub fn number_modes(&self) -> usize {
+        2 * self.number_orbitals
+    }
+
+    /// Returns the ordering of the spin-orbital modes.
+    ///
+    /// # Returns
+    ///
+    /// * `SpinfulOrdering` - The ordering of the layout.
+    pub fn ordering(&self) -> SpinfulOrdering {
+        self.ordering
+    }
+
+    /// Returns the fermionic mode of a spin orbital.
+    ///
+    /// # Arguments
+    ///
+    /// * `orbital` - The index of the spatial orbital.
+    /// * `spin` - The spin projection.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The index of the fermionic mode.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The orbital is not part of the layout.
+    pub fn mode(&self, orbital: usize, spin: FermionSpin) -> Result<usize, StruqtureError> {
+        if orbital >= self.number_orbitals {
+            return Err(StruqtureError::NumberModesExceeded);
+        }
+        let sigma = match spin {
+            FermionSpin::Up => 0,
+            FermionSpin::Down => 1,
+        };
+        Ok(match self.ordering {
+            SpinfulOrdering::Interleaved => 2 * orbital + sigma,
+            SpinfulOrdering::Blocked => orbital + sigma * self.number_orbitals,
+        })
+    }
+
+    /// Returns the spin orbital of a fermionic mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The index of the fermionic mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((usize, FermionSpin))` - The index of the spatial orbital and the spin projection.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The mode is not part of the layout.
+    pub fn orbital_spin(&self, mode: usize) -> Result<(usize, FermionSpin), StruqtureError> {
+        if mode >= self.number_modes() {
+            return Err(StruqtureError::NumberModesExceeded);
+        }
+        let (orbital, sigma) = match self.ordering {
+            SpinfulOrdering::Interleaved => (mode / 2, mode % 2),
+            SpinfulOrdering::Blocked => (mode % self.number_orbitals, mode / self.number_orbitals),
+        };
+        let spin = if sigma == 0 {
+            FermionSpin::Up
+        } else {
+            FermionSpin::Down
+        };
+        Ok((orbital, spin))
+    }
+
+    /// Formats a FermionProduct with spin-orbital labels.
+    ///
+    /// The format follows the one of FermionProduct with each mode replaced by its orbital and spin,
+    /// e.g. c0↑c0↓a1↑a1↓ for the modes 0, 1, 2 and 3 of an interleaved layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `product` - The FermionProduct to format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The formatted FermionProduct.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - The product acts on a mode that is not part of the layout.
+    pub fn format_product(&self, product: &FermionProduct) -> Result<String, StruqtureError> {
+        if product == &FermionProduct::default() {
+            return Ok("I".to_string());
+        }
+        let mut string = String::new();
+        for (prefix, modes) in [("c", product.creators()), ("a", product.annihilators())] {
+            for mode in modes {
+                let (orbital, spin) = self.orbital_spin(*mode)?;
+                string.push_str(format!("{}{}{}", prefix, orbital, spin).as_str());
+            }
+        }
+        Ok(string)
+    }
+
+    /// Creates the number operator N_σ = Σ_i n_iσ of one spin projection.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin projection σ.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionHamiltonian` - The number operator.
+    pub fn number_operator(&self, spin: FermionSpin) -> FermionHamiltonian {
+        let mut hamiltonian = FermionHamiltonian::with_capacity(self.number_orbitals);
+        for mode in self.modes(spin) {
+            hamiltonian
+                .add_operator_product(number_product(mode), CalculatorComplex::from(1.0))
+                .expect("Internal bug in add_operator_product");
+        }
+        hamiltonian
+    }
+
+    /// Creates the spin operator S_z = (N_↑ - N_↓) / 2.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionHamiltonian` - The S_z operator.
+    pub fn s_z(&self) -> FermionHamiltonian {
+        let mut hamiltonian = FermionHamiltonian::with_capacity(self.number_modes());
+        for (spin, value) in [(FermionSpin::Up, 0.5), (FermionSpin::Down, -0.5)] {
+            for mode in self.modes(spin) {
+                hamiltonian
+                    .add_operator_product(number_product(mode), CalculatorComplex::from(value))
+                    .expect("Internal bug in add_operator_product");
+            }
+        }
+        hamiltonian
+    }
+
+    /// Creates the spin raising operator S_+ = Σ_i c_i↑^† c_i↓.
+    ///
+    /// # Returns
+    ///
+    /// * `FermionOperator` - The S_+ operator.
+    pub fn s_plus(&self) -> FermionOperator {
+        let mut operator = FermionOperator::with_capacity(self.number_orbitals);
+        for (up, down) in self
+            .modes(FermionSpin::Up)
+            .zip(self.modes(FermionSpin::Down))
+        {
+            operator
+                .add_operator_product(
+                    FermionProduct::new([up], [down]).expect("Internal bug in FermionProduct::new"),
+                    CalculatorComplex::from(1.0),
+                )
+                .expect("Internal bug in add_operator_product");
+        }
+        operator
+    }
+
+    /// Creates the total spin operator S^2 = S_- S_+ + S_z (S_z + 1).
+    ///
+    /// # Returns
+    ///
+    /// * `FermionHamiltonian` - The S^2 operator.
+    pub fn s_squared(&self) -> FermionHamiltonian {
+        let s_plus = self.s_plus();
+        let s_minus = s_plus.hermitian_conjugate();
+        let s_z = FermionOperator::from(self.s_z());
+        let mut s_z_plus_one = s_z.clone();
+        s_z_plus_one
+            .add_operator_product(FermionProduct::default(), CalculatorComplex::from(1.0))
+            .expect("Internal bug in add_operator_product");
+        let s_squared = s_minus * s_plus + s_z * s_z_plus_one;
+
+        // Each pair of hermitian conjugated products is stored once as a HermitianFermionProduct,
+        // products with equal minimal creator and annihilator index are kept if they are the smaller one
+        let is_valid =
+            |product: &FermionProduct| product.creators().next() <= product.annihilators().next();
+        let mut hamiltonian = FermionHamiltonian::with_capacity(s_squared.len());
+        for (product, value) in s_squared {
+            let (conjugate, _) = product.hermitian_conjugate();
+            if !is_valid(&product) || (is_valid(&conjugate) && conjugate < product) {
+                continue;
+            }
+            let hermitian_product = HermitianFermionProduct::new(
+                product.creators().copied(),
+                product.annihilators().copied(),
+            )
+            .expect("Internal bug in HermitianFermionProduct::new");
+            hamiltonian
+                .add_operator_product(hermitian_product, value)
+                .expect("Internal bug in add_operator_product");
+        }
+        hamiltonian
+    }
+
+    /// Returns the modes of all orbitals with one spin projection.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin projection.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = usize>` - The modes ordered by orbital.
+    fn modes(&self, spin: FermionSpin) -> impl Iterator<Item = usize> + '_ {
+        (0..self.number_orbitals).map(move |orbital| {
+            self.mode(orbital, spin)
+                .expect("Internal bug in SpinfulLayout::mode")
+        })
+    }
+}
+
+/// Returns the HermitianFermionProduct of the number operator n_j = c_j^† c_j.
+///
+/// # Arguments
+///
+/// * `mode` - The mode j.
+///
+/// # Returns
+///
+/// * `HermitianFermionProduct` - The number operator of the mode.
+fn number_product(mode: usize) -> HermitianFermionProduct {
+    HermitianFermionProduct::new([mode], [mode])
+        .expect("Internal bug in HermitianFermionProduct::new")
+}
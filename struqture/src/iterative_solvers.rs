@@ -0,0 +1,446 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Iterative solvers for linear problems given by the action of an operator on vectors.
+//!
+//! The operators are never constructed as matrices, so that the solvers can be used with the
+//! matrix-free application of operators and superoperators.
+
+use crate::StruqtureError;
+use ndarray::Array2;
+use num_complex::Complex64;
+use std::cmp::Ordering;
+
+/// Returns the euclidean norm of a vector.
+pub(crate) fn norm(vector: &[Complex64]) -> f64 {
+    vector
+        .iter()
+        .map(|entry| entry.norm_sqr())
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Returns the scalar product <left|right>.
+pub(crate) fn scalar_product(left: &[Complex64], right: &[Complex64]) -> Complex64 {
+    left.iter()
+        .zip(right.iter())
+        .map(|(left, right)| left.conj() * right)
+        .sum()
+}
+
+/// Returns the eigenvalues and eigenvectors of a hermitian matrix.
+///
+/// The eigensystem is computed with cyclic complex Jacobi rotations, which is sufficient
+/// for the small rate matrices of Lindblad noise.
+///
+/// # Arguments
+///
+/// * `matrix` - The hermitian matrix.
+///
+/// # Returns
+///
+/// * `(Vec<f64>, Array2<Complex64>)` - The eigenvalues and the matrix with the corresponding eigenvectors as columns.
+pub(crate) fn hermitian_eigensystem(matrix: &Array2<Complex64>) -> (Vec<f64>, Array2<Complex64>) {
+    let dimension = matrix.nrows();
+    let mut matrix = matrix.clone();
+    let mut eigenvectors: Array2<Complex64> = Array2::eye(dimension);
+    let scale = matrix.iter().map(|entry| entry.norm()).fold(0.0, f64::max);
+    for _ in 0..100 {
+        let off_diagonal = (0..dimension)
+            .flat_map(|p| (0..dimension).filter(move |q| *q != p).map(move |q| (p, q)))
+            .map(|(p, q)| matrix[[p, q]].norm())
+            .fold(0.0, f64::max);
+        if off_diagonal <= f64::EPSILON * scale {
+            break;
+        }
+        for p in 0..dimension {
+            for q in (p + 1)..dimension {
+                let magnitude = matrix[[p, q]].norm();
+                if magnitude == 0.0 {
+                    continue;
+                }
+                // The unitary W = diag(1, e^(-i phi)) G removes the phase of the off-diagonal
+                // entry and applies the real Jacobi rotation G zeroing it
+                let phase = matrix[[p, q]] / magnitude;
+                let theta = 0.5 * (2.0 * magnitude).atan2(matrix[[q, q]].re - matrix[[p, p]].re);
+                let (sin, cos) = theta.sin_cos();
+                for k in 0..dimension {
+                    let kp = matrix[[k, p]];
+                    let kq = matrix[[k, q]];
+                    matrix[[k, p]] = kp * cos - kq * phase.conj() * sin;
+                    matrix[[k, q]] = kp * sin + kq * phase.conj() * cos;
+                    let kp = eigenvectors[[k, p]];
+                    let kq = eigenvectors[[k, q]];
+                    eigenvectors[[k, p]] = kp * cos - kq * phase.conj() * sin;
+                    eigenvectors[[k, q]] = kp * sin + kq * phase.conj() * cos;
+                }
+                for k in 0..dimension {
+                    let pk = matrix[[p, k]];
+                    let qk = matrix[[q, k]];
+                    matrix[[p, k]] = pk * cos - qk * phase * sin;
+                    matrix[[q, k]] = pk * sin + qk * phase * cos;
+                }
+                matrix[[p, q]] = Complex64::default();
+                matrix[[q, p]] = Complex64::default();
+            }
+        }
+    }
+    let eigenvalues = (0..dimension).map(|k| matrix[[k, k]].re).collect();
+    (eigenvalues, eigenvectors)
+}
+
+/// Solves the linear system A x = b with the restarted GMRES method.
+///
+/// The Krylov subspace of the residual is constructed with the Arnoldi iteration and the
+/// least-squares problem on the subspace is solved with Givens rotations.
+///
+/// # Arguments
+///
+/// * `operator` - The action of the operator A on a vector.
+/// * `rhs` - The right-hand side b.
+/// * `tolerance` - The tolerance of the residual |A x - b| relative to |b|.
+/// * `restart` - The dimension of the Krylov subspace after which the method is restarted.
+/// * `maximal_iterations` - The maximal number of applications of the operator in the Arnoldi iterations.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Complex64>)` - The solution x.
+/// * `Err(StruqtureError::NoConvergence)` - The residual did not reach the tolerance within the maximal number of iterations.
+/// * `Err(StruqtureError)` - The operator could not be applied to a vector.
+pub(crate) fn gmres<F>(
+    operator: F,
+    rhs: &[Complex64],
+    tolerance: f64,
+    restart: usize,
+    maximal_iterations: usize,
+) -> Result<Vec<Complex64>, StruqtureError>
+where
+    F: Fn(&[Complex64]) -> Result<Vec<Complex64>, StruqtureError>,
+{
+    let restart = restart.max(1);
+    let mut solution = vec![Complex64::default(); rhs.len()];
+    let rhs_norm = norm(rhs);
+    if rhs_norm == 0.0 {
+        return Ok(solution);
+    }
+    let mut iterations = 0;
+    loop {
+        let mut residual = operator(&solution)?;
+        for (entry, rhs_entry) in residual.iter_mut().zip(rhs.iter()) {
+            *entry = rhs_entry - *entry;
+        }
+        let beta = norm(&residual);
+        if beta <= tolerance * rhs_norm {
+            return Ok(solution);
+        }
+        if iterations >= maximal_iterations {
+            return Err(StruqtureError::NoConvergence { iterations });
+        }
+
+        let mut basis: Vec<Vec<Complex64>> =
+            vec![residual.iter().map(|entry| entry / beta).collect()];
+        let mut hessenberg: Vec<Vec<Complex64>> = Vec::with_capacity(restart);
+        let mut rotations: Vec<(f64, Complex64)> = Vec::with_capacity(restart);
+        let mut projected_rhs: Vec<Complex64> = vec![Complex64::new(beta, 0.0)];
+        for column in 0..restart {
+            iterations += 1;
+            let mut vector = operator(&basis[column])?;
+            let mut entries: Vec<Complex64> = Vec::with_capacity(column + 2);
+            for basis_vector in basis.iter() {
+                let overlap = scalar_product(basis_vector, &vector);
+                entries.push(overlap);
+                for (entry, basis_entry) in vector.iter_mut().zip(basis_vector.iter()) {
+                    *entry -= overlap * basis_entry;
+                }
+            }
+            let next_norm = norm(&vector);
+            entries.push(Complex64::new(next_norm, 0.0));
+            // Previous rotations G = [[c, s], [-s^*, c]] act on the new column
+            for (row, &(cos, sin)) in rotations.iter().enumerate() {
+                let upper = entries[row] * cos + sin * entries[row + 1];
+                entries[row + 1] = -sin.conj() * entries[row] + entries[row + 1] * cos;
+                entries[row] = upper;
+            }
+            // New rotation eliminating the subdiagonal entry
+            let diagonal = entries[column];
+            let subdiagonal = entries[column + 1];
+            let radius = (diagonal.norm_sqr() + subdiagonal.norm_sqr()).sqrt();
+            let (cos, sin) = if diagonal.norm() == 0.0 {
+                (0.0, subdiagonal.conj() / subdiagonal.norm())
+            } else {
+                (
+                    diagonal.norm() / radius,
+                    diagonal / diagonal.norm() * subdiagonal.conj() / radius,
+                )
+            };
+            entries[column] = diagonal * cos + sin * subdiagonal;
+            entries[column + 1] = Complex64::default();
+            rotations.push((cos, sin));
+            projected_rhs.push(-sin.conj() * projected_rhs[column]);
+            projected_rhs[column] *= cos;
+            hessenberg.push(entries);
+
+            let converged = projected_rhs[column + 1].norm() <= tolerance * rhs_norm;
+            if converged || next_norm == 0.0 || iterations >= maximal_iterations {
+                break;
+            }
+            basis.push(vector.iter().map(|entry| entry / next_norm).collect());
+        }
+
+        // Back substitution for the triangular least-squares problem
+        let size = hessenberg.len();
+        let mut coefficients = vec![Complex64::default(); size];
+        for row in (0..size).rev() {
+            let mut value = projected_rhs[row];
+            for (column, coefficient) in coefficients.iter().enumerate().skip(row + 1) {
+                value -= hessenberg[column][row] * coefficient;
+            }
+            coefficients[row] = value / hessenberg[row][row];
+        }
+        for (basis_vector, coefficient) in basis.iter().zip(coefficients.iter()) {
+            for (entry, basis_entry) in solution.iter_mut().zip(basis_vector.iter()) {
+                *entry += coefficient * basis_entry;
+            }
+        }
+    }
+}
+
+/// Removes the components of a vector along a set of orthonormal vectors.
+///
+/// # Arguments
+///
+/// * `vector` - The vector that is orthogonalized.
+/// * `vectors` - The orthonormal vectors.
+fn orthogonalize<'b, I>(vector: &mut [Complex64], vectors: I)
+where
+    I: IntoIterator<Item = &'b Vec<Complex64>>,
+{
+    for other in vectors {
+        let overlap = scalar_product(other, vector);
+        for (entry, other_entry) in vector.iter_mut().zip(other.iter()) {
+            *entry -= overlap * other_entry;
+        }
+    }
+}
+
+/// Returns a deterministic pseudo-random start vector for iterative eigensolvers.
+///
+/// Generic start vectors have an overlap with all eigenvectors, unlike for example uniform vectors
+/// that only overlap with the eigenvectors in their symmetry sector.
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the vector.
+/// * `seed` - The seed distinguishing different start vectors.
+///
+/// # Returns
+///
+/// * `Vec<Complex64>` - The start vector with entries in the square [-1/2, 1/2] x [-1/2, 1/2] i.
+fn start_vector(dimension: usize, seed: u64) -> Vec<Complex64> {
+    // SplitMix64 hashing of the index
+    let hash = |value: u64| -> f64 {
+        let mut value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^= value >> 31;
+        (value >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+    (0..dimension as u64)
+        .map(|index| {
+            let key = (seed << 32) ^ (2 * index);
+            Complex64::new(hash(key), hash(key + 1))
+        })
+        .collect()
+}
+
+/// Dimension of the Krylov subspace after which the GMRES method of the steady state is restarted.
+const STEADY_STATE_RESTART: usize = 50;
+
+/// Returns the steady state of a superoperator given by its action on flattened density matrices.
+///
+/// The steady state is the density matrix p with `S flatten(p) = 0` and `Tr(p) = 1`.
+/// Adding the rank-one term |v><t| to S, with the maximally mixed state v and the trace functional t,
+/// removes the null space of S, and the resulting linear system `(S + |v><t|) flatten(p) = v`
+/// is solved with the restarted GMRES method.
+///
+/// # Arguments
+///
+/// * `superoperator` - The action of the superoperator S on a density matrix flattened in row-major form.
+/// * `dimension` - The dimension of the Hilbert space.
+/// * `tolerance` - The tolerance of the residual of the linear system relative to the norm of v.
+/// * `maximal_iterations` - The maximal number of applications of the superoperator.
+///
+/// # Returns
+///
+/// * `Ok(Array2<Complex64>)` - The hermitian steady-state density matrix with unit trace.
+/// * `Err(StruqtureError::NoConvergence)` - The solver did not converge within the maximal number of iterations.
+/// * `Err(StruqtureError)` - The superoperator could not be applied to a density matrix.
+pub(crate) fn steady_state<F>(
+    superoperator: F,
+    dimension: usize,
+    tolerance: f64,
+    maximal_iterations: usize,
+) -> Result<Array2<Complex64>, StruqtureError>
+where
+    F: Fn(&[Complex64]) -> Result<Vec<Complex64>, StruqtureError>,
+{
+    let diagonal = |index: usize| index % (dimension + 1) == 0;
+    let maximally_mixed: Vec<Complex64> = (0..dimension * dimension)
+        .map(|index| {
+            if diagonal(index) {
+                Complex64::new(1.0 / dimension as f64, 0.0)
+            } else {
+                Complex64::default()
+            }
+        })
+        .collect();
+    let trace = |vector: &[Complex64]| -> Complex64 { vector.iter().step_by(dimension + 1).sum() };
+    let solution = gmres(
+        |vector| {
+            let mut result = superoperator(vector)?;
+            let vector_trace = trace(vector);
+            for (entry, mixed) in result.iter_mut().zip(maximally_mixed.iter()) {
+                *entry += mixed * vector_trace;
+            }
+            Ok(result)
+        },
+        &maximally_mixed,
+        tolerance,
+        STEADY_STATE_RESTART,
+        maximal_iterations,
+    )?;
+    let solution_trace = trace(&solution);
+    // The hermitian part removes the numerical anti-hermitian error of the solution
+    Ok(Array2::from_shape_fn(
+        (dimension, dimension),
+        |(row, column)| {
+            (solution[row * dimension + column] + solution[column * dimension + row].conj())
+                / (2.0 * solution_trace)
+        },
+    ))
+}
+
+/// Returns the lowest eigenvalues and eigenvectors of a hermitian operator with the Lanczos method.
+///
+/// The eigenpairs are computed one after another. For each eigenpair the Lanczos iteration with full
+/// reorthogonalization is run in the orthogonal complement of the eigenvectors already found,
+/// which also resolves degenerate eigenvalues. The iteration is restarted with the lowest Ritz vector
+/// when the Krylov subspace reaches its maximal dimension.
+///
+/// # Arguments
+///
+/// * `operator` - The action of the hermitian operator on a vector.
+/// * `dimension` - The dimension of the vector space.
+/// * `number_eigenvalues` - The number of lowest eigenvalues, at most the dimension.
+/// * `tolerance` - The tolerance of the residual |A v - lambda v| relative to max(1, |lambda|).
+/// * `krylov_dimension` - The maximal dimension of the Krylov subspace before a restart.
+/// * `maximal_iterations` - The maximal total number of applications of the operator.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(f64, Vec<Complex64>)>)` - The lowest eigenvalues in ascending order with their normalized eigenvectors.
+/// * `Err(StruqtureError::NoConvergence)` - An eigenpair did not converge within the maximal number of iterations.
+/// * `Err(StruqtureError)` - The operator could not be applied to a vector.
+pub(crate) fn lanczos_lowest<F>(
+    operator: F,
+    dimension: usize,
+    number_eigenvalues: usize,
+    tolerance: f64,
+    krylov_dimension: usize,
+    maximal_iterations: usize,
+) -> Result<Vec<(f64, Vec<Complex64>)>, StruqtureError>
+where
+    F: Fn(&[Complex64]) -> Result<Vec<Complex64>, StruqtureError>,
+{
+    let krylov_dimension = krylov_dimension.max(1);
+    let mut eigenpairs: Vec<(f64, Vec<Complex64>)> = Vec::with_capacity(number_eigenvalues);
+    let mut iterations = 0;
+    for eigenpair_index in 0..number_eigenvalues.min(dimension) {
+        let available_dimension = dimension - eigenpair_index;
+        let mut start = start_vector(dimension, eigenpair_index as u64);
+        orthogonalize(&mut start, eigenpairs.iter().map(|(_, vector)| vector));
+        loop {
+            let start_norm = norm(&start);
+            let mut basis: Vec<Vec<Complex64>> =
+                vec![start.iter().map(|entry| entry / start_norm).collect()];
+            let mut diagonal: Vec<f64> = Vec::with_capacity(krylov_dimension);
+            let mut off_diagonal: Vec<f64> = Vec::with_capacity(krylov_dimension);
+            let residual_norm = loop {
+                let current = basis.len() - 1;
+                let mut vector = operator(&basis[current])?;
+                iterations += 1;
+                diagonal.push(scalar_product(&basis[current], &vector).re);
+                // Orthogonalizing twice keeps the basis orthogonal when the residual becomes small
+                for _ in 0..2 {
+                    orthogonalize(&mut vector, eigenpairs.iter().map(|(_, vector)| vector));
+                    orthogonalize(&mut vector, basis.iter());
+                }
+                let vector_norm = norm(&vector);
+                let invariant = vector_norm <= 1e-12 * diagonal[current].abs().max(1.0);
+                if invariant
+                    || basis.len() == krylov_dimension.min(available_dimension)
+                    || iterations >= maximal_iterations
+                {
+                    break if invariant { 0.0 } else { vector_norm };
+                }
+                off_diagonal.push(vector_norm);
+                basis.push(vector.iter().map(|entry| entry / vector_norm).collect());
+            };
+
+            let size = diagonal.len();
+            let tridiagonal: Array2<Complex64> =
+                Array2::from_shape_fn((size, size), |(row, column)| {
+                    if row == column {
+                        Complex64::new(diagonal[row], 0.0)
+                    } else if row + 1 == column {
+                        Complex64::new(off_diagonal[row], 0.0)
+                    } else if column + 1 == row {
+                        Complex64::new(off_diagonal[column], 0.0)
+                    } else {
+                        Complex64::default()
+                    }
+                });
+            let (ritz_values, ritz_vectors) = hermitian_eigensystem(&tridiagonal);
+            let lowest = (0..size)
+                .min_by(|left, right| {
+                    ritz_values[*left]
+                        .partial_cmp(&ritz_values[*right])
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap_or_default();
+            let eigenvalue = ritz_values[lowest];
+            let mut ritz_vector = vec![Complex64::default(); dimension];
+            for (basis_vector, coefficient) in basis.iter().zip(ritz_vectors.column(lowest).iter())
+            {
+                for (entry, basis_entry) in ritz_vector.iter_mut().zip(basis_vector.iter()) {
+                    *entry += coefficient * basis_entry;
+                }
+            }
+            let ritz_norm = norm(&ritz_vector);
+            for entry in ritz_vector.iter_mut() {
+                *entry /= ritz_norm;
+            }
+
+            let residual = residual_norm * ritz_vectors[[size - 1, lowest]].norm();
+            if residual <= tolerance * eigenvalue.abs().max(1.0) {
+                eigenpairs.push((eigenvalue, ritz_vector));
+                break;
+            }
+            if iterations >= maximal_iterations {
+                return Err(StruqtureError::NoConvergence { iterations });
+            }
+            start = ritz_vector;
+        }
+    }
+    eigenpairs.sort_by(|left, right| left.0.partial_cmp(&right.0).unwrap_or(Ordering::Equal));
+    Ok(eigenpairs)
+}
@@ -18,10 +18,11 @@
 // #![deny(missing_debug_implementations)]
 
 use num_complex::Complex64;
+use qoqo_calculator::Calculator;
 use qoqo_calculator::CalculatorComplex;
 use qoqo_calculator::CalculatorError;
 use qoqo_calculator::CalculatorFloat;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::ops::Add;
 use std::ops::Mul;
@@ -220,6 +221,12 @@ pub enum StruqtureError {
     /// Error when parsing from str
     #[error("Error parsing str into {target_type}: {msg}")]
     ParsingError { target_type: String, msg: String },
+    /// Error when a symbolic expression contains a function that cannot be differentiated.
+    #[error("The function {function} cannot be differentiated symbolically")]
+    NonDifferentiableFunction {
+        /// Name of the function
+        function: String,
+    },
     /// Error when trying to deserialize struqture data created with an incompatible version of struqture
     #[error("Trying to deserialize data created with incompatible version of struqture Library version: {library_major_version}.{library_minor_version} Data version: {data_major_version}.{data_minor_version}. Try to convert data with struqture data conversion tool.")]
     VersionMissmatch {
@@ -232,6 +239,94 @@ pub enum StruqtureError {
         /// Minor version of the data
         data_minor_version: u32,
     },
+    /// Error when a matrix is too large to be constructed in dense form.
+    #[error("The dimension {dimension} exceeds the maximal dimension {maximal_dimension} of dense matrices")]
+    DimensionTooLarge {
+        /// Dimension of the requested matrix
+        dimension: usize,
+        /// Maximal dimension of dense matrices
+        maximal_dimension: usize,
+    },
+    /// Error when the dimension of a Hilbert space does not fit into the index type.
+    #[error("The Hilbert space of {number_spins} spins and {number_modes} bosonic or fermionic modes exceeds the maximal dimension {maximal_dimension} of dense matrices")]
+    HilbertSpaceTooLarge {
+        /// Number of spins of the Hilbert space
+        number_spins: usize,
+        /// Number of bosonic and fermionic modes of the Hilbert space
+        number_modes: usize,
+        /// Maximal dimension of dense matrices
+        maximal_dimension: usize,
+    },
+    /// Error when the length of a state does not correspond to a Hilbert space the object can act on.
+    #[error("The length {length} of the state does not correspond to a Hilbert space of at least {number_spins} spins")]
+    MissmatchedStateLength {
+        /// Length of the state
+        length: usize,
+        /// Minimal number of spins of the Hilbert space
+        number_spins: usize,
+    },
+    /// Error when the length of a state does not correspond to a Fock space of fermionic modes the object can act on.
+    #[error("The length {length} of the state does not correspond to a Fock space of at least {number_modes} fermionic modes")]
+    MissmatchedStateLengthModes {
+        /// Length of the state
+        length: usize,
+        /// Minimal number of fermionic modes of the Fock space
+        number_modes: usize,
+    },
+    /// Error when the length of a flattened density matrix does not match the dimension of the Hilbert space.
+    #[error("The length {length} of the density matrix does not correspond to the dimension {dimension} of the Hilbert space")]
+    MissmatchedDensityMatrixLength {
+        /// Length of the flattened density matrix
+        length: usize,
+        /// Dimension of the Hilbert space
+        dimension: usize,
+    },
+    /// Error when the rate matrix of Lindblad noise is not positive semi-definite.
+    #[error("The rate matrix of the noise is not positive semi-definite, it has the eigenvalue {eigenvalue}")]
+    NegativeRate {
+        /// Negative eigenvalue of the rate matrix
+        eigenvalue: f64,
+    },
+    /// Error when the tolerance of an integrator is not a positive finite number.
+    #[error("The tolerance {tolerance} is not a positive finite number")]
+    InvalidTolerance {
+        /// Tolerance passed to the integrator
+        tolerance: f64,
+    },
+    /// Error when the rate matrix of Lindblad noise is not hermitian.
+    #[error("The rate matrix of the noise is not hermitian")]
+    NonHermitianRateMatrix,
+    /// Error when the times of a time grid are not in ascending order.
+    #[error("The times of the time grid are not in ascending order")]
+    UnsortedTimeGrid,
+    /// Error when an iterative solver does not converge.
+    #[error("The iterative solver did not converge within {iterations} iterations")]
+    NoConvergence {
+        /// Number of iterations performed by the solver
+        iterations: usize,
+    },
+    /// Error when a Clifford tableau or gate does not describe a valid Clifford operation.
+    #[error("The Clifford operation is not valid: {msg}")]
+    InvalidClifford {
+        /// Reason why the Clifford operation is not valid
+        msg: String,
+    },
+    /// Error when an operator couples a symmetry sector to basis states outside of the sector.
+    #[error("The operator does not conserve the symmetry: it has a non-zero entry in row {row} and column {column}, of which exactly one is in the sector")]
+    SymmetryNotConserved {
+        /// Row of the non-zero entry in the full basis
+        row: usize,
+        /// Column of the non-zero entry in the full basis
+        column: usize,
+    },
+    /// Error when an edge of a lattice does not connect two different sites of the lattice.
+    #[error("The edge ({first}, {second}) does not connect two different sites of the lattice")]
+    InvalidLatticeEdge {
+        /// First site of the edge
+        first: usize,
+        /// Second site of the edge
+        second: usize,
+    },
     /// Transparent propagation of CalculatorError.
     #[error(transparent)]
     CalculatorError(#[from] CalculatorError),
@@ -247,6 +342,461 @@ pub type CooSparseMatrix = (Vec<Complex64>, (Vec<usize>, Vec<usize>));
 /// Input in the form (value_vector, (row_index_vector, column_index_vector))
 pub type CooSparseMatrixReal = (Vec<f64>, (Vec<usize>, Vec<usize>));
 
+/// Complex sparse matrix in compressed sparse row (CSR) format.
+///
+/// Input in the form (value_vector, column_index_vector, row_pointer_vector)
+/// where the column indices of each row are sorted and unique.
+pub type CsrSparseMatrix = (Vec<Complex64>, Vec<usize>, Vec<usize>);
+
+/// Complex sparse matrix in compressed sparse column (CSC) format.
+///
+/// Input in the form (value_vector, row_index_vector, column_pointer_vector)
+/// where the row indices of each column are sorted and unique.
+pub type CscSparseMatrix = (Vec<Complex64>, Vec<usize>, Vec<usize>);
+
+/// Number of rows that are constructed in parallel before they are merged into the sparse matrix.
+#[cfg(feature = "rayon")]
+const PARALLEL_ROW_CHUNK: usize = 1 << 14;
+
+/// Returns the entries of one row sorted by column.
+fn sorted_row(entries: HashMap<usize, Complex64>) -> Vec<(usize, Complex64)> {
+    let mut entries: Vec<(usize, Complex64)> = entries.into_iter().collect();
+    entries.sort_unstable_by_key(|(column, _)| *column);
+    entries
+}
+
+/// Constructs the entries of all rows of a sparse matrix and passes them on in order of the rows.
+///
+/// The entries of each row are sorted by column.
+///
+/// # Arguments
+///
+/// * `number_rows` - The number of rows of the matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+/// * `consume` - Function receiving the row index and the sorted entries of the row.
+///
+/// # Returns
+///
+/// * `Ok(())` - All rows were constructed.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+fn for_each_row<F, G>(
+    number_rows: usize,
+    entries_on_row: F,
+    mut consume: G,
+) -> Result<(), StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+    G: FnMut(usize, Vec<(usize, Complex64)>),
+{
+    for row in 0..number_rows {
+        consume(row, sorted_row(entries_on_row(row)?));
+    }
+    Ok(())
+}
+
+/// Constructs the entries of all rows of a sparse matrix in parallel and passes them on in order of the rows.
+///
+/// The rows are constructed in parallel in chunks and passed on in order,
+/// so the result is identical to [for_each_row].
+///
+/// # Arguments
+///
+/// * `number_rows` - The number of rows of the matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+/// * `consume` - Function receiving the row index and the sorted entries of the row.
+///
+/// # Returns
+///
+/// * `Ok(())` - All rows were constructed.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+#[cfg(feature = "rayon")]
+fn for_each_row_parallel<F, G>(
+    number_rows: usize,
+    entries_on_row: F,
+    mut consume: G,
+) -> Result<(), StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError> + Sync + Send,
+    G: FnMut(usize, Vec<(usize, Complex64)>),
+{
+    use rayon::prelude::*;
+    for start in (0..number_rows).step_by(PARALLEL_ROW_CHUNK) {
+        let end = (start + PARALLEL_ROW_CHUNK).min(number_rows);
+        let rows: Vec<Vec<(usize, Complex64)>> = (start..end)
+            .into_par_iter()
+            .map(|row| entries_on_row(row).map(sorted_row))
+            .collect::<Result<Vec<Vec<(usize, Complex64)>>, StruqtureError>>()?;
+        for (row, entries) in (start..end).zip(rows) {
+            consume(row, entries);
+        }
+    }
+    Ok(())
+}
+
+/// Appends the sorted entries of one row to a sparse matrix in COO format.
+fn push_coo_row(matrix: &mut CooSparseMatrix, row: usize, entries: Vec<(usize, Complex64)>) {
+    let (values, (rows, columns)) = matrix;
+    for (column, value) in entries {
+        rows.push(row);
+        columns.push(column);
+        values.push(value);
+    }
+}
+
+/// Constructs a square sparse matrix in COO format row by row.
+///
+/// The entries are ordered by row and, within each row, by column.
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the square matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(CooSparseMatrix)` - The matrix in COO format.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+pub(crate) fn coo_from_rows<F>(
+    dimension: usize,
+    entries_on_row: F,
+) -> Result<CooSparseMatrix, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+{
+    let mut matrix: CooSparseMatrix = (
+        Vec::with_capacity(dimension),
+        (Vec::with_capacity(dimension), Vec::with_capacity(dimension)),
+    );
+    for_each_row(dimension, entries_on_row, |row, entries| {
+        push_coo_row(&mut matrix, row, entries)
+    })?;
+    Ok(matrix)
+}
+
+/// Constructs a square sparse matrix in COO format with the rows constructed in parallel.
+///
+/// The result is identical to [coo_from_rows].
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the square matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(CooSparseMatrix)` - The matrix in COO format.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+#[cfg(feature = "rayon")]
+pub(crate) fn coo_from_rows_parallel<F>(
+    dimension: usize,
+    entries_on_row: F,
+) -> Result<CooSparseMatrix, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError> + Sync + Send,
+{
+    let mut matrix: CooSparseMatrix = (
+        Vec::with_capacity(dimension),
+        (Vec::with_capacity(dimension), Vec::with_capacity(dimension)),
+    );
+    for_each_row_parallel(dimension, entries_on_row, |row, entries| {
+        push_coo_row(&mut matrix, row, entries)
+    })?;
+    Ok(matrix)
+}
+
+/// Constructs the block of a square sparse matrix in COO format restricted to a subset of the basis.
+///
+/// The rows and columns of the block are the positions of the basis states in `basis`.
+///
+/// The block is only valid if the matrix does not couple the basis states to the rest of the space.
+/// Non-zero entries in rows of the block with columns outside of it are reported while the block is constructed.
+/// Afterwards the rows outside of the block are constructed one at a time and non-zero entries
+/// with columns inside of the block are reported, so that non-hermitian matrices are checked in both directions.
+/// Only the block is stored, but the run time scales with the full dimension of the matrix.
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the full matrix.
+/// * `basis` - The indices of the basis states spanning the block.
+/// * `entries_on_row` - Function returning the entries of a row of the full matrix as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(CooSparseMatrix)` - The block of the matrix in COO format.
+/// * `Err(StruqtureError::SymmetryNotConserved)` - A non-zero entry couples a basis state of the block to a state outside of it.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+pub(crate) fn block_coo_from_rows<F>(
+    dimension: usize,
+    basis: &[usize],
+    entries_on_row: F,
+) -> Result<CooSparseMatrix, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+{
+    let positions: HashMap<usize, usize> = basis
+        .iter()
+        .enumerate()
+        .map(|(position, state)| (*state, position))
+        .collect();
+    let mut matrix: CooSparseMatrix = (
+        Vec::with_capacity(basis.len()),
+        (
+            Vec::with_capacity(basis.len()),
+            Vec::with_capacity(basis.len()),
+        ),
+    );
+    for_each_row(
+        basis.len(),
+        |position| {
+            let row = basis[position];
+            let mut entries: HashMap<usize, Complex64> = HashMap::new();
+            for (column, value) in entries_on_row(row)? {
+                if value == Complex64::default() {
+                    continue;
+                }
+                match positions.get(&column) {
+                    Some(column_position) => {
+                        entries.insert(*column_position, value);
+                    }
+                    None => return Err(StruqtureError::SymmetryNotConserved { row, column }),
+                }
+            }
+            Ok(entries)
+        },
+        |row, entries| push_coo_row(&mut matrix, row, entries),
+    )?;
+    for row in (0..dimension).filter(|row| !positions.contains_key(row)) {
+        for (column, value) in entries_on_row(row)? {
+            if value != Complex64::default() && positions.contains_key(&column) {
+                return Err(StruqtureError::SymmetryNotConserved { row, column });
+            }
+        }
+    }
+    Ok(matrix)
+}
+
+/// Constructs a square sparse matrix in CSR format row by row.
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the square matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(CsrSparseMatrix)` - The matrix in CSR format.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+pub(crate) fn csr_from_rows<F>(
+    dimension: usize,
+    entries_on_row: F,
+) -> Result<CsrSparseMatrix, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+{
+    let mut values: Vec<Complex64> = Vec::with_capacity(dimension);
+    let mut columns: Vec<usize> = Vec::with_capacity(dimension);
+    let mut row_pointers: Vec<usize> = Vec::with_capacity(dimension + 1);
+    row_pointers.push(0);
+    for_each_row(dimension, entries_on_row, |_, entries| {
+        for (column, value) in entries {
+            columns.push(column);
+            values.push(value);
+        }
+        row_pointers.push(columns.len());
+    })?;
+    Ok((values, columns, row_pointers))
+}
+
+/// Constructs a square sparse matrix in CSC format row by row.
+///
+/// The rows are constructed twice: the first pass counts the entries of each column,
+/// the second pass writes the entries directly to their position in the CSC arrays.
+/// This trades the construction time of the rows for memory, as no intermediate CSR matrix is held.
+/// The rows are passed in ascending order, so the row indices of each column are sorted.
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the square matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(CscSparseMatrix)` - The matrix in CSC format.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+pub(crate) fn csc_from_rows<F>(
+    dimension: usize,
+    entries_on_row: F,
+) -> Result<CscSparseMatrix, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+{
+    let mut column_pointers: Vec<usize> = vec![0; dimension + 1];
+    for_each_row(dimension, &entries_on_row, |_, entries| {
+        for (column, _) in entries {
+            column_pointers[column + 1] += 1;
+        }
+    })?;
+    for column in 0..dimension {
+        column_pointers[column + 1] += column_pointers[column];
+    }
+    let mut positions: Vec<usize> = column_pointers[..dimension].to_vec();
+    let mut values: Vec<Complex64> = vec![Complex64::default(); column_pointers[dimension]];
+    let mut rows: Vec<usize> = vec![0; column_pointers[dimension]];
+    for_each_row(dimension, &entries_on_row, |row, entries| {
+        for (column, value) in entries {
+            values[positions[column]] = value;
+            rows[positions[column]] = row;
+            positions[column] += 1;
+        }
+    })?;
+    Ok((values, rows, column_pointers))
+}
+
+/// Converts a CalculatorComplex into a Complex64.
+///
+/// # Arguments
+///
+/// * `value` - The CalculatorComplex to convert.
+///
+/// # Returns
+///
+/// * `Ok(Complex64)` - The converted value.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn complex_value(value: &CalculatorComplex) -> Result<Complex64, StruqtureError> {
+    Ok(Complex64::new(*value.re.float()?, *value.im.float()?))
+}
+
+/// Adds a value to the entry of a sparse matrix row.
+pub(crate) fn add_entry(entries: &mut HashMap<usize, Complex64>, column: usize, value: Complex64) {
+    *entries
+        .entry(column)
+        .or_insert_with(|| Complex64::new(0.0, 0.0)) += value;
+}
+
+/// Applies a square sparse matrix constructed row by row to a vector.
+///
+/// # Arguments
+///
+/// * `vector` - The vector the matrix is applied to, with the dimension of the matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Complex64>)` - The product of the matrix and the vector.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+pub(crate) fn apply_rows<F>(
+    vector: &[Complex64],
+    entries_on_row: F,
+) -> Result<Vec<Complex64>, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+{
+    let mut result: Vec<Complex64> = vec![Complex64::default(); vector.len()];
+    for_each_row(vector.len(), entries_on_row, |row, entries| {
+        result[row] = entries
+            .into_iter()
+            .map(|(column, value)| value * vector[column])
+            .sum();
+    })?;
+    Ok(result)
+}
+
+/// Checks that the length of a flattened density matrix matches the dimension of the Hilbert space.
+///
+/// # Arguments
+///
+/// * `length` - The length of the flattened density matrix.
+/// * `dimension` - The dimension of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Ok(())` - The length is the square of the dimension.
+/// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length is not the square of the dimension.
+pub(crate) fn check_density_matrix_length(
+    length: usize,
+    dimension: usize,
+) -> Result<(), StruqtureError> {
+    if dimension.checked_mul(dimension) == Some(length) {
+        Ok(())
+    } else {
+        Err(StruqtureError::MissmatchedDensityMatrixLength { length, dimension })
+    }
+}
+
+/// Maximal dimension of the matrices that can be constructed in dense form.
+///
+/// A dense complex matrix of this dimension occupies 256 MiB of memory.
+pub const MAXIMAL_DENSE_DIMENSION: usize = 4096;
+
+/// Returns the dimension of a dense matrix acting on `copies` copies of a Hilbert space.
+///
+/// # Arguments
+///
+/// * `local_dimensions` - The dimensions of the spins and modes the Hilbert space is the tensor product of.
+/// * `copies` - The number of copies of the Hilbert space (1 for operators, 2 for superoperators).
+/// * `number_spins` - The number of spins of the Hilbert space, used for the error.
+/// * `number_modes` - The number of bosonic and fermionic modes of the Hilbert space, used for the error.
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The dimension of the dense matrix.
+/// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+/// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+pub(crate) fn dense_dimension<I>(
+    local_dimensions: I,
+    copies: u32,
+    number_spins: usize,
+    number_modes: usize,
+) -> Result<usize, StruqtureError>
+where
+    I: IntoIterator<Item = usize>,
+{
+    // try_fold stops at the first overflow, so long iterators are not exhausted
+    match local_dimensions
+        .into_iter()
+        .try_fold(1usize, |dimension, local| dimension.checked_mul(local))
+        .and_then(|dimension| dimension.checked_pow(copies))
+    {
+        Some(dimension) if dimension <= MAXIMAL_DENSE_DIMENSION => Ok(dimension),
+        Some(dimension) => Err(StruqtureError::DimensionTooLarge {
+            dimension,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION,
+        }),
+        None => Err(StruqtureError::HilbertSpaceTooLarge {
+            number_spins,
+            number_modes,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION,
+        }),
+    }
+}
+
+/// Constructs a square dense matrix row by row.
+///
+/// # Arguments
+///
+/// * `dimension` - The dimension of the matrix.
+/// * `entries_on_row` - Function returning the entries of a row as a map from column to value.
+///
+/// # Returns
+///
+/// * `Ok(Array2<Complex64>)` - The dense matrix.
+/// * `Err(StruqtureError)` - The entries of a row could not be constructed.
+pub(crate) fn dense_from_rows<F>(
+    dimension: usize,
+    entries_on_row: F,
+) -> Result<ndarray::Array2<Complex64>, StruqtureError>
+where
+    F: Fn(usize) -> Result<HashMap<usize, Complex64>, StruqtureError>,
+{
+    let mut matrix: ndarray::Array2<Complex64> = ndarray::Array2::zeros((dimension, dimension));
+    for row in 0..dimension {
+        for (column, value) in entries_on_row(row)?.into_iter() {
+            matrix[[row, column]] = value;
+        }
+    }
+    Ok(matrix)
+}
+
 /// Trait for all hermitian indices
 pub trait SymmetricIndex:
     std::hash::Hash + Eq + Sized + Clone + std::fmt::Debug + std::fmt::Display + FromStr + Default
@@ -476,6 +1026,97 @@ pub trait ModeIndex:
     }
 }
 
+/// Returns the sign picked up when exchanging the order of two mode products on disjoint modes.
+///
+/// Bosonic products acting on different modes commute. Fermionic products acting on different
+/// modes anticommute when both contain an odd number of operators and commute otherwise.
+///
+/// # Arguments
+///
+/// * `left` - The left product.
+/// * `right` - The right product.
+/// * `fermionic` - Whether the products consist of fermionic operators.
+///
+/// # Returns
+///
+/// * `Some(f64)` - The exchange sign when the products act on disjoint sets of modes.
+/// * `None` - The products share at least one mode.
+pub(crate) fn mode_exchange_sign<T: ModeIndex>(
+    left: &T,
+    right: &T,
+    fermionic: bool,
+) -> Option<f64> {
+    let right_modes: HashSet<usize> = right
+        .creators()
+        .chain(right.annihilators())
+        .copied()
+        .collect();
+    if left
+        .creators()
+        .chain(left.annihilators())
+        .any(|mode| right_modes.contains(mode))
+    {
+        return None;
+    }
+    let odd_left = (left.number_creators() + left.number_annihilators()) % 2 == 1;
+    let odd_right = (right.number_creators() + right.number_annihilators()) % 2 == 1;
+    if fermionic && odd_left && odd_right {
+        Some(-1.0)
+    } else {
+        Some(1.0)
+    }
+}
+
+/// Parses a sequence of creation and annihilation operators in arbitrary order.
+///
+/// Creators are written as `c` and annihilators as `a` followed by the index of the mode,
+/// e.g. `a1 c0 a0 c2`. Whitespace between the operators is ignored, an empty string or `I` is the identity.
+///
+/// # Arguments
+///
+/// * `s` - The string to parse.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(bool, usize)>)` - The operators in order from left to right, `true` for creators and `false` for annihilators, with their modes.
+/// * `Err(StruqtureError::FromStringFailed)` - Used operator that is neither 'c' nor 'a'.
+/// * `Err(StruqtureError::FromStringFailed)` - Operator is not followed by an integer index.
+pub(crate) fn parse_ladder_operators(s: &str) -> Result<Vec<(bool, usize)>, StruqtureError> {
+    if s.trim() == "I" {
+        return Ok(Vec::new());
+    }
+    let mut operators: Vec<(bool, usize)> = Vec::new();
+    let mut characters = s.chars().filter(|c| !c.is_whitespace()).peekable();
+    while let Some(operator) = characters.next() {
+        let is_creator = match operator {
+            'c' => true,
+            'a' => false,
+            _ => {
+                return Err(StruqtureError::FromStringFailed {
+                    msg: format!(
+                        "Used operator {} that is neither 'c' nor 'a' in operator sequence {}",
+                        operator, s
+                    ),
+                })
+            }
+        };
+        let mut index = String::new();
+        while let Some(digit) = characters.next_if(|c| c.is_ascii_digit()) {
+            index.push(digit);
+        }
+        let mode: usize = index
+            .parse()
+            .map_err(|_| StruqtureError::FromStringFailed {
+                msg: format!(
+                    "Operator {} is not followed by an integer index in operator sequence {}",
+                    operator, s
+                ),
+            })?;
+        operators.push((is_creator, mode));
+    }
+    Ok(operators)
+}
+
 /// Trait for transforming value stored at index I when using index of different type T to read out value
 /// e.g. Hermitian Hamiltonian H but we access H[NOIndex(2,1)] -> H[HermitianIndex(1,2)].conj()
 pub trait GetValue<T> {
@@ -569,6 +1210,180 @@ impl TruncateTrait for CalculatorFloat {
     }
 }
 
+/// Helper trait to substitute the symbolic parameters of values with numbers.
+///
+/// Implemented for CalculatorFloat and CalculatorComplex, the values of operators, Hamiltonians and noise operators.
+/// The expressions are evaluated with the variables set in a qoqo_calculator Calculator.
+pub trait SubstituteParametersTrait: Sized {
+    /// Substitutes the symbolic parameters in Self with the values set in the calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `calculator` - The Calculator containing the values of the symbolic parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The numeric version of Self.
+    /// * `Err(StruqtureError::CalculatorError)` - A symbolic expression could not be evaluated, e.g. because a parameter is not set.
+    fn substitute_parameters(&self, calculator: &Calculator) -> Result<Self, StruqtureError>;
+}
+
+impl SubstituteParametersTrait for CalculatorComplex {
+    fn substitute_parameters(&self, calculator: &Calculator) -> Result<Self, StruqtureError> {
+        Ok(CalculatorComplex::new(
+            calculator.parse_get(self.re.clone())?,
+            calculator.parse_get(self.im.clone())?,
+        ))
+    }
+}
+
+impl SubstituteParametersTrait for CalculatorFloat {
+    fn substitute_parameters(&self, calculator: &Calculator) -> Result<Self, StruqtureError> {
+        Ok(CalculatorFloat::from(calculator.parse_get(self.clone())?))
+    }
+}
+
+/// Helper trait to enumerate the symbolic parameters of values.
+///
+/// Implemented for CalculatorFloat and CalculatorComplex, numeric values have no free parameters.
+pub trait FreeParametersTrait {
+    /// Returns the names of all symbolic parameters in Self.
+    ///
+    /// Names followed by an opening parenthesis are functions and not parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `HashSet<String>` - The names of the symbolic parameters in Self.
+    fn free_parameters(&self) -> HashSet<String>;
+}
+
+impl FreeParametersTrait for CalculatorComplex {
+    fn free_parameters(&self) -> HashSet<String> {
+        let mut parameters = self.re.free_parameters();
+        parameters.extend(self.im.free_parameters());
+        parameters
+    }
+}
+
+impl FreeParametersTrait for CalculatorFloat {
+    fn free_parameters(&self) -> HashSet<String> {
+        match self {
+            CalculatorFloat::Float(_) => HashSet::new(),
+            CalculatorFloat::Str(expression) => symbolic_expression::free_parameters(expression),
+        }
+    }
+}
+
+/// Computes `left * right + sign * right * left` product by product.
+///
+/// Pairs of products with a known exchange sign are only multiplied once, as their reversed
+/// product is the product itself times the exchange sign.
+///
+/// # Arguments
+///
+/// * `left` - The operator on the left-hand side.
+/// * `right` - The operator on the right-hand side.
+/// * `sign` - The sign of the reversed product (-1.0 for the commutator, 1.0 for the anticommutator).
+/// * `result` - The empty operator the terms are added to.
+/// * `exchange_sign` - Function returning the exchange sign of two products if it is known without multiplying them.
+/// * `expand` - Function expanding the output of the product multiplication into (product, coefficient) pairs.
+///
+/// # Returns
+///
+/// * `Ok(O)` - The result operator with all terms added.
+/// * `Err(StruqtureError)` - The products could not be multiplied or added to the result.
+pub(crate) fn commutation<'a, T, O, M, F, G>(
+    left: &'a T,
+    right: &'a T,
+    sign: f64,
+    mut result: O,
+    exchange_sign: F,
+    expand: G,
+) -> Result<O, StruqtureError>
+where
+    T: OperateOnDensityMatrix<'a>,
+    &'a T: IntoIterator,
+    T::Index: Clone + Mul<T::Index, Output = M>,
+    T::Value: Clone,
+    CalculatorComplex: From<T::Value>,
+    T::IteratorType: ExactSizeIterator<Item = (&'a T::Index, &'a T::Value)>,
+    T::KeyIteratorType: ExactSizeIterator<Item = &'a T::Index>,
+    T::ValueIteratorType: ExactSizeIterator<Item = &'a T::Value>,
+    O: OperateOnDensityMatrix<'a, Value = CalculatorComplex>,
+    &'a O: IntoIterator,
+    O::Index: Clone,
+    O::IteratorType: ExactSizeIterator<Item = (&'a O::Index, &'a CalculatorComplex)>,
+    O::KeyIteratorType: ExactSizeIterator<Item = &'a O::Index>,
+    O::ValueIteratorType: ExactSizeIterator<Item = &'a CalculatorComplex>,
+    F: Fn(&T::Index, &T::Index) -> Option<f64>,
+    G: Fn(M) -> Result<Vec<(O::Index, CalculatorComplex)>, StruqtureError>,
+{
+    for (key_left, value_left) in left.iter() {
+        for (key_right, value_right) in right.iter() {
+            let coefficient = CalculatorComplex::from(value_left.clone())
+                * CalculatorComplex::from(value_right.clone());
+            match exchange_sign(key_left, key_right) {
+                Some(exchange) => {
+                    let prefactor = 1.0 + sign * exchange;
+                    if prefactor == 0.0 {
+                        continue;
+                    }
+                    for (product, factor) in expand(key_left.clone() * key_right.clone())? {
+                        result.add_operator_product(
+                            product,
+                            coefficient.clone() * factor * prefactor,
+                        )?;
+                    }
+                }
+                None => {
+                    for (product, factor) in expand(key_left.clone() * key_right.clone())? {
+                        result.add_operator_product(product, coefficient.clone() * factor)?;
+                    }
+                    for (product, factor) in expand(key_right.clone() * key_left.clone())? {
+                        result
+                            .add_operator_product(product, coefficient.clone() * factor * sign)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Helper trait to differentiate symbolic values with respect to a parameter.
+///
+/// Implemented for CalculatorFloat and CalculatorComplex. The derivative is returned as an
+/// expression that can be evaluated by a qoqo_calculator Calculator.
+pub trait DerivativeTrait: Sized {
+    /// Differentiates Self symbolically with respect to a parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameter` - The name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The derivative of Self, numeric if it does not depend on any parameter.
+    /// * `Err(StruqtureError::ParsingError)` - A symbolic expression could not be parsed.
+    /// * `Err(StruqtureError::NonDifferentiableFunction)` - A symbolic expression contains a function that cannot be differentiated.
+    fn derivative(&self, parameter: &str) -> Result<Self, StruqtureError>;
+}
+
+impl DerivativeTrait for CalculatorComplex {
+    fn derivative(&self, parameter: &str) -> Result<Self, StruqtureError> {
+        Ok(CalculatorComplex {
+            re: self.re.derivative(parameter)?,
+            im: self.im.derivative(parameter)?,
+        })
+    }
+}
+
+impl DerivativeTrait for CalculatorFloat {
+    fn derivative(&self, parameter: &str) -> Result<Self, StruqtureError> {
+        symbolic_expression::differentiate(self, parameter)
+    }
+}
+
 /// Helper trait to allow hermitian conjugation of values
 /// Should eventually be ported to qoqo_calculator like this
 /// and be implemented for CalculatorFloat, CaclulatorComplex, f64 and Complexf64
@@ -766,6 +1581,65 @@ where
         }));
         new_self
     }
+
+    /// Differentiates the coefficients of Self symbolically with respect to a parameter.
+    ///
+    /// Terms whose derivative is zero are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameter` - The name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The derivative of Self.
+    /// * `Err(StruqtureError::ParsingError)` - A coefficient could not be parsed.
+    /// * `Err(StruqtureError::NonDifferentiableFunction)` - A coefficient contains a function that cannot be differentiated.
+    fn derivative(&'a self, parameter: &str) -> Result<Self, StruqtureError>
+    where
+        Self::Value: DerivativeTrait,
+    {
+        let mut new_self = self.empty_clone(Some(self.len()));
+        for (key, value) in self.iter() {
+            new_self.set(key.clone(), value.derivative(parameter)?)?;
+        }
+        Ok(new_self)
+    }
+
+    /// Returns the names of all symbolic parameters in the coefficients of Self.
+    ///
+    /// # Returns
+    ///
+    /// * `HashSet<String>` - The names of the symbolic parameters.
+    fn free_parameters(&'a self) -> HashSet<String>
+    where
+        Self::Value: FreeParametersTrait,
+    {
+        self.values()
+            .flat_map(|value| value.free_parameters())
+            .collect()
+    }
+
+    /// Substitutes the symbolic parameters in the coefficients of Self.
+    ///
+    /// # Arguments
+    ///
+    /// * `calculator` - The Calculator containing the values of the symbolic parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - A copy of Self with numeric coefficients.
+    /// * `Err(StruqtureError::CalculatorError)` - A coefficient could not be evaluated, e.g. because a parameter is not set.
+    fn substitute_parameters(&'a self, calculator: &Calculator) -> Result<Self, StruqtureError>
+    where
+        Self::Value: SubstituteParametersTrait,
+    {
+        let mut new_self = self.empty_clone(Some(self.len()));
+        for (key, value) in self.iter() {
+            new_self.set(key.clone(), value.substitute_parameters(calculator)?)?;
+        }
+        Ok(new_self)
+    }
 }
 
 /// Trait for representing complete open systems
@@ -901,6 +1775,44 @@ where
         Self::group(new_system, new_noise)
             .expect("Internal error: System and Noise size unexpectedly do not match")
     }
+
+    /// Returns the names of all symbolic parameters in the coefficients of the system and the noise of Self.
+    ///
+    /// # Returns
+    ///
+    /// * `HashSet<String>` - The names of the symbolic parameters.
+    fn free_parameters(&'a self) -> HashSet<String>
+    where
+        <<Self as OpenSystem<'a>>::System as OperateOnDensityMatrix<'a>>::Value:
+            FreeParametersTrait,
+        <<Self as OpenSystem<'a>>::Noise as OperateOnDensityMatrix<'a>>::Value: FreeParametersTrait,
+    {
+        let mut parameters = self.system().free_parameters();
+        parameters.extend(self.noise().free_parameters());
+        parameters
+    }
+
+    /// Substitutes the symbolic parameters in the coefficients of the system and the noise of Self.
+    ///
+    /// # Arguments
+    ///
+    /// * `calculator` - The Calculator containing the values of the symbolic parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - A copy of Self with numeric coefficients.
+    /// * `Err(StruqtureError::CalculatorError)` - A coefficient could not be evaluated, e.g. because a parameter is not set.
+    fn substitute_parameters(&'a self, calculator: &Calculator) -> Result<Self, StruqtureError>
+    where
+        <<Self as OpenSystem<'a>>::System as OperateOnDensityMatrix<'a>>::Value:
+            SubstituteParametersTrait,
+        <<Self as OpenSystem<'a>>::Noise as OperateOnDensityMatrix<'a>>::Value:
+            SubstituteParametersTrait,
+    {
+        let new_system = self.system().substitute_parameters(calculator)?;
+        let new_noise = self.noise().substitute_parameters(calculator)?;
+        Self::group(new_system, new_noise)
+    }
 }
 
 /// Trait for all objects that can act on a quantum state like an operator.
@@ -999,9 +1911,14 @@ type CreatorsAnnihilators = (TinyVec<[usize; 2]>, TinyVec<[usize; 2]>);
 
 pub mod bosons;
 pub mod fermions;
+mod iterative_solvers;
+pub mod mappings;
 pub mod mixed_systems;
+pub mod models;
 pub mod prelude;
 pub mod spins;
+mod symbolic_expression;
+mod time_evolution;
 
 /// Shorhand type for TinyVec representation of creators or annihilators
 #[cfg(test)]
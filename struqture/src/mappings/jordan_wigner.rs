@@ -0,0 +1,359 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{JordanWignerFermionToSpin, JordanWignerSpinToFermion};
+use crate::fermions::{
+    FermionHamiltonian, FermionHamiltonianSystem, FermionOperator, FermionProduct,
+    FermionQubitEncoding, FermionSystem, HermitianFermionProduct, JordanWignerEncoding,
+};
+use crate::spins::{
+    PauliProduct, SingleSpinOperator, SpinHamiltonian, SpinHamiltonianSystem, SpinOperator,
+    SpinSystem,
+};
+use crate::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, SpinIndex, SymmetricIndex};
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+
+impl JordanWignerFermionToSpin for FermionProduct {
+    type Output = SpinOperator;
+
+    /// Implements JordanWignerFermionToSpin for a FermionProduct.
+    ///
+    /// The creators are multiplied in order, followed by the annihilators in order,
+    /// using the Majorana operators of the [JordanWignerEncoding].
+    ///
+    /// # Returns
+    ///
+    /// `SpinOperator` - The spin operator that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in encode_fermion_product.
+    fn jordan_wigner(&self) -> Self::Output {
+        JordanWignerEncoding
+            .encode_fermion_product(self, self.current_number_modes())
+            .expect("Internal bug in encode_fermion_product")
+    }
+}
+
+impl JordanWignerFermionToSpin for HermitianFermionProduct {
+    type Output = SpinHamiltonian;
+
+    /// Implements JordanWignerFermionToSpin for a HermitianFermionProduct.
+    ///
+    /// The HermitianFermionProduct is interpreted as the hermitian operator P + P^†,
+    /// or as P if the product is naturally hermitian.
+    ///
+    /// # Returns
+    ///
+    /// `SpinHamiltonian` - The spin Hamiltonian that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in set.
+    fn jordan_wigner(&self) -> Self::Output {
+        let mut hamiltonian = FermionHamiltonian::with_capacity(1);
+        hamiltonian
+            .set(self.clone(), CalculatorComplex::from(1.0))
+            .expect("Internal bug in set");
+        hamiltonian.jordan_wigner()
+    }
+}
+
+impl JordanWignerFermionToSpin for FermionOperator {
+    type Output = SpinOperator;
+
+    /// Implements JordanWignerFermionToSpin for a FermionOperator.
+    ///
+    /// # Returns
+    ///
+    /// `SpinOperator` - The spin operator that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in encode_fermion_operator.
+    fn jordan_wigner(&self) -> Self::Output {
+        JordanWignerEncoding
+            .encode_fermion_operator(self, self.current_number_modes(), None)
+            .expect("Internal bug in encode_fermion_operator")
+    }
+}
+
+impl JordanWignerFermionToSpin for FermionHamiltonian {
+    type Output = SpinHamiltonian;
+
+    /// Implements JordanWignerFermionToSpin for a FermionHamiltonian.
+    ///
+    /// # Returns
+    ///
+    /// `SpinHamiltonian` - The spin Hamiltonian that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in encode_fermion_hamiltonian.
+    fn jordan_wigner(&self) -> Self::Output {
+        JordanWignerEncoding
+            .encode_fermion_hamiltonian(self, self.current_number_modes(), None)
+            .expect("Internal bug in encode_fermion_hamiltonian")
+    }
+}
+
+impl JordanWignerFermionToSpin for FermionSystem {
+    type Output = SpinSystem;
+
+    /// Implements JordanWignerFermionToSpin for a FermionSystem.
+    ///
+    /// The number of spins of the resulting SpinSystem is the number of modes of the FermionSystem.
+    ///
+    /// # Returns
+    ///
+    /// `SpinSystem` - The spin system that results from the transformation.
+    fn jordan_wigner(&self) -> Self::Output {
+        SpinSystem {
+            number_spins: self.number_modes,
+            operator: self.operator.jordan_wigner(),
+        }
+    }
+}
+
+impl JordanWignerFermionToSpin for FermionHamiltonianSystem {
+    type Output = SpinHamiltonianSystem;
+
+    /// Implements JordanWignerFermionToSpin for a FermionHamiltonianSystem.
+    ///
+    /// The number of spins of the resulting SpinHamiltonianSystem is the number of modes of the FermionHamiltonianSystem.
+    ///
+    /// # Returns
+    ///
+    /// `SpinHamiltonianSystem` - The spin Hamiltonian system that results from the transformation.
+    fn jordan_wigner(&self) -> Self::Output {
+        SpinHamiltonianSystem {
+            number_spins: self.number_modes,
+            hamiltonian: self.hamiltonian.jordan_wigner(),
+        }
+    }
+}
+
+/// Returns the fermionic representation of a local factor of a transformed PauliProduct.
+///
+/// The local factor is the single spin operator on the given site, multiplied from the right
+/// by (1 - 2 n_j) if an odd number of σ^x or σ^y operators act on higher sites.
+///
+/// # Arguments
+///
+/// * `mode` - The site/mode the factor acts on.
+/// * `operator` - The single spin operator acting on the site.
+/// * `odd_parity` - Whether the parity string (1 - 2 n_j) of operators on higher sites acts on the site.
+///
+/// # Returns
+///
+/// * `Option<FermionOperator>` - The local factor, or None if the local factor is the identity.
+fn local_inverse_jordan_wigner(
+    mode: usize,
+    operator: SingleSpinOperator,
+    odd_parity: bool,
+) -> Option<FermionOperator> {
+    let identity = FermionProduct::new([], []).expect("Internal bug in FermionProduct::new");
+    let number = FermionProduct::new([mode], [mode]).expect("Internal bug in FermionProduct::new");
+    let creator = FermionProduct::new([mode], []).expect("Internal bug in FermionProduct::new");
+    let annihilator = FermionProduct::new([], [mode]).expect("Internal bug in FermionProduct::new");
+    // Uses (c^† + c)(1 - 2n) = c^† - c and (c^† - c)(1 - 2n) = c^† + c.
+    let entries: Vec<(FermionProduct, CalculatorComplex)> = match (operator, odd_parity) {
+        (SingleSpinOperator::Identity, false) | (SingleSpinOperator::Z, true) => return None,
+        (SingleSpinOperator::Identity, true) | (SingleSpinOperator::Z, false) => vec![
+            (identity, CalculatorComplex::from(1.0)),
+            (number, CalculatorComplex::from(-2.0)),
+        ],
+        (SingleSpinOperator::X, false) | (SingleSpinOperator::Y, true) => {
+            let prefactor = if operator == SingleSpinOperator::X {
+                CalculatorComplex::from(1.0)
+            } else {
+                CalculatorComplex::new(0.0, 1.0)
+            };
+            vec![(creator, prefactor.clone()), (annihilator, prefactor)]
+        }
+        (SingleSpinOperator::X, true) | (SingleSpinOperator::Y, false) => {
+            let prefactor = if operator == SingleSpinOperator::X {
+                CalculatorComplex::from(1.0)
+            } else {
+                CalculatorComplex::new(0.0, 1.0)
+            };
+            vec![
+                (creator, prefactor.clone()),
+                (annihilator, prefactor * -1.0),
+            ]
+        }
+    };
+    Some(entries.into_iter().collect())
+}
+
+impl JordanWignerSpinToFermion for PauliProduct {
+    type Output = FermionOperator;
+
+    /// Implements JordanWignerSpinToFermion for a PauliProduct.
+    ///
+    /// The parity strings of the σ^x and σ^y operators are combined site by site before
+    /// the local factors are multiplied, so only the sites below the highest σ^x/σ^y operator
+    /// and the sites with σ^z operators contribute number operators.
+    ///
+    /// # Returns
+    ///
+    /// `FermionOperator` - The fermionic operator that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in set.
+    fn jordan_wigner(&self) -> Self::Output {
+        let number_sites = self.iter().map(|(site, _)| site + 1).max().unwrap_or(0);
+        let mut fermion_operator = FermionOperator::new();
+        fermion_operator
+            .set(
+                FermionProduct::new([], []).expect("Internal bug in FermionProduct::new"),
+                CalculatorComplex::from(1.0),
+            )
+            .expect("Internal bug in set");
+
+        let mut local_factors: Vec<FermionOperator> = Vec::with_capacity(number_sites);
+        let mut odd_parity = false;
+        for site in (0..number_sites).rev() {
+            let operator = *self.get(&site).unwrap_or(&SingleSpinOperator::Identity);
+            if let Some(local_factor) = local_inverse_jordan_wigner(site, operator, odd_parity) {
+                local_factors.push(local_factor);
+            }
+            if operator == SingleSpinOperator::X || operator == SingleSpinOperator::Y {
+                odd_parity = !odd_parity;
+            }
+        }
+        for local_factor in local_factors.into_iter().rev() {
+            fermion_operator = fermion_operator * local_factor;
+        }
+        fermion_operator
+    }
+}
+
+impl JordanWignerSpinToFermion for SpinOperator {
+    type Output = FermionOperator;
+
+    /// Implements JordanWignerSpinToFermion for a SpinOperator.
+    ///
+    /// # Returns
+    ///
+    /// `FermionOperator` - The fermionic operator that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn jordan_wigner(&self) -> Self::Output {
+        let mut fermion_operator = FermionOperator::new();
+        for (pauli_product, value) in self.iter() {
+            for (fermion_product, coefficient) in pauli_product.jordan_wigner() {
+                fermion_operator
+                    .add_operator_product(fermion_product, coefficient * value)
+                    .expect("Internal bug in add_operator_product");
+            }
+        }
+        fermion_operator
+    }
+}
+
+impl JordanWignerSpinToFermion for SpinHamiltonian {
+    type Output = FermionHamiltonian;
+
+    /// Implements JordanWignerSpinToFermion for a SpinHamiltonian.
+    ///
+    /// Of each pair of hermitian conjugated products only one is stored in the FermionHamiltonian.
+    ///
+    /// # Returns
+    ///
+    /// `FermionHamiltonian` - The fermionic Hamiltonian that results from the transformation.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn jordan_wigner(&self) -> Self::Output {
+        let mut fermion_operator = FermionOperator::new();
+        for (pauli_product, value) in self.iter() {
+            for (fermion_product, coefficient) in pauli_product.jordan_wigner() {
+                fermion_operator
+                    .add_operator_product(fermion_product, coefficient * value.clone())
+                    .expect("Internal bug in add_operator_product");
+            }
+        }
+
+        let mut hamiltonian = FermionHamiltonian::new();
+        for (fermion_product, value) in fermion_operator.into_iter() {
+            let hermitian_product = match HermitianFermionProduct::new(
+                fermion_product.creators().copied(),
+                fermion_product.annihilators().copied(),
+            ) {
+                Ok(hermitian_product) => hermitian_product,
+                Err(_) => continue,
+            };
+            let value = if fermion_product.is_natural_hermitian() {
+                // The imaginary part vanishes for hermitian input
+                CalculatorComplex::new(value.re, CalculatorFloat::ZERO)
+            } else {
+                // If both the product and its conjugate are valid HermitianFermionProducts,
+                // only the smaller one is kept
+                let (conjugate, _) = fermion_product.hermitian_conjugate();
+                let conjugate_is_valid = HermitianFermionProduct::new(
+                    conjugate.creators().copied(),
+                    conjugate.annihilators().copied(),
+                )
+                .is_ok();
+                if conjugate_is_valid && conjugate < fermion_product {
+                    continue;
+                }
+                value
+            };
+            hamiltonian
+                .add_operator_product(hermitian_product, value)
+                .expect("Internal bug in add_operator_product");
+        }
+        hamiltonian
+    }
+}
+
+impl JordanWignerSpinToFermion for SpinSystem {
+    type Output = FermionSystem;
+
+    /// Implements JordanWignerSpinToFermion for a SpinSystem.
+    ///
+    /// The number of modes of the resulting FermionSystem is the number of spins of the SpinSystem.
+    ///
+    /// # Returns
+    ///
+    /// `FermionSystem` - The fermionic system that results from the transformation.
+    fn jordan_wigner(&self) -> Self::Output {
+        FermionSystem {
+            number_modes: self.number_spins,
+            operator: self.operator.jordan_wigner(),
+        }
+    }
+}
+
+impl JordanWignerSpinToFermion for SpinHamiltonianSystem {
+    type Output = FermionHamiltonianSystem;
+
+    /// Implements JordanWignerSpinToFermion for a SpinHamiltonianSystem.
+    ///
+    /// The number of modes of the resulting FermionHamiltonianSystem is the number of spins of the SpinHamiltonianSystem.
+    ///
+    /// # Returns
+    ///
+    /// `FermionHamiltonianSystem` - The fermionic Hamiltonian system that results from the transformation.
+    fn jordan_wigner(&self) -> Self::Output {
+        FermionHamiltonianSystem {
+            number_modes: self.number_spins,
+            hamiltonian: self.hamiltonian.jordan_wigner(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Module for the mappings between the different physical types of struqture.
+//!
+//! Currently implemented is the Jordan-Wigner transformation between fermionic and spin objects.
+//! Fermionic mode `j` is mapped onto spin `j`, where an occupied mode corresponds to the spin state |1>
+//! (eigenvalue -1 of σ^z). The creation and annihilation operators are mapped to
+//!
+//! c_j^† = (∏_{k<j} σ^z_k) σ^+_j and c_j = (∏_{k<j} σ^z_k) σ^-_j,
+//!
+//! with σ^+ = (σ^x - iσ^y)/2 = |1><0| and σ^- = (σ^x + iσ^y)/2 = |0><1|.
+
+mod jordan_wigner;
+
+/// Trait for transforming fermionic indices and operators into spin objects with the Jordan-Wigner transformation.
+///
+/// # Example
+///
+/// ```
+/// use struqture::prelude::*;
+/// use struqture::mappings::JordanWignerFermionToSpin;
+/// use struqture::fermions::FermionProduct;
+/// use struqture::spins::PauliProduct;
+/// use qoqo_calculator::CalculatorComplex;
+///
+/// let fp = FermionProduct::new([0], [0]).unwrap();
+/// let so = fp.jordan_wigner();
+///
+/// assert_eq!(so.get(&PauliProduct::new()), &CalculatorComplex::from(0.5));
+/// assert_eq!(so.get(&PauliProduct::new().z(0)), &CalculatorComplex::from(-0.5));
+/// ```
+pub trait JordanWignerFermionToSpin {
+    /// The spin type the fermionic object is mapped to.
+    type Output;
+
+    /// Transforms the given fermionic object into a spin object using the Jordan-Wigner mapping.
+    ///
+    /// # Returns
+    ///
+    /// * `Self::Output` - The spin object corresponding to the fermionic object.
+    fn jordan_wigner(&self) -> Self::Output;
+}
+
+/// Trait for transforming spin indices and operators into fermionic objects with the inverse Jordan-Wigner transformation.
+///
+/// # Example
+///
+/// ```
+/// use struqture::prelude::*;
+/// use struqture::mappings::JordanWignerSpinToFermion;
+/// use struqture::fermions::FermionProduct;
+/// use struqture::spins::PauliProduct;
+/// use qoqo_calculator::CalculatorComplex;
+///
+/// let pp = PauliProduct::new().z(0);
+/// let fo = pp.jordan_wigner();
+///
+/// assert_eq!(fo.get(&FermionProduct::new([], []).unwrap()), &CalculatorComplex::from(1.0));
+/// assert_eq!(fo.get(&FermionProduct::new([0], [0]).unwrap()), &CalculatorComplex::from(-2.0));
+/// ```
+pub trait JordanWignerSpinToFermion {
+    /// The fermionic type the spin object is mapped to.
+    type Output;
+
+    /// Transforms the given spin object into a fermionic object using the inverse Jordan-Wigner mapping.
+    ///
+    /// # Returns
+    ///
+    /// * `Self::Output` - The fermionic object corresponding to the spin object.
+    fn jordan_wigner(&self) -> Self::Output;
+}
@@ -10,11 +10,16 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{HermitianMixedProduct, MixedIndex, MixedOperator, OperateOnMixedSystems};
+use super::{
+    mixed_commutator_entries_on_row, mixed_entries_on_row, mixed_hamiltonian_terms,
+    HermitianMixedProduct, MixedHilbertSpace, MixedIndex, MixedOperator, OperateOnMixedSystems,
+    ToMixedSparseMatrixOperator, ToMixedSparseMatrixSuperOperator,
+};
 use crate::{
     ModeIndex, OperateOnDensityMatrix, OperateOnState, SpinIndex, StruqtureError, StruqtureVersion,
     SymmetricIndex,
 };
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -257,6 +262,28 @@ impl<'a> OperateOnMixedSystems<'a> for MixedHamiltonian {
     }
 }
 
+impl<'a> ToMixedSparseMatrixOperator<'a> for MixedHamiltonian {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        mixed_entries_on_row(&mixed_hamiltonian_terms(self)?, row, hilbert_space)
+    }
+}
+
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedHamiltonian {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        mixed_commutator_entries_on_row(&mixed_hamiltonian_terms(self)?, row, hilbert_space)
+    }
+}
+
 /// Implements the default function (Default trait) of MixedHamiltonian (an empty MixedHamiltonian).
 ///
 impl Default for MixedHamiltonian {
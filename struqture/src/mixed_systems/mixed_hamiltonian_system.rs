@@ -11,14 +11,17 @@
 // limitations under the License.
 
 use super::{
-    HermitianMixedProduct, HermitianOperateOnMixedSystems, MixedHamiltonian, MixedSystem,
-    OperateOnMixedSystems,
+    HermitianMixedProduct, HermitianOperateOnMixedSystems, MixedHamiltonian, MixedHilbertSpace,
+    MixedSystem, OperateOnMixedSystems, ToMixedSparseMatrixOperator,
+    ToMixedSparseMatrixSuperOperator,
 };
 use crate::prelude::*;
 use crate::{OperateOnDensityMatrix, OperateOnState, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -275,6 +278,30 @@ impl<'a> OperateOnMixedSystems<'a> for MixedHamiltonianSystem {
 
 impl<'a> HermitianOperateOnMixedSystems<'a> for MixedHamiltonianSystem {}
 
+impl<'a> ToMixedSparseMatrixOperator<'a> for MixedHamiltonianSystem {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.hamiltonian
+            .sparse_matrix_entries_on_row(row, hilbert_space)
+    }
+}
+
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedHamiltonianSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.hamiltonian
+            .sparse_matrix_superoperator_entries_on_row(row, hilbert_space)
+    }
+}
+
 /// Functions for the MixedHamiltonianSystem
 ///
 impl MixedHamiltonianSystem {
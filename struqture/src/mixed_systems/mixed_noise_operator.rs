@@ -10,9 +10,13 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{MixedDecoherenceProduct, MixedIndex, OperateOnMixedSystems};
+use super::{
+    mixed_lindblad_entries_on_row, MixedDecoherenceProduct, MixedHilbertSpace, MixedIndex,
+    OperateOnMixedSystems, ToMixedSparseMatrixSuperOperator,
+};
 use crate::prelude::*;
-use crate::{OperateOnDensityMatrix, StruqtureError, StruqtureVersion};
+use crate::{complex_value, OperateOnDensityMatrix, StruqtureError, StruqtureVersion};
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -299,6 +303,22 @@ impl<'a> OperateOnMixedSystems<'a> for MixedLindbladNoiseOperator {
     }
 }
 
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedLindbladNoiseOperator {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        let mut terms: Vec<(MixedDecoherenceProduct, MixedDecoherenceProduct, Complex64)> =
+            Vec::with_capacity(self.len());
+        for ((left, right), rate) in self.iter() {
+            terms.push((left.clone(), right.clone(), complex_value(rate)?));
+        }
+        mixed_lindblad_entries_on_row(&terms, row, hilbert_space)
+    }
+}
+
 /// Implements the default function (Default trait) of MixedLindbladNoiseOperator (an empty MixedLindbladNoiseOperator).
 ///
 impl Default for MixedLindbladNoiseOperator {
@@ -11,13 +11,16 @@
 // limitations under the License.
 
 use super::{
-    MixedDecoherenceProduct, MixedIndex, MixedLindbladNoiseOperator, OperateOnMixedSystems,
+    MixedDecoherenceProduct, MixedHilbertSpace, MixedIndex, MixedLindbladNoiseOperator,
+    OperateOnMixedSystems, ToMixedSparseMatrixSuperOperator,
 };
 use crate::prelude::*;
 use crate::{OperateOnDensityMatrix, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::iter::{FromIterator, IntoIterator};
 use std::{
     fmt::{self, Write},
@@ -317,6 +320,18 @@ impl<'a> OperateOnMixedSystems<'a> for MixedLindbladNoiseSystem {
     }
 }
 
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedLindbladNoiseSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_superoperator_entries_on_row(row, hilbert_space)
+    }
+}
+
 /// Functions for the MixedLindbladNoiseSystem.
 ///
 impl MixedLindbladNoiseSystem {
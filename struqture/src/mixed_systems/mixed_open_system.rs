@@ -10,10 +10,17 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{MixedHamiltonianSystem, MixedLindbladNoiseSystem, OperateOnMixedSystems};
-use crate::{OpenSystem, OperateOnDensityMatrix, StruqtureError};
+use super::{
+    MixedDecoherenceProduct, MixedHamiltonianSystem, MixedHilbertSpace, MixedIndex,
+    MixedLindbladNoiseSystem, MixedOperator, MixedProduct, OperateOnMixedSystems,
+    ToMixedSparseMatrixSuperOperator,
+};
+use crate::spins::{DecoherenceProduct, PauliProduct};
+use crate::{ConjugationTrait, OpenSystem, OperateOnDensityMatrix, StruqtureError, SymmetricIndex};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorFloat;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::ops;
 use tinyvec::TinyVec;
@@ -187,6 +194,32 @@ impl<'a> OperateOnMixedSystems<'a> for MixedLindbladOpenSystem {
     }
 }
 
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedLindbladOpenSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        let mut system_row = self
+            .system
+            .sparse_matrix_superoperator_entries_on_row(row, hilbert_space)?;
+        let noise_row = self
+            .noise
+            .sparse_matrix_superoperator_entries_on_row(row, hilbert_space)?;
+        for (key, val) in noise_row.into_iter() {
+            match system_row.get_mut(&key) {
+                Some(x) => *x += val,
+                None => {
+                    system_row.insert(key, val);
+                }
+            }
+        }
+        system_row.retain(|_, val| *val != Complex64::new(0.0, 0.0));
+        Ok(system_row)
+    }
+}
+
 /// Functions for the MixedLindbladOpenSystem
 ///
 impl MixedLindbladOpenSystem {
@@ -218,6 +251,78 @@ impl MixedLindbladOpenSystem {
             noise: MixedLindbladNoiseSystem::new(number_spins, number_bosons, number_fermions),
         }
     }
+
+    /// Returns the effective non-hermitian Hamiltonian of the open system.
+    ///
+    /// The effective Hamiltonian H_eff = H - i/2 sum_ij gamma_ij R_j^† L_i generates the deterministic
+    /// evolution between quantum jumps in quantum-trajectory methods. The DecoherenceProducts of
+    /// the spin subsystems are converted to PauliProducts, picking up a factor i for every iY operator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MixedOperator)` - The effective non-hermitian Hamiltonian.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - Number of subsystems in system and noise do not match.
+    pub fn effective_hamiltonian(&self) -> Result<MixedOperator, StruqtureError> {
+        let mut effective = MixedOperator::new(
+            self.system.number_spins().len(),
+            self.system.number_bosonic_modes().len(),
+            self.system.number_fermionic_modes().len(),
+        );
+        for (hermitian_product, value) in self.system.hamiltonian().iter() {
+            let product = MixedProduct::new(
+                hermitian_product.spins().cloned(),
+                hermitian_product.bosons().cloned(),
+                hermitian_product.fermions().cloned(),
+            )?;
+            if !hermitian_product.is_natural_hermitian() {
+                let (conjugate, sign) = product.hermitian_conjugate();
+                effective.add_operator_product(conjugate, value.conjugate() * sign)?;
+            }
+            effective.add_operator_product(product, value.clone())?;
+        }
+        for ((left, right), rate) in self.noise.iter() {
+            let (left_product, left_prefactor) = decoherence_to_mixed_product(left)?;
+            let (right_product, right_prefactor) = decoherence_to_mixed_product(right)?;
+            let (right_conjugate, sign) = right_product.hermitian_conjugate();
+            let coefficient = rate.clone()
+                * (right_prefactor.conj() * left_prefactor * Complex64::new(0.0, -0.5 * sign));
+            for (product, prefactor) in (right_conjugate * left_product)? {
+                effective.add_operator_product(product, coefficient.clone() * prefactor)?;
+            }
+        }
+        Ok(effective)
+    }
+}
+
+/// Converts a MixedDecoherenceProduct into a MixedProduct.
+///
+/// # Arguments
+///
+/// * `product` - The MixedDecoherenceProduct to convert.
+///
+/// # Returns
+///
+/// * `Ok((MixedProduct, Complex64))` - The MixedProduct and the prefactor picked up from the iY operators.
+/// * `Err(StruqtureError)` - The MixedProduct could not be constructed.
+fn decoherence_to_mixed_product(
+    product: &MixedDecoherenceProduct,
+) -> Result<(MixedProduct, Complex64), StruqtureError> {
+    let mut prefactor = Complex64::new(1.0, 0.0);
+    let mut spins: Vec<PauliProduct> = Vec::with_capacity(product.spins().len());
+    for decoherence_product in product.spins() {
+        let (pauli_product, coefficient) =
+            DecoherenceProduct::decoherence_to_spin(decoherence_product.clone());
+        prefactor *= coefficient;
+        spins.push(pauli_product);
+    }
+    Ok((
+        MixedProduct::new(
+            spins,
+            product.bosons().cloned(),
+            product.fermions().cloned(),
+        )?,
+        prefactor,
+    ))
 }
 
 /// Implements the negative sign function of MixedLindbladOpenSystem.
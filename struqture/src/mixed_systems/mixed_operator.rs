@@ -10,10 +10,16 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{MixedIndex, MixedProduct, OperateOnMixedSystems};
+use super::{
+    mixed_commutator_entries_on_row, mixed_entries_on_row, mixed_exchange_sign,
+    mixed_operator_terms, MixedHilbertSpace, MixedIndex, MixedProduct, OperateOnMixedSystems,
+    ToMixedSparseMatrixOperator, ToMixedSparseMatrixSuperOperator,
+};
 use crate::{
-    ModeIndex, OperateOnDensityMatrix, OperateOnState, SpinIndex, StruqtureError, StruqtureVersion,
+    commutation, ModeIndex, OperateOnDensityMatrix, OperateOnState, SpinIndex, StruqtureError,
+    StruqtureVersion,
 };
+use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
@@ -248,6 +254,28 @@ impl<'a> OperateOnMixedSystems<'a> for MixedOperator {
     }
 }
 
+impl<'a> ToMixedSparseMatrixOperator<'a> for MixedOperator {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        mixed_entries_on_row(&mixed_operator_terms(self)?, row, hilbert_space)
+    }
+}
+
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedOperator {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        mixed_commutator_entries_on_row(&mixed_operator_terms(self)?, row, hilbert_space)
+    }
+}
+
 /// Implements the default function (Default trait) of MixedOperator (an empty MixedOperator).
 ///
 impl Default for MixedOperator {
@@ -304,6 +332,81 @@ impl MixedOperator {
             n_fermions,
         }
     }
+
+    /// Computes the commutator `self * other - other * self`.
+    ///
+    /// Pairs of MixedProducts whose bosonic and fermionic parts act on disjoint modes either
+    /// commute or anticommute and are only multiplied once.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The MixedOperator to commute with.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MixedOperator)` - The commutator of self and other.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - Number of subsystems in self and other do not match.
+    pub fn commutator(&self, other: &MixedOperator) -> Result<MixedOperator, StruqtureError> {
+        self.commutation(other, -1.0)
+    }
+
+    /// Computes the anticommutator `self * other + other * self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The MixedOperator to anticommute with.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MixedOperator)` - The anticommutator of self and other.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - Number of subsystems in self and other do not match.
+    pub fn anticommutator(&self, other: &MixedOperator) -> Result<MixedOperator, StruqtureError> {
+        self.commutation(other, 1.0)
+    }
+
+    /// Computes `self * other + sign * other * self` product by product.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The MixedOperator on the right-hand side.
+    /// * `sign` - The sign of the reversed product.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MixedOperator)` - The resulting MixedOperator.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - Number of subsystems in self and other do not match.
+    fn commutation(
+        &self,
+        other: &MixedOperator,
+        sign: f64,
+    ) -> Result<MixedOperator, StruqtureError> {
+        if self.n_spins != other.n_spins
+            || self.n_bosons != other.n_bosons
+            || self.n_fermions != other.n_fermions
+        {
+            return Err(StruqtureError::MissmatchedNumberSubsystems {
+                target_number_spin_subsystems: self.n_spins,
+                target_number_boson_subsystems: self.n_bosons,
+                target_number_fermion_subsystems: self.n_fermions,
+                actual_number_spin_subsystems: other.n_spins,
+                actual_number_boson_subsystems: other.n_bosons,
+                actual_number_fermion_subsystems: other.n_fermions,
+            });
+        }
+        commutation(
+            self,
+            other,
+            sign,
+            MixedOperator::new(self.n_spins, self.n_bosons, self.n_fermions),
+            mixed_exchange_sign,
+            |products| {
+                Ok(products?
+                    .into_iter()
+                    .map(|(product, coefficient)| (product, CalculatorComplex::from(coefficient)))
+                    .collect())
+            },
+        )
+    }
 }
 
 /// Implements the negative sign function of MixedOperator.
@@ -10,12 +10,17 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{HermitianOperateOnMixedSystems, MixedOperator, MixedProduct, OperateOnMixedSystems};
+use super::{
+    HermitianOperateOnMixedSystems, MixedHilbertSpace, MixedOperator, MixedProduct,
+    OperateOnMixedSystems, ToMixedSparseMatrixOperator, ToMixedSparseMatrixSuperOperator,
+};
 use crate::prelude::*;
 use crate::{OperateOnDensityMatrix, OperateOnState, StruqtureError};
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Iter, Keys, Values};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -278,6 +283,30 @@ impl<'a> OperateOnMixedSystems<'a> for MixedSystem {
 
 impl<'a> HermitianOperateOnMixedSystems<'a> for MixedSystem {}
 
+impl<'a> ToMixedSparseMatrixOperator<'a> for MixedSystem {
+    // From trait
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_entries_on_row(row, hilbert_space)
+    }
+}
+
+impl<'a> ToMixedSparseMatrixSuperOperator<'a> for MixedSystem {
+    // From trait
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+        self.operator
+            .sparse_matrix_superoperator_entries_on_row(row, hilbert_space)
+    }
+}
+
 /// Functions for the MixedSystem
 ///
 impl MixedSystem {
@@ -35,11 +35,28 @@ mod mixed_operator;
 mod mixed_product;
 mod mixed_system;
 
+use crate::bosons::{
+    apply_boson_product, checked_mode_cutoffs, truncated_dimension, BosonCutoff, BosonIndex,
+};
+#[cfg(feature = "rayon")]
+use crate::coo_from_rows_parallel;
+use crate::fermions::{apply_fermion_product, FermionIndex};
+use crate::iterative_solvers::steady_state;
+use crate::spins::{
+    spin_exchange_sign, DecoherenceProduct, PauliProduct, SingleDecoherenceOperator,
+    SingleSpinOperator,
+};
+use crate::time_evolution::{krylov_evolution, runge_kutta_45_evolution};
 use crate::{
-    bosons::BosonIndex, fermions::FermionIndex, ModeIndex, OperateOnDensityMatrix, SpinIndex,
-    StruqtureError,
+    add_entry, apply_rows, check_density_matrix_length, complex_value, coo_from_rows,
+    csc_from_rows, csr_from_rows, dense_dimension, dense_from_rows, mode_exchange_sign,
+    CooSparseMatrix, CscSparseMatrix, CsrSparseMatrix, ModeIndex, OperateOnDensityMatrix,
+    SpinIndex, StruqtureError, SymmetricIndex,
 };
+use ndarray::Array2;
+use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 pub use mixed_decoherence_product::MixedDecoherenceProduct;
@@ -241,3 +258,1145 @@ where
     &'a Self: IntoIterator<Item = (&'a Self::Index, &'a Self::Value)>,
 {
 }
+
+/// The truncated tensor-product Hilbert space of a mixed system.
+///
+/// The subsystems are ordered as all spin subsystems, followed by all bosonic subsystems, followed by all
+/// fermionic subsystems. The index of a basis state is composed from the indices `i_k` of the subsystem basis states
+/// with local dimensions `d_k` as `i_0 + d_0 * (i_1 + d_1 * (i_2 + ...))`, so the first spin subsystem is the fastest changing index.
+///
+/// Within the subsystems the bases of the single-type matrices are used: bit `j` of a spin subsystem index is spin `j`,
+/// bit `j` of a fermionic subsystem index is the occupation of mode `j` and bosonic subsystems use the truncated
+/// Fock basis of [crate::bosons::ToBosonSparseMatrixOperator].
+/// Operators acting on different subsystems commute, the fermionic sign is only taken into account within each fermionic subsystem.
+///
+/// # Example
+/// ```
+/// use struqture::mixed_systems::MixedHilbertSpace;
+///
+/// let hilbert_space = MixedHilbertSpace::new(vec![1], vec![vec![3]], vec![2]);
+/// assert_eq!(hilbert_space.dimension(), 2 * 4 * 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MixedHilbertSpace {
+    /// The number of spins in each spin subsystem.
+    number_spins: Vec<usize>,
+    /// The maximal occupation of each mode in each bosonic subsystem.
+    boson_cutoffs: Vec<Vec<usize>>,
+    /// The number of modes in each fermionic subsystem.
+    number_fermionic_modes: Vec<usize>,
+}
+
+impl MixedHilbertSpace {
+    /// Creates a new MixedHilbertSpace.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins in each spin subsystem.
+    /// * `boson_cutoffs` - The maximal occupation of each mode in each bosonic subsystem.
+    /// * `number_fermionic_modes` - The number of modes in each fermionic subsystem.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new MixedHilbertSpace.
+    pub fn new(
+        number_spins: Vec<usize>,
+        boson_cutoffs: Vec<Vec<usize>>,
+        number_fermionic_modes: Vec<usize>,
+    ) -> Self {
+        MixedHilbertSpace {
+            number_spins,
+            boson_cutoffs,
+            number_fermionic_modes,
+        }
+    }
+
+    /// Returns the number of spins in each spin subsystem.
+    ///
+    /// # Returns
+    ///
+    /// * `&[usize]` - The number of spins in each spin subsystem.
+    pub fn number_spins(&self) -> &[usize] {
+        &self.number_spins
+    }
+
+    /// Returns the maximal occupation of each mode in each bosonic subsystem.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Vec<usize>]` - The maximal occupation of each mode in each bosonic subsystem.
+    pub fn boson_cutoffs(&self) -> &[Vec<usize>] {
+        &self.boson_cutoffs
+    }
+
+    /// Returns the number of modes in each fermionic subsystem.
+    ///
+    /// # Returns
+    ///
+    /// * `&[usize]` - The number of modes in each fermionic subsystem.
+    pub fn number_fermionic_modes(&self) -> &[usize] {
+        &self.number_fermionic_modes
+    }
+
+    /// Returns the dimensions of all subsystems in the order used for the basis index.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The dimension of each subsystem.
+    pub fn subsystem_dimensions(&self) -> Vec<usize> {
+        self.number_spins
+            .iter()
+            .map(|number_spins| 2usize.pow(*number_spins as u32))
+            .chain(
+                self.boson_cutoffs
+                    .iter()
+                    .map(|cutoffs| truncated_dimension(cutoffs)),
+            )
+            .chain(
+                self.number_fermionic_modes
+                    .iter()
+                    .map(|number_modes| 2usize.pow(*number_modes as u32)),
+            )
+            .collect()
+    }
+
+    /// Returns the dimension of the full Hilbert space.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The product of the dimensions of all subsystems.
+    pub fn dimension(&self) -> usize {
+        self.subsystem_dimensions().iter().product()
+    }
+
+    /// Returns the dimension of a dense matrix acting on `copies` copies of the Hilbert space.
+    ///
+    /// # Arguments
+    ///
+    /// * `copies` - The number of copies of the Hilbert space (1 for operators, 2 for superoperators).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The dimension of the dense matrix.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    pub(crate) fn dense_dimension(&self, copies: u32) -> Result<usize, StruqtureError> {
+        let number_spins: usize = self.number_spins.iter().sum();
+        let number_bosonic_modes: usize =
+            self.boson_cutoffs.iter().map(|cutoffs| cutoffs.len()).sum();
+        let number_fermionic_modes: usize = self.number_fermionic_modes.iter().sum();
+        // Spins and fermionic modes are two-dimensional, the order of the factors does not change the product
+        let local_dimensions = std::iter::repeat(2)
+            .take(number_spins + number_fermionic_modes)
+            .chain(
+                self.boson_cutoffs
+                    .iter()
+                    .flatten()
+                    .map(|cutoff| cutoff.saturating_add(1)),
+            );
+        dense_dimension(
+            local_dimensions,
+            copies,
+            number_spins,
+            number_bosonic_modes + number_fermionic_modes,
+        )
+    }
+}
+
+/// Trait for constructing the sparse matrix superoperator of mixed objects in the basis of a [MixedHilbertSpace].
+///
+/// The superoperator for the operator O is defined as the Matrix S so that
+/// `flatten(-i [O, p]) = S flatten(p)` where `[,]` is the commutator, `p` is a matrix
+/// and `flatten` flattens a matrix into a vector in row-major form.
+/// For noise and open systems the Lindblad terms are included in the same row-major convention.
+pub trait ToMixedSparseMatrixSuperOperator<'a>:
+    OperateOnMixedSystems<'a> + PartialEq + Clone
+{
+    /// Constructs the Hilbert space of Self with the given cutoffs for the bosonic subsystems.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MixedHilbertSpace)` - The Hilbert space Self acts on.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    fn mixed_hilbert_space(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<MixedHilbertSpace, StruqtureError> {
+        let number_spins = self.number_spins();
+        let number_bosonic_modes = self.number_bosonic_modes();
+        let current_number_bosonic_modes = self.current_number_bosonic_modes();
+        let number_fermionic_modes = self.number_fermionic_modes();
+        if boson_cutoffs.len() != number_bosonic_modes.len() {
+            return Err(StruqtureError::MissmatchedNumberSubsystems {
+                target_number_spin_subsystems: number_spins.len(),
+                target_number_boson_subsystems: boson_cutoffs.len(),
+                target_number_fermion_subsystems: number_fermionic_modes.len(),
+                actual_number_spin_subsystems: number_spins.len(),
+                actual_number_boson_subsystems: number_bosonic_modes.len(),
+                actual_number_fermion_subsystems: number_fermionic_modes.len(),
+            });
+        }
+        let cutoffs = boson_cutoffs
+            .iter()
+            .zip(number_bosonic_modes.iter())
+            .zip(current_number_bosonic_modes.iter())
+            .map(|((cutoff, number_modes), current_number_modes)| {
+                checked_mode_cutoffs(cutoff, *number_modes, *current_number_modes)
+            })
+            .collect::<Result<Vec<Vec<usize>>, StruqtureError>>()?;
+        Ok(MixedHilbertSpace::new(
+            number_spins,
+            cutoffs,
+            number_fermionic_modes,
+        ))
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in COO representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_coo(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CooSparseMatrix, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        let dimension = hilbert_space.dimension();
+        coo_from_rows(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in COO representation, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToMixedSparseMatrixSuperOperator::sparse_matrix_superoperator_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_superoperator_coo_parallel(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        let dimension = hilbert_space.dimension();
+        coo_from_rows_parallel(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in the tensor-product space in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csr(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        csr_from_rows(hilbert_space.dimension().pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in the tensor-product space in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csc(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        csc_from_rows(hilbert_space.dimension().pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the dense matrix representation of the superoperator in the tensor-product space.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_superoperator(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        let dimension = hilbert_space.dense_dimension(2)?;
+        dense_from_rows(dimension, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix entries for one row of the sparse matrix superoperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row for which to get the superoperator entries.
+    /// * `hilbert_space` - The Hilbert space in which to construct the superoperator, must cover all subsystems Self acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError>;
+
+    /// Applies the superoperator in the tensor-product space to a flattened density matrix without constructing its matrix.
+    ///
+    /// The density matrix is flattened in row-major form, as for the sparse matrix representation of the superoperator.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix the superoperator is applied to.
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix after the application of the superoperator.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length of the density matrix is not the square of the dimension of the Hilbert space.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        check_density_matrix_length(density_matrix.len(), hilbert_space.dimension())?;
+        apply_rows(density_matrix, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Evolves a flattened density matrix under the superoperator in the tensor-product space with the adaptive RK45 method.
+    ///
+    /// The density matrix p(t) solves `d/dt flatten(p) = S flatten(p)` for the superoperator S of
+    /// [ToMixedSparseMatrixSuperOperator::sparse_matrix_superoperator_coo], with `flatten` the row-major flattening.
+    /// The equation is integrated with the Dormand-Prince method, applying the superoperator
+    /// without constructing its matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    /// * `time` - The evolution time.
+    /// * `tolerance` - The (positive) tolerance of the local error of each integration step, relative to the magnitude of the entries.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length of the density matrix is not the square of the dimension of the Hilbert space.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix(
+        &'a self,
+        density_matrix: &[Complex64],
+        boson_cutoffs: &[BosonCutoff],
+        time: f64,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        runge_kutta_45_evolution(
+            |state| self.apply_superoperator(state, boson_cutoffs),
+            density_matrix,
+            time,
+            tolerance,
+        )
+    }
+
+    /// Evolves a flattened density matrix under the superoperator in the tensor-product space with Krylov subspace exponentiation.
+    ///
+    /// The density matrix p(t) = exp(t S) p(0) is computed for the superoperator S of
+    /// [ToMixedSparseMatrixSuperOperator::sparse_matrix_superoperator_coo], with p flattened in row-major form.
+    /// The exponential is approximated in Krylov subspaces constructed by applying the superoperator
+    /// without constructing its matrix, splitting the evolution time into steps to reach the tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    /// * `time` - The evolution time.
+    /// * `krylov_dimension` - The maximal dimension of the Krylov subspaces.
+    /// * `tolerance` - The (positive) tolerance of the error relative to the norm of the density matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::MissmatchedDensityMatrixLength)` - The length of the density matrix is not the square of the dimension of the Hilbert space.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix_krylov(
+        &'a self,
+        density_matrix: &[Complex64],
+        boson_cutoffs: &[BosonCutoff],
+        time: f64,
+        krylov_dimension: usize,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        check_density_matrix_length(density_matrix.len(), hilbert_space.dimension())?;
+        krylov_evolution(
+            |state| self.apply_superoperator(state, boson_cutoffs),
+            density_matrix,
+            time,
+            krylov_dimension,
+            tolerance,
+        )
+    }
+
+    /// Returns the steady state of the superoperator in the tensor-product space.
+    ///
+    /// The steady state is the density matrix p with `S flatten(p) = 0` and `Tr(p) = 1` for the
+    /// superoperator S of [ToMixedSparseMatrixSuperOperator::sparse_matrix_superoperator_coo].
+    /// Adding the rank-one term |v><t| to S, with the maximally mixed state v and the trace functional t,
+    /// removes the null space of S, and the resulting linear system `(S + |v><t|) flatten(p) = v`
+    /// is solved with the restarted GMRES method, applying the superoperator without constructing its matrix.
+    /// The steady state is assumed to be unique.
+    ///
+    /// The steady state is returned as a dense matrix, so the dimension of the Hilbert space
+    /// is limited to [crate::MAXIMAL_DENSE_DIMENSION] like for [ToMixedSparseMatrixOperator::dense_matrix].
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    /// * `tolerance` - The tolerance of the residual of the linear system relative to the norm of v.
+    /// * `maximal_iterations` - The maximal number of applications of the superoperator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense steady-state density matrix.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(StruqtureError::NoConvergence)` - The solver did not converge within the maximal number of iterations.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn steady_state(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+        tolerance: f64,
+        maximal_iterations: usize,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let dimension = self
+            .mixed_hilbert_space(boson_cutoffs)?
+            .dense_dimension(1)?;
+        steady_state(
+            |vector| self.apply_superoperator(vector, boson_cutoffs),
+            dimension,
+            tolerance,
+            maximal_iterations,
+        )
+    }
+}
+
+/// Trait for constructing the sparse matrix of mixed operators in the basis of a [MixedHilbertSpace].
+///
+/// # Example
+/// ```
+/// use struqture::prelude::*;
+/// use qoqo_calculator::CalculatorComplex;
+/// use struqture::bosons::{BosonCutoff, BosonProduct};
+/// use struqture::mixed_systems::{MixedProduct, MixedSystem, ToMixedSparseMatrixOperator};
+/// use struqture::spins::PauliProduct;
+/// use num_complex::Complex64;
+///
+/// let mut system = MixedSystem::new([Some(1)], [Some(1)], []);
+/// let product = MixedProduct::new(
+///     [PauliProduct::new().z(0)],
+///     [BosonProduct::new([0], [0]).unwrap()],
+///     [],
+/// )
+/// .unwrap();
+/// system.set(product, CalculatorComplex::from(1.0)).unwrap();
+///
+/// // The spin is the fastest changing index: |s=1, n=1> has the index 1 + 2 * 1 = 3
+/// let (values, (rows, columns)) = system.sparse_matrix_coo(&[BosonCutoff::Global(1)]).unwrap();
+/// assert_eq!(rows, vec![2, 3]);
+/// assert_eq!(columns, vec![2, 3]);
+/// assert_eq!(values, vec![Complex64::new(1.0, 0.0), Complex64::new(-1.0, 0.0)]);
+/// ```
+pub trait ToMixedSparseMatrixOperator<'a>: ToMixedSparseMatrixSuperOperator<'a> {
+    /// Constructs the sparse matrix representation of Self as a COO matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_coo(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CooSparseMatrix, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        let dimension = hilbert_space.dimension();
+        coo_from_rows(dimension, |row| {
+            self.sparse_matrix_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self as a COO matrix, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToMixedSparseMatrixOperator::sparse_matrix_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_coo_parallel(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        let dimension = hilbert_space.dimension();
+        coo_from_rows_parallel(dimension, |row| {
+            self.sparse_matrix_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self in the tensor-product space in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csr(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        csr_from_rows(hilbert_space.dimension(), |row| {
+            self.sparse_matrix_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of Self in the tensor-product space in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csc(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        csc_from_rows(hilbert_space.dimension(), |row| {
+            self.sparse_matrix_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the dense matrix representation of Self in the tensor-product space.
+    ///
+    /// # Arguments
+    ///
+    /// * `boson_cutoffs` - The occupation cutoff of each bosonic subsystem, either global or per mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::MissmatchedNumberSubsystems)` - The number of cutoffs does not match the number of bosonic subsystems.
+    /// * `Err(StruqtureError::NumberModesExceeded)` - A per-mode cutoff covers fewer modes than Self acts on.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_matrix(
+        &'a self,
+        boson_cutoffs: &[BosonCutoff],
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let hilbert_space = self.mixed_hilbert_space(boson_cutoffs)?;
+        let dimension = hilbert_space.dense_dimension(1)?;
+        dense_from_rows(dimension, |row| {
+            self.sparse_matrix_entries_on_row(row, &hilbert_space)
+        })
+    }
+
+    /// Constructs the sparse matrix entries for one row of the sparse matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row for which to get the entries.
+    /// * `hilbert_space` - The Hilbert space in which to construct the matrix, must cover all subsystems Self acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_entries_on_row(
+        &'a self,
+        row: usize,
+        hilbert_space: &MixedHilbertSpace,
+    ) -> Result<HashMap<usize, Complex64>, StruqtureError>;
+}
+
+/// Applies a PauliProduct to a spin basis state.
+///
+/// # Arguments
+///
+/// * `product` - The PauliProduct to apply.
+/// * `state` - The index of the basis state, bit `j` is spin `j`.
+/// * `number_spins` - The number of spins in the subsystem.
+///
+/// # Returns
+///
+/// * `Some((usize, Complex64))` - The index of the resulting basis state and the amplitude.
+/// * `None` - The product acts on spins outside of the subsystem.
+fn apply_pauli_product(
+    product: &PauliProduct,
+    state: usize,
+    number_spins: usize,
+) -> Option<(usize, Complex64)> {
+    let mut state = state;
+    let mut amplitude = Complex64::new(1.0, 0.0);
+    for (spin, operator) in product.iter() {
+        if *spin >= number_spins {
+            return None;
+        }
+        let up = (state >> *spin) & 1 == 0;
+        match operator {
+            SingleSpinOperator::Identity => (),
+            SingleSpinOperator::X => state ^= 1usize << *spin,
+            SingleSpinOperator::Y => {
+                // Y |0> = i |1> and Y |1> = -i |0>
+                amplitude *= if up {
+                    Complex64::new(0.0, 1.0)
+                } else {
+                    Complex64::new(0.0, -1.0)
+                };
+                state ^= 1usize << *spin;
+            }
+            SingleSpinOperator::Z => {
+                if !up {
+                    amplitude = -amplitude;
+                }
+            }
+        }
+    }
+    Some((state, amplitude))
+}
+
+/// Applies a DecoherenceProduct or its hermitian conjugate to a spin basis state.
+///
+/// # Arguments
+///
+/// * `product` - The DecoherenceProduct to apply.
+/// * `state` - The index of the basis state, bit `j` is spin `j`.
+/// * `number_spins` - The number of spins in the subsystem.
+/// * `adjoint` - Whether to apply the hermitian conjugate of the product.
+///
+/// # Returns
+///
+/// * `Some((usize, Complex64))` - The index of the resulting basis state and the amplitude.
+/// * `None` - The product acts on spins outside of the subsystem.
+fn apply_decoherence_product(
+    product: &DecoherenceProduct,
+    state: usize,
+    number_spins: usize,
+    adjoint: bool,
+) -> Option<(usize, Complex64)> {
+    let mut state = state;
+    let mut amplitude = Complex64::new(1.0, 0.0);
+    for (spin, operator) in product.iter() {
+        if *spin >= number_spins {
+            return None;
+        }
+        let up = (state >> *spin) & 1 == 0;
+        match operator {
+            SingleDecoherenceOperator::Identity => (),
+            SingleDecoherenceOperator::X => state ^= 1usize << *spin,
+            SingleDecoherenceOperator::IY => {
+                // iY |0> = -|1> and iY |1> = |0>, (iY)^† = -iY
+                if up != adjoint {
+                    amplitude = -amplitude;
+                }
+                state ^= 1usize << *spin;
+            }
+            SingleDecoherenceOperator::Z => {
+                if !up {
+                    amplitude = -amplitude;
+                }
+            }
+        }
+    }
+    Some((state, amplitude))
+}
+
+/// Splits the index of a basis state of a [MixedHilbertSpace] into the indices of the subsystem basis states.
+fn split_state(state: usize, dimensions: &[usize]) -> Vec<usize> {
+    let mut remainder = state;
+    dimensions
+        .iter()
+        .map(|dimension| {
+            let local = remainder % dimension;
+            remainder /= dimension;
+            local
+        })
+        .collect()
+}
+
+/// Combines the indices of the subsystem basis states into the index of a basis state of a [MixedHilbertSpace].
+fn combine_state(local_states: &[usize], dimensions: &[usize]) -> usize {
+    local_states
+        .iter()
+        .zip(dimensions.iter())
+        .rev()
+        .fold(0, |state, (local, dimension)| state * dimension + local)
+}
+
+/// Applies the bosonic and fermionic parts of a mixed product or its hermitian conjugate to the subsystem states.
+///
+/// Returns `Ok(None)` when the product annihilates the state or leads out of the truncated space and
+/// `Err(StruqtureError::NumberModesExceeded)` when it acts on more modes than the Hilbert space contains.
+fn apply_modes(
+    bosons: std::slice::Iter<crate::bosons::BosonProduct>,
+    fermions: std::slice::Iter<crate::fermions::FermionProduct>,
+    local_states: &mut [usize],
+    hilbert_space: &MixedHilbertSpace,
+    adjoint: bool,
+) -> Result<Option<f64>, StruqtureError> {
+    let number_spin_subsystems = hilbert_space.number_spins.len();
+    let number_boson_subsystems = hilbert_space.boson_cutoffs.len();
+    let mut factor = 1.0;
+    for (index, (product, cutoffs)) in bosons.zip(hilbert_space.boson_cutoffs.iter()).enumerate() {
+        let local = &mut local_states[number_spin_subsystems + index];
+        match apply_boson_product(product, *local, cutoffs, adjoint)? {
+            Some((new_local, element)) => {
+                *local = new_local;
+                factor *= element;
+            }
+            None => return Ok(None),
+        }
+    }
+    for (index, (product, number_modes)) in fermions
+        .zip(hilbert_space.number_fermionic_modes.iter())
+        .enumerate()
+    {
+        if product.current_number_modes() > *number_modes {
+            return Err(StruqtureError::NumberModesExceeded);
+        }
+        let local = &mut local_states[number_spin_subsystems + number_boson_subsystems + index];
+        match apply_fermion_product(product, *local, adjoint) {
+            Some((new_local, sign)) => {
+                *local = new_local;
+                factor *= sign;
+            }
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(factor))
+}
+
+/// Applies a MixedProduct or its hermitian conjugate to a basis state of a [MixedHilbertSpace].
+///
+/// # Arguments
+///
+/// * `product` - The MixedProduct P to apply.
+/// * `state` - The index of the basis state.
+/// * `hilbert_space` - The Hilbert space of the basis state.
+/// * `adjoint` - Whether to apply the hermitian conjugate P^† instead of P.
+///
+/// # Returns
+///
+/// * `Ok(Some((usize, Complex64)))` - The index of the resulting basis state and the amplitude.
+/// * `Ok(None)` - The product annihilates the basis state or leads out of the truncated space.
+/// * `Err(StruqtureError::NumberModesExceeded)` - The product acts on more modes than the Hilbert space contains.
+pub(crate) fn apply_mixed_product(
+    product: &MixedProduct,
+    state: usize,
+    hilbert_space: &MixedHilbertSpace,
+    adjoint: bool,
+) -> Result<Option<(usize, Complex64)>, StruqtureError> {
+    let dimensions = hilbert_space.subsystem_dimensions();
+    let mut local_states = split_state(state, &dimensions);
+    let mut amplitude = Complex64::new(1.0, 0.0);
+    // PauliProducts are hermitian
+    for ((product, number_spins), local) in product
+        .spins()
+        .zip(hilbert_space.number_spins.iter())
+        .zip(local_states.iter_mut())
+    {
+        match apply_pauli_product(product, *local, *number_spins) {
+            Some((new_local, local_amplitude)) => {
+                *local = new_local;
+                amplitude *= local_amplitude;
+            }
+            None => return Ok(None),
+        }
+    }
+    match apply_modes(
+        product.bosons(),
+        product.fermions(),
+        &mut local_states,
+        hilbert_space,
+        adjoint,
+    )? {
+        Some(factor) => amplitude *= factor,
+        None => return Ok(None),
+    }
+    Ok(Some((combine_state(&local_states, &dimensions), amplitude)))
+}
+
+/// Applies a MixedDecoherenceProduct or its hermitian conjugate to a basis state of a [MixedHilbertSpace].
+///
+/// # Arguments
+///
+/// * `product` - The MixedDecoherenceProduct P to apply.
+/// * `state` - The index of the basis state.
+/// * `hilbert_space` - The Hilbert space of the basis state.
+/// * `adjoint` - Whether to apply the hermitian conjugate P^† instead of P.
+///
+/// # Returns
+///
+/// * `Ok(Some((usize, Complex64)))` - The index of the resulting basis state and the amplitude.
+/// * `Ok(None)` - The product annihilates the basis state or leads out of the truncated space.
+/// * `Err(StruqtureError::NumberModesExceeded)` - The product acts on more modes than the Hilbert space contains.
+pub(crate) fn apply_mixed_decoherence_product(
+    product: &MixedDecoherenceProduct,
+    state: usize,
+    hilbert_space: &MixedHilbertSpace,
+    adjoint: bool,
+) -> Result<Option<(usize, Complex64)>, StruqtureError> {
+    let dimensions = hilbert_space.subsystem_dimensions();
+    let mut local_states = split_state(state, &dimensions);
+    let mut amplitude = Complex64::new(1.0, 0.0);
+    for ((product, number_spins), local) in product
+        .spins()
+        .zip(hilbert_space.number_spins.iter())
+        .zip(local_states.iter_mut())
+    {
+        match apply_decoherence_product(product, *local, *number_spins, adjoint) {
+            Some((new_local, local_amplitude)) => {
+                *local = new_local;
+                amplitude *= local_amplitude;
+            }
+            None => return Ok(None),
+        }
+    }
+    match apply_modes(
+        product.bosons(),
+        product.fermions(),
+        &mut local_states,
+        hilbert_space,
+        adjoint,
+    )? {
+        Some(factor) => amplitude *= factor,
+        None => return Ok(None),
+    }
+    Ok(Some((combine_state(&local_states, &dimensions), amplitude)))
+}
+
+/// Collects the terms of a MixedOperator as (P, adjoint, value) for the sparse matrix construction.
+///
+/// # Arguments
+///
+/// * `operator` - The MixedOperator to collect the terms of.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(MixedProduct, bool, Complex64)>)` - The terms value * P of the operator.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn mixed_operator_terms(
+    operator: &MixedOperator,
+) -> Result<Vec<(MixedProduct, bool, Complex64)>, StruqtureError> {
+    operator
+        .iter()
+        .map(|(product, value)| Ok((product.clone(), false, complex_value(value)?)))
+        .collect()
+}
+
+/// Collects the terms of a MixedHamiltonian as (P, adjoint, value) for the sparse matrix construction.
+///
+/// Every key K with value v stands for v * K + v^* * K^†, where the hermitian conjugate is only
+/// added when K is not naturally hermitian.
+///
+/// # Arguments
+///
+/// * `hamiltonian` - The MixedHamiltonian to collect the terms of.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(MixedProduct, bool, Complex64)>)` - The terms of the hamiltonian.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+pub(crate) fn mixed_hamiltonian_terms(
+    hamiltonian: &MixedHamiltonian,
+) -> Result<Vec<(MixedProduct, bool, Complex64)>, StruqtureError> {
+    let mut terms: Vec<(MixedProduct, bool, Complex64)> = Vec::with_capacity(2 * hamiltonian.len());
+    for (hermitian_product, value) in hamiltonian.iter() {
+        let product = MixedProduct::new(
+            hermitian_product.spins().cloned(),
+            hermitian_product.bosons().cloned(),
+            hermitian_product.fermions().cloned(),
+        )?;
+        let value = complex_value(value)?;
+        if !hermitian_product.is_natural_hermitian() {
+            terms.push((product.clone(), true, value.conj()));
+        }
+        terms.push((product, false, value));
+    }
+    Ok(terms)
+}
+
+/// Constructs the entries of one row of the sparse matrix of a sum of mixed terms.
+///
+/// # Arguments
+///
+/// * `terms` - The terms (P, adjoint, value) representing value * P or value * P^† if adjoint is true.
+/// * `row` - The row for which to get the entries.
+/// * `hilbert_space` - The Hilbert space in which to construct the matrix.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+/// * `Err(StruqtureError::NumberModesExceeded)` - A product acts on more modes than the Hilbert space contains.
+pub(crate) fn mixed_entries_on_row(
+    terms: &[(MixedProduct, bool, Complex64)],
+    row: usize,
+    hilbert_space: &MixedHilbertSpace,
+) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(terms.len());
+    for (product, adjoint, value) in terms {
+        // <row| T = (T^† |row>)^†
+        if let Some((column, amplitude)) =
+            apply_mixed_product(product, row, hilbert_space, !adjoint)?
+        {
+            add_entry(&mut entries, column, *value * amplitude.conj());
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    Ok(entries)
+}
+
+/// Constructs the entries of one row of the commutator superoperator -i [O, p] of a sum of mixed terms.
+///
+/// # Arguments
+///
+/// * `terms` - The terms (P, adjoint, value) representing value * P or value * P^† if adjoint is true.
+/// * `row` - The row for which to get the superoperator entries.
+/// * `hilbert_space` - The Hilbert space in which to construct the superoperator.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+/// * `Err(StruqtureError::NumberModesExceeded)` - A product acts on more modes than the Hilbert space contains.
+pub(crate) fn mixed_commutator_entries_on_row(
+    terms: &[(MixedProduct, bool, Complex64)],
+    row: usize,
+    hilbert_space: &MixedHilbertSpace,
+) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+    let dimension = hilbert_space.dimension();
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(2 * terms.len());
+    let left_index = row.div_euclid(dimension);
+    let right_index = row % dimension;
+    let constant_prefactor = Complex64::new(0.0, -1.0);
+    for (product, adjoint, value) in terms {
+        // -i O p => -i O.kron(I) flatten(p) uses the row left_index of O
+        if let Some((column, amplitude)) =
+            apply_mixed_product(product, left_index, hilbert_space, !adjoint)?
+        {
+            add_entry(
+                &mut entries,
+                column * dimension + right_index,
+                constant_prefactor * *value * amplitude.conj(),
+            );
+        }
+        // i p O => i I.kron(O.T) flatten(p) uses the column right_index of O
+        if let Some((column, amplitude)) =
+            apply_mixed_product(product, right_index, hilbert_space, *adjoint)?
+        {
+            add_entry(
+                &mut entries,
+                left_index * dimension + column,
+                -constant_prefactor * *value * amplitude,
+            );
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    Ok(entries)
+}
+
+/// Constructs the entries of one row of the Lindblad superoperator of mixed noise terms.
+///
+/// Each term ((L, R), rate) contributes rate * (L p R^† - 1/2 R^† L p - 1/2 p R^† L).
+///
+/// # Arguments
+///
+/// * `terms` - The noise terms (L, R, rate).
+/// * `row` - The row for which to get the superoperator entries.
+/// * `hilbert_space` - The Hilbert space in which to construct the superoperator.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, Complex64>)` - The non-zero entries of the row, indexed by column.
+/// * `Err(StruqtureError::NumberModesExceeded)` - A product acts on more modes than the Hilbert space contains.
+pub(crate) fn mixed_lindblad_entries_on_row(
+    terms: &[(MixedDecoherenceProduct, MixedDecoherenceProduct, Complex64)],
+    row: usize,
+    hilbert_space: &MixedHilbertSpace,
+) -> Result<HashMap<usize, Complex64>, StruqtureError> {
+    let dimension = hilbert_space.dimension();
+    let mut entries: HashMap<usize, Complex64> = HashMap::with_capacity(3 * terms.len());
+    let left_index = row.div_euclid(dimension);
+    let right_index = row % dimension;
+    for (left, right, rate) in terms {
+        // L p R^† uses the row left_index of L and the column right_index of R^†
+        if let (Some((column_left, amplitude_left)), Some((column_right, amplitude_right))) = (
+            apply_mixed_decoherence_product(left, left_index, hilbert_space, true)?,
+            apply_mixed_decoherence_product(right, right_index, hilbert_space, true)?,
+        ) {
+            add_entry(
+                &mut entries,
+                column_left * dimension + column_right,
+                *rate * amplitude_left.conj() * amplitude_right,
+            );
+        }
+        // - 1/2 R^† L p uses the row left_index of R^† L: <left_index| R^† L = (L^† R |left_index>)^†
+        if let Some((intermediate, amplitude_right)) =
+            apply_mixed_decoherence_product(right, left_index, hilbert_space, false)?
+        {
+            if let Some((column, amplitude_left)) =
+                apply_mixed_decoherence_product(left, intermediate, hilbert_space, true)?
+            {
+                add_entry(
+                    &mut entries,
+                    column * dimension + right_index,
+                    *rate * (-0.5 * (amplitude_left * amplitude_right).conj()),
+                );
+            }
+        }
+        // - 1/2 p R^† L uses the column right_index of R^† L
+        if let Some((intermediate, amplitude_left)) =
+            apply_mixed_decoherence_product(left, right_index, hilbert_space, false)?
+        {
+            if let Some((column, amplitude_right)) =
+                apply_mixed_decoherence_product(right, intermediate, hilbert_space, true)?
+            {
+                add_entry(
+                    &mut entries,
+                    left_index * dimension + column,
+                    *rate * (-0.5 * amplitude_left * amplitude_right),
+                );
+            }
+        }
+    }
+    entries.retain(|_, value| *value != Complex64::new(0.0, 0.0));
+    Ok(entries)
+}
+
+/// Returns the sign picked up when exchanging the order of two MixedProducts.
+///
+/// The products are only known to commute or anticommute when all their bosonic and
+/// fermionic sub-products act on disjoint modes; spin sub-products always do.
+///
+/// # Arguments
+///
+/// * `left` - The left MixedProduct.
+/// * `right` - The right MixedProduct.
+///
+/// # Returns
+///
+/// * `Some(f64)` - The exchange sign if it is known without multiplying the products.
+/// * `None` - At least one bosonic or fermionic sub-product shares a mode.
+pub(crate) fn mixed_exchange_sign(left: &MixedProduct, right: &MixedProduct) -> Option<f64> {
+    let mut exchange = 1.0;
+    for (left_spins, right_spins) in left.spins().zip(right.spins()) {
+        exchange *= spin_exchange_sign(left_spins, right_spins);
+    }
+    for (left_bosons, right_bosons) in left.bosons().zip(right.bosons()) {
+        exchange *= mode_exchange_sign(left_bosons, right_bosons, false)?;
+    }
+    for (left_fermions, right_fermions) in left.fermions().zip(right.fermions()) {
+        exchange *= mode_exchange_sign(left_fermions, right_fermions, true)?;
+    }
+    Some(exchange)
+}
@@ -0,0 +1,74 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Lattice;
+use crate::bosons::{BosonHamiltonianSystem, HermitianBosonProduct};
+use crate::{ModeIndex, OperateOnDensityMatrix};
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+
+/// Creates the Bose-Hubbard model on a lattice.
+///
+/// The Hamiltonian is
+/// -t Σ_<i,j> (b_i^† b_j + h.c.) + U/2 Σ_i n_i (n_i - 1) - μ Σ_i n_i,
+/// where site i is mode i.
+///
+/// # Arguments
+///
+/// * `lattice` - The lattice, each edge <i,j> allows hopping between the sites i and j.
+/// * `hopping` - The hopping amplitude t.
+/// * `interaction` - The on-site interaction U.
+/// * `chemical_potential` - The chemical potential μ.
+///
+/// # Returns
+///
+/// * `BosonHamiltonianSystem` - The Hamiltonian with one mode per site of the lattice.
+///
+/// # Panics
+///
+/// * Internal error in add_operator_product.
+pub fn bose_hubbard(
+    lattice: &Lattice,
+    hopping: CalculatorFloat,
+    interaction: CalculatorFloat,
+    chemical_potential: CalculatorFloat,
+) -> BosonHamiltonianSystem {
+    let number_sites = lattice.number_sites();
+    let mut system = BosonHamiltonianSystem::with_capacity(
+        Some(number_sites),
+        lattice.edges().len() + 2 * number_sites,
+    );
+    for (first, second) in lattice.edges() {
+        // Edges fulfill first < second, so the product is a valid HermitianBosonProduct
+        let hbp = HermitianBosonProduct::new([*first], [*second])
+            .expect("Internal bug in HermitianBosonProduct::new");
+        system
+            .add_operator_product(hbp, CalculatorComplex::from(hopping.clone() * -1.0))
+            .expect("Internal bug in add_operator_product");
+    }
+    for site in 0..number_sites {
+        // n (n - 1) = b^† b^† b b
+        let hbp = HermitianBosonProduct::new([site, site], [site, site])
+            .expect("Internal bug in HermitianBosonProduct::new");
+        system
+            .add_operator_product(hbp, CalculatorComplex::from(interaction.clone() * 0.5))
+            .expect("Internal bug in add_operator_product");
+        let hbp = HermitianBosonProduct::new([site], [site])
+            .expect("Internal bug in HermitianBosonProduct::new");
+        system
+            .add_operator_product(
+                hbp,
+                CalculatorComplex::from(chemical_potential.clone() * -1.0),
+            )
+            .expect("Internal bug in add_operator_product");
+    }
+    system
+}
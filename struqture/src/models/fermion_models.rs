@@ -0,0 +1,79 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Lattice;
+use crate::fermions::{FermionHamiltonianSystem, HermitianFermionProduct};
+use crate::{ModeIndex, OperateOnDensityMatrix};
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+
+/// Creates the Fermi-Hubbard model on a lattice.
+///
+/// The Hamiltonian is
+/// -t Σ_<i,j>,σ (c_iσ^† c_jσ + h.c.) + U Σ_i n_i↑ n_i↓ - μ Σ_i,σ n_iσ,
+/// where the spin-up mode of site i is mode 2i and the spin-down mode is mode 2i + 1.
+///
+/// # Arguments
+///
+/// * `lattice` - The lattice, each edge <i,j> allows hopping between the sites i and j.
+/// * `hopping` - The hopping amplitude t.
+/// * `interaction` - The on-site interaction U.
+/// * `chemical_potential` - The chemical potential μ.
+///
+/// # Returns
+///
+/// * `FermionHamiltonianSystem` - The Hamiltonian with two modes per site of the lattice.
+///
+/// # Panics
+///
+/// * Internal error in add_operator_product.
+pub fn fermi_hubbard(
+    lattice: &Lattice,
+    hopping: CalculatorFloat,
+    interaction: CalculatorFloat,
+    chemical_potential: CalculatorFloat,
+) -> FermionHamiltonianSystem {
+    let number_sites = lattice.number_sites();
+    let mut system = FermionHamiltonianSystem::with_capacity(
+        Some(2 * number_sites),
+        2 * lattice.edges().len() + 3 * number_sites,
+    );
+    for (first, second) in lattice.edges() {
+        for spin in 0..2 {
+            // Edges fulfill first < second, so the product is a valid HermitianFermionProduct
+            let hfp = HermitianFermionProduct::new([2 * first + spin], [2 * second + spin])
+                .expect("Internal bug in HermitianFermionProduct::new");
+            system
+                .add_operator_product(hfp, CalculatorComplex::from(hopping.clone() * -1.0))
+                .expect("Internal bug in add_operator_product");
+        }
+    }
+    for site in 0..number_sites {
+        let (up, down) = (2 * site, 2 * site + 1);
+        // n_up n_down = -c_up^† c_down^† c_up c_down
+        let hfp = HermitianFermionProduct::new([up, down], [up, down])
+            .expect("Internal bug in HermitianFermionProduct::new");
+        system
+            .add_operator_product(hfp, CalculatorComplex::from(interaction.clone() * -1.0))
+            .expect("Internal bug in add_operator_product");
+        for mode in [up, down] {
+            let hfp = HermitianFermionProduct::new([mode], [mode])
+                .expect("Internal bug in HermitianFermionProduct::new");
+            system
+                .add_operator_product(
+                    hfp,
+                    CalculatorComplex::from(chemical_potential.clone() * -1.0),
+                )
+                .expect("Internal bug in add_operator_product");
+        }
+    }
+    system
+}
@@ -0,0 +1,209 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::StruqtureError;
+use std::collections::BTreeSet;
+
+/// Boundary conditions of a lattice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Boundary {
+    /// The lattice ends at its boundaries.
+    Open,
+    /// The lattice wraps around at its boundaries, connecting the last and the first sites of each row or column.
+    Periodic,
+}
+
+/// Lattices are sets of sites connected by nearest-neighbour edges.
+///
+/// The edges are stored as pairs of sites (i, j) with i < j, sorted and without duplicates.
+/// Sites of two-dimensional lattices with a width w are numbered row by row, site (x, y) has index x + w y.
+///
+/// # Example
+///
+/// ```
+/// use struqture::models::{Boundary, Lattice};
+///
+/// let lattice = Lattice::chain(3, Boundary::Periodic);
+/// assert_eq!(lattice.number_sites(), 3);
+/// assert_eq!(lattice.edges(), &[(0, 1), (0, 2), (1, 2)]);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lattice {
+    /// The number of sites of the lattice.
+    number_sites: usize,
+    /// The edges between the sites of the lattice.
+    edges: Vec<(usize, usize)>,
+}
+
+impl Lattice {
+    /// Creates a Lattice from a list of edges.
+    ///
+    /// The order of the sites within an edge and of the edges is irrelevant, duplicate edges are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_sites` - The number of sites of the lattice.
+    /// * `edges` - The pairs of sites connected by edges.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The lattice with the given edges.
+    /// * `Err(StruqtureError::InvalidLatticeEdge)` - An edge connects a site to itself or to a site outside of the lattice.
+    pub fn from_edges(
+        number_sites: usize,
+        edges: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, StruqtureError> {
+        let mut unique_edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+        for (first, second) in edges {
+            if first >= number_sites || second >= number_sites || first == second {
+                return Err(StruqtureError::InvalidLatticeEdge { first, second });
+            }
+            unique_edges.insert((first.min(second), first.max(second)));
+        }
+        Ok(Lattice {
+            number_sites,
+            edges: unique_edges.into_iter().collect(),
+        })
+    }
+
+    /// Creates a one-dimensional chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The number of sites of the chain.
+    /// * `boundary` - The boundary conditions of the chain.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The chain lattice.
+    pub fn chain(length: usize, boundary: Boundary) -> Self {
+        Lattice::rectangular(length, 1, boundary, Boundary::Open, false)
+    }
+
+    /// Creates a ladder of two coupled chains.
+    ///
+    /// Site x of the first leg has index x, site x of the second leg has index x + length.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The number of sites of each leg.
+    /// * `boundary` - The boundary conditions along the legs.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The ladder lattice.
+    pub fn ladder(length: usize, boundary: Boundary) -> Self {
+        Lattice::rectangular(length, 2, boundary, Boundary::Open, false)
+    }
+
+    /// Creates a two-dimensional square lattice.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of sites in x direction.
+    /// * `height` - The number of sites in y direction.
+    /// * `boundary` - The boundary conditions in both directions.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The square lattice.
+    pub fn square(width: usize, height: usize, boundary: Boundary) -> Self {
+        Lattice::rectangular(width, height, boundary, boundary, false)
+    }
+
+    /// Creates a two-dimensional triangular lattice.
+    ///
+    /// The triangular lattice is the square lattice with an additional edge from site (x, y) to site (x + 1, y + 1).
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of sites in x direction.
+    /// * `height` - The number of sites in y direction.
+    /// * `boundary` - The boundary conditions in both directions.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The triangular lattice.
+    pub fn triangular(width: usize, height: usize, boundary: Boundary) -> Self {
+        Lattice::rectangular(width, height, boundary, boundary, true)
+    }
+
+    /// Returns the number of sites of the lattice.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of sites.
+    pub fn number_sites(&self) -> usize {
+        self.number_sites
+    }
+
+    /// Returns the edges of the lattice.
+    ///
+    /// # Returns
+    ///
+    /// * `&[(usize, usize)]` - The pairs of sites (i, j) with i < j connected by edges.
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Creates a lattice on a rectangular grid of sites.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of sites in x direction.
+    /// * `height` - The number of sites in y direction.
+    /// * `boundary_x` - The boundary conditions in x direction.
+    /// * `boundary_y` - The boundary conditions in y direction.
+    /// * `diagonal` - Whether site (x, y) is connected to site (x + 1, y + 1).
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The lattice.
+    fn rectangular(
+        width: usize,
+        height: usize,
+        boundary_x: Boundary,
+        boundary_y: Boundary,
+        diagonal: bool,
+    ) -> Self {
+        // Returns the neighbouring coordinate, wrapping around for periodic boundaries
+        let next = |coordinate: usize, size: usize, boundary: Boundary| {
+            if coordinate + 1 < size {
+                Some(coordinate + 1)
+            } else if boundary == Boundary::Periodic && size > 1 {
+                Some(0)
+            } else {
+                None
+            }
+        };
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let site = x + width * y;
+                let next_x = next(x, width, boundary_x);
+                let next_y = next(y, height, boundary_y);
+                if let Some(next_x) = next_x {
+                    edges.push((site, next_x + width * y));
+                }
+                if let Some(next_y) = next_y {
+                    edges.push((site, x + width * next_y));
+                }
+                if let (true, Some(next_x), Some(next_y)) = (diagonal, next_x, next_y) {
+                    edges.push((site, next_x + width * next_y));
+                }
+            }
+        }
+        Lattice::from_edges(width * height, edges)
+            .expect("Internal bug in the construction of lattice edges")
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Module for building the Hamiltonians of standard lattice models.
+//!
+//! The geometry of a model is described by a [Lattice], a number of sites and the list of
+//! nearest-neighbour edges between them. The builders create spin, fermion and boson Hamiltonian
+//! systems with one term per edge and per site. All couplings are CalculatorFloat values
+//! and can be numeric or symbolic.
+//!
+//! For spin models site `i` is spin `i` and for bosonic models site `i` is mode `i`.
+//! For fermionic models with spin the modes are interleaved: site `i` has the spin-up mode `2i`
+//! and the spin-down mode `2i + 1`.
+
+mod lattice;
+pub use lattice::*;
+
+mod spin_models;
+pub use spin_models::*;
+
+mod fermion_models;
+pub use fermion_models::*;
+
+mod boson_models;
+pub use boson_models::*;
@@ -0,0 +1,105 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Lattice;
+use crate::spins::{PauliProduct, SpinHamiltonianSystem};
+use crate::{OperateOnDensityMatrix, SpinIndex};
+use qoqo_calculator::CalculatorFloat;
+
+/// Creates the Heisenberg model J Σ_<i,j> (X_i X_j + Y_i Y_j + Z_i Z_j) on a lattice.
+///
+/// # Arguments
+///
+/// * `lattice` - The lattice, each edge <i,j> couples the spins i and j.
+/// * `coupling` - The exchange coupling J.
+///
+/// # Returns
+///
+/// * `SpinHamiltonianSystem` - The Hamiltonian with one spin per site of the lattice.
+pub fn heisenberg(lattice: &Lattice, coupling: CalculatorFloat) -> SpinHamiltonianSystem {
+    xxz(lattice, coupling.clone(), coupling)
+}
+
+/// Creates the XXZ model J_xy Σ_<i,j> (X_i X_j + Y_i Y_j) + J_z Σ_<i,j> Z_i Z_j on a lattice.
+///
+/// # Arguments
+///
+/// * `lattice` - The lattice, each edge <i,j> couples the spins i and j.
+/// * `coupling_xy` - The exchange coupling J_xy in the xy-plane.
+/// * `coupling_z` - The exchange coupling J_z in z-direction.
+///
+/// # Returns
+///
+/// * `SpinHamiltonianSystem` - The Hamiltonian with one spin per site of the lattice.
+///
+/// # Panics
+///
+/// * Internal error in add_operator_product.
+pub fn xxz(
+    lattice: &Lattice,
+    coupling_xy: CalculatorFloat,
+    coupling_z: CalculatorFloat,
+) -> SpinHamiltonianSystem {
+    let mut system = SpinHamiltonianSystem::with_capacity(
+        Some(lattice.number_sites()),
+        3 * lattice.edges().len(),
+    );
+    for (first, second) in lattice.edges() {
+        for (pp, coupling) in [
+            (PauliProduct::new().x(*first).x(*second), &coupling_xy),
+            (PauliProduct::new().y(*first).y(*second), &coupling_xy),
+            (PauliProduct::new().z(*first).z(*second), &coupling_z),
+        ] {
+            system
+                .add_operator_product(pp, coupling.clone())
+                .expect("Internal bug in add_operator_product");
+        }
+    }
+    system
+}
+
+/// Creates the transverse-field Ising model J Σ_<i,j> Z_i Z_j + h Σ_i X_i on a lattice.
+///
+/// # Arguments
+///
+/// * `lattice` - The lattice, each edge <i,j> couples the spins i and j.
+/// * `coupling` - The Ising coupling J.
+/// * `field` - The transverse field h.
+///
+/// # Returns
+///
+/// * `SpinHamiltonianSystem` - The Hamiltonian with one spin per site of the lattice.
+///
+/// # Panics
+///
+/// * Internal error in add_operator_product.
+pub fn transverse_field_ising(
+    lattice: &Lattice,
+    coupling: CalculatorFloat,
+    field: CalculatorFloat,
+) -> SpinHamiltonianSystem {
+    let mut system = SpinHamiltonianSystem::with_capacity(
+        Some(lattice.number_sites()),
+        lattice.edges().len() + lattice.number_sites(),
+    );
+    for (first, second) in lattice.edges() {
+        system
+            .add_operator_product(PauliProduct::new().z(*first).z(*second), coupling.clone())
+            .expect("Internal bug in add_operator_product");
+    }
+    for site in 0..lattice.number_sites() {
+        system
+            .add_operator_product(PauliProduct::new().x(site), field.clone())
+            .expect("Internal bug in add_operator_product");
+    }
+    system
+}
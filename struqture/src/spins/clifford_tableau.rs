@@ -0,0 +1,415 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{spin_exchange_sign, PauliProduct, SingleSpinOperator};
+use crate::{SpinIndex, StruqtureError};
+use num_complex::Complex64;
+
+/// Image of a single-spin Pauli operator under a Clifford operation, as a PauliProduct and its sign.
+type PauliImage = (PauliProduct, f64);
+
+/// CliffordTableaux represent Clifford unitaries U by the images of single-spin Pauli operators.
+///
+/// Under conjugation P -> U P U^dagger a Clifford unitary maps PauliProducts to PauliProducts with a sign.
+/// The operation is fully determined by the images of X and Z on each spin it acts on,
+/// the image of Y follows from Y = i X Z. Spins outside of the tableau are left unchanged.
+///
+/// The gate functions append a gate to the Clifford operation, i.e. the gate acts after the
+/// operation already represented by the tableau.
+///
+/// # Example
+///
+/// ```
+/// use struqture::prelude::*;
+/// use struqture::spins::{CliffordTableau, PauliProduct};
+///
+/// let tableau = CliffordTableau::new(2).hadamard(0).cnot(0, 1).unwrap();
+///
+/// // Z on spin 0 is mapped to X by the Hadamard gate and to XX by the CNOT gate
+/// let (image, sign) = PauliProduct::new().z(0).conjugate_by_clifford(&tableau);
+/// assert_eq!(image, PauliProduct::new().x(0).x(1));
+/// assert_eq!(sign, 1.0);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliffordTableau {
+    /// The images of X on each spin.
+    x_images: Vec<PauliImage>,
+    /// The images of Z on each spin.
+    z_images: Vec<PauliImage>,
+}
+
+/// Functions for the CliffordTableau
+///
+impl CliffordTableau {
+    /// Creates the CliffordTableau of the identity operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins of the tableau.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The CliffordTableau of the identity operation.
+    pub fn new(number_spins: usize) -> Self {
+        CliffordTableau {
+            x_images: (0..number_spins)
+                .map(|spin| (PauliProduct::new().x(spin), 1.0))
+                .collect(),
+            z_images: (0..number_spins)
+                .map(|spin| (PauliProduct::new().z(spin), 1.0))
+                .collect(),
+        }
+    }
+
+    /// Creates a CliffordTableau from the images of X and Z on each spin.
+    ///
+    /// The images need to preserve the commutation relations of the Pauli operators:
+    /// the images of X and Z on the same spin anticommute, all other pairs of images commute.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_images` - The images U X_i U^dagger as PauliProducts and signs (1.0 or -1.0).
+    /// * `z_images` - The images U Z_i U^dagger as PauliProducts and signs (1.0 or -1.0).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The CliffordTableau with the given images.
+    /// * `Err(StruqtureError::InvalidClifford)` - The images do not describe a Clifford operation.
+    pub fn from_images(
+        x_images: Vec<(PauliProduct, f64)>,
+        z_images: Vec<(PauliProduct, f64)>,
+    ) -> Result<Self, StruqtureError> {
+        if x_images.len() != z_images.len() {
+            return Err(StruqtureError::InvalidClifford {
+                msg: format!(
+                    "{} images of X but {} images of Z were given",
+                    x_images.len(),
+                    z_images.len()
+                ),
+            });
+        }
+        let number_spins = x_images.len();
+        for (product, sign) in x_images.iter().chain(z_images.iter()) {
+            if *sign != 1.0 && *sign != -1.0 {
+                return Err(StruqtureError::InvalidClifford {
+                    msg: format!("The sign {} of the image {} is not 1 or -1", sign, product),
+                });
+            }
+            if product.iter().any(|(spin, _)| *spin >= number_spins) {
+                return Err(StruqtureError::InvalidClifford {
+                    msg: format!(
+                        "The image {} acts on spins outside of the {} spins of the tableau",
+                        product, number_spins
+                    ),
+                });
+            }
+        }
+        // Images ordered as X_0, Z_0, X_1, Z_1, ... so that only pairs (2i, 2i+1) anticommute
+        let images: Vec<&PauliProduct> = x_images
+            .iter()
+            .zip(z_images.iter())
+            .flat_map(|((x_image, _), (z_image, _))| [x_image, z_image])
+            .collect();
+        for (left_index, left) in images.iter().enumerate() {
+            for (right_index, right) in images.iter().enumerate().skip(left_index + 1) {
+                let expected = if left_index / 2 == right_index / 2 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                if spin_exchange_sign(*left, *right) != expected {
+                    return Err(StruqtureError::InvalidClifford {
+                        msg: format!(
+                            "The images {} and {} do not have the commutation relation of the Pauli operators they replace",
+                            left, right
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(CliffordTableau { x_images, z_images })
+    }
+
+    /// Returns the number of spins the CliffordTableau acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of spins in the tableau.
+    pub fn number_spins(&self) -> usize {
+        self.x_images.len()
+    }
+
+    /// Returns the image of X on a spin.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin of the X operator.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&(PauliProduct, f64))` - The image U X U^dagger as PauliProduct and sign.
+    /// * `None` - The spin is outside of the tableau.
+    pub fn x_image(&self, spin: usize) -> Option<&(PauliProduct, f64)> {
+        self.x_images.get(spin)
+    }
+
+    /// Returns the image of Z on a spin.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin of the Z operator.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&(PauliProduct, f64))` - The image U Z U^dagger as PauliProduct and sign.
+    /// * `None` - The spin is outside of the tableau.
+    pub fn z_image(&self, spin: usize) -> Option<&(PauliProduct, f64)> {
+        self.z_images.get(spin)
+    }
+
+    /// Returns the Clifford operation that first applies self and then other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The CliffordTableau of the operation applied after self.
+    ///
+    /// # Returns
+    ///
+    /// * `CliffordTableau` - The CliffordTableau of the combined operation.
+    pub fn then(&self, other: &CliffordTableau) -> CliffordTableau {
+        let number_spins = self.number_spins().max(other.number_spins());
+        let extended = self.extended(number_spins);
+        let conjugate_image = |(product, sign): &PauliImage| {
+            let (image, image_sign) = other.conjugate(product);
+            (image, sign * image_sign)
+        };
+        CliffordTableau {
+            x_images: extended.x_images.iter().map(conjugate_image).collect(),
+            z_images: extended.z_images.iter().map(conjugate_image).collect(),
+        }
+    }
+
+    /// Appends a Hadamard gate, which maps X to Z and Z to X.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin the gate acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The CliffordTableau with the gate applied.
+    pub fn hadamard(self, spin: usize) -> Self {
+        self.single_spin_gate(
+            spin,
+            (PauliProduct::new().z(spin), 1.0),
+            (PauliProduct::new().x(spin), 1.0),
+        )
+    }
+
+    /// Appends an S gate (square root of Z), which maps X to Y and leaves Z unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin the gate acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The CliffordTableau with the gate applied.
+    pub fn s(self, spin: usize) -> Self {
+        self.single_spin_gate(
+            spin,
+            (PauliProduct::new().y(spin), 1.0),
+            (PauliProduct::new().z(spin), 1.0),
+        )
+    }
+
+    /// Appends a CNOT gate.
+    ///
+    /// The gate maps X on the control to X on both spins and Z on the target to Z on both spins.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The control spin of the gate.
+    /// * `target` - The target spin of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The CliffordTableau with the gate applied.
+    /// * `Err(StruqtureError::InvalidClifford)` - Control and target are the same spin.
+    pub fn cnot(self, control: usize, target: usize) -> Result<Self, StruqtureError> {
+        self.two_spin_gate(
+            control,
+            target,
+            [
+                (PauliProduct::new().x(control).x(target), 1.0),
+                (PauliProduct::new().z(control), 1.0),
+                (PauliProduct::new().x(target), 1.0),
+                (PauliProduct::new().z(control).z(target), 1.0),
+            ],
+        )
+    }
+
+    /// Appends a controlled Z gate.
+    ///
+    /// The gate maps X on either spin to X on that spin times Z on the other one.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first spin of the gate.
+    /// * `second` - The second spin of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The CliffordTableau with the gate applied.
+    /// * `Err(StruqtureError::InvalidClifford)` - Both spins are the same.
+    pub fn cz(self, first: usize, second: usize) -> Result<Self, StruqtureError> {
+        self.two_spin_gate(
+            first,
+            second,
+            [
+                (PauliProduct::new().x(first).z(second), 1.0),
+                (PauliProduct::new().z(first), 1.0),
+                (PauliProduct::new().z(first).x(second), 1.0),
+                (PauliProduct::new().z(second), 1.0),
+            ],
+        )
+    }
+
+    /// Appends a SWAP gate, which exchanges the Pauli operators of the two spins.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first spin of the gate.
+    /// * `second` - The second spin of the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The CliffordTableau with the gate applied.
+    /// * `Err(StruqtureError::InvalidClifford)` - Both spins are the same.
+    pub fn swap(self, first: usize, second: usize) -> Result<Self, StruqtureError> {
+        self.two_spin_gate(
+            first,
+            second,
+            [
+                (PauliProduct::new().x(second), 1.0),
+                (PauliProduct::new().z(second), 1.0),
+                (PauliProduct::new().x(first), 1.0),
+                (PauliProduct::new().z(first), 1.0),
+            ],
+        )
+    }
+
+    /// Returns the image U P U^dagger of a PauliProduct P.
+    ///
+    /// # Arguments
+    ///
+    /// * `product` - The PauliProduct P to conjugate.
+    ///
+    /// # Returns
+    ///
+    /// * `(PauliProduct, f64)` - The PauliProduct and sign of the image.
+    pub(crate) fn conjugate(&self, product: &PauliProduct) -> (PauliProduct, f64) {
+        let mut result = PauliProduct::new();
+        let mut phase = Complex64::new(1.0, 0.0);
+        for (spin, operator) in product.iter() {
+            let (image, factor) = match (self.x_images.get(*spin), self.z_images.get(*spin)) {
+                (Some((x_image, x_sign)), Some((z_image, z_sign))) => match operator {
+                    SingleSpinOperator::Identity => (PauliProduct::new(), Complex64::new(1.0, 0.0)),
+                    SingleSpinOperator::X => (x_image.clone(), Complex64::new(*x_sign, 0.0)),
+                    SingleSpinOperator::Z => (z_image.clone(), Complex64::new(*z_sign, 0.0)),
+                    SingleSpinOperator::Y => {
+                        let (image, factor) = x_image.clone() * z_image.clone();
+                        (image, factor * Complex64::new(0.0, x_sign * z_sign))
+                    }
+                },
+                _ => (
+                    PauliProduct::new().set_pauli(*spin, *operator),
+                    Complex64::new(1.0, 0.0),
+                ),
+            };
+            let (next, product_factor) = result * image;
+            result = next;
+            phase *= factor * product_factor;
+        }
+        // The images are Hermitian Pauli products, so the phase is real
+        (result, phase.re)
+    }
+
+    /// Returns the tableau extended with unchanged spins.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins of the extended tableau.
+    ///
+    /// # Returns
+    ///
+    /// * `CliffordTableau` - The extended tableau.
+    fn extended(&self, number_spins: usize) -> CliffordTableau {
+        let mut extended = self.clone();
+        for spin in self.number_spins()..number_spins {
+            extended.x_images.push((PauliProduct::new().x(spin), 1.0));
+            extended.z_images.push((PauliProduct::new().z(spin), 1.0));
+        }
+        extended
+    }
+
+    /// Appends a gate acting on a single spin.
+    ///
+    /// # Arguments
+    ///
+    /// * `spin` - The spin the gate acts on.
+    /// * `x_image` - The image of X on the spin under the gate.
+    /// * `z_image` - The image of Z on the spin under the gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The CliffordTableau with the gate applied.
+    fn single_spin_gate(self, spin: usize, x_image: PauliImage, z_image: PauliImage) -> Self {
+        let mut gate = CliffordTableau::new(spin + 1);
+        gate.x_images[spin] = x_image;
+        gate.z_images[spin] = z_image;
+        self.then(&gate)
+    }
+
+    /// Appends a gate acting on two spins.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first spin the gate acts on.
+    /// * `second` - The second spin the gate acts on.
+    /// * `images` - The images of X and Z on the first spin followed by those on the second spin.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - The CliffordTableau with the gate applied.
+    /// * `Err(StruqtureError::InvalidClifford)` - Both spins are the same.
+    fn two_spin_gate(
+        self,
+        first: usize,
+        second: usize,
+        images: [PauliImage; 4],
+    ) -> Result<Self, StruqtureError> {
+        if first == second {
+            return Err(StruqtureError::InvalidClifford {
+                msg: format!("Two-spin gate acting twice on spin {}", first),
+            });
+        }
+        let [first_x, first_z, second_x, second_z] = images;
+        let mut gate = CliffordTableau::new(first.max(second) + 1);
+        gate.x_images[first] = first_x;
+        gate.z_images[first] = first_z;
+        gate.x_images[second] = second_x;
+        gate.z_images[second] = second_z;
+        Ok(self.then(&gate))
+    }
+}
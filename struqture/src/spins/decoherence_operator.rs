@@ -10,11 +10,11 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::OperateOnSpins;
+use super::{spin_exchange_sign, OperateOnSpins};
 use crate::spins::DecoherenceProduct;
 use crate::{
-    OperateOnDensityMatrix, OperateOnState, SpinIndex, StruqtureError, StruqtureVersion,
-    SymmetricIndex,
+    commutation, OperateOnDensityMatrix, OperateOnState, SpinIndex, StruqtureError,
+    StruqtureVersion, SymmetricIndex,
 };
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
@@ -240,6 +240,61 @@ impl DecoherenceOperator {
             internal_map: HashMap::with_capacity(capacity),
         }
     }
+
+    /// Computes the commutator `self * other - other * self`.
+    ///
+    /// Products of Pauli operators either commute or anticommute, so each pair of
+    /// DecoherenceProducts contributes either twice its product or nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The DecoherenceOperator to commute with.
+    ///
+    /// # Returns
+    ///
+    /// * `DecoherenceOperator` - The commutator of self and other.
+    pub fn commutator(&self, other: &DecoherenceOperator) -> DecoherenceOperator {
+        self.commutation(other, -1.0)
+    }
+
+    /// Computes the anticommutator `self * other + other * self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The DecoherenceOperator to anticommute with.
+    ///
+    /// # Returns
+    ///
+    /// * `DecoherenceOperator` - The anticommutator of self and other.
+    pub fn anticommutator(&self, other: &DecoherenceOperator) -> DecoherenceOperator {
+        self.commutation(other, 1.0)
+    }
+
+    /// Computes `self * other + sign * other * self` product by product.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The DecoherenceOperator on the right-hand side.
+    /// * `sign` - The sign of the reversed product.
+    ///
+    /// # Returns
+    ///
+    /// * `DecoherenceOperator` - The resulting DecoherenceOperator.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn commutation(&self, other: &DecoherenceOperator, sign: f64) -> DecoherenceOperator {
+        commutation(
+            self,
+            other,
+            sign,
+            DecoherenceOperator::new(),
+            |left, right| Some(spin_exchange_sign(left, right)),
+            |(product, coefficient)| Ok(vec![(product, CalculatorComplex::from(coefficient))]),
+        )
+        .expect("Internal bug in add_operator_product")
+    }
 }
 
 /// Implements the negative sign function of DecoherenceOperator.
@@ -12,7 +12,12 @@
 
 //! Module for representing spin physical systems
 
+#[cfg(feature = "rayon")]
+use crate::coo_from_rows_parallel;
+use crate::iterative_solvers::{lanczos_lowest, steady_state};
+use crate::time_evolution::{krylov_evolution, runge_kutta_45_evolution};
 use crate::{OperateOnDensityMatrix, SpinIndex, StruqtureError};
+use ndarray::Array2;
 use num_complex::{Complex, Complex64};
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use std::collections::HashMap;
@@ -50,7 +55,16 @@ pub use spin_noise_system::*;
 mod spin_open_system;
 pub use spin_open_system::*;
 
-use crate::CooSparseMatrix;
+mod clifford_tableau;
+pub use clifford_tableau::*;
+
+mod symmetry_sector;
+pub use symmetry_sector::*;
+
+use crate::{
+    apply_rows, block_coo_from_rows, coo_from_rows, csc_from_rows, csr_from_rows, dense_dimension,
+    dense_from_rows, CooSparseMatrix, CscSparseMatrix, CsrSparseMatrix,
+};
 
 /// Trait for non-Hermitian operations on spins.
 ///
@@ -156,19 +170,319 @@ where
             Some(num_spins) => 2usize.pow(num_spins as u32),
         };
 
-        let capacity = dimension;
-        let mut values: Vec<Complex64> = Vec::with_capacity(capacity);
-        let mut rows: Vec<usize> = Vec::with_capacity(capacity);
-        let mut columns: Vec<usize> = Vec::with_capacity(capacity);
+        coo_from_rows(dimension, |row| self.sparse_matrix_entries_on_row(row))
+    }
 
-        for row in 0..dimension {
-            for (col, val) in self.sparse_matrix_entries_on_row(row)?.into_iter() {
-                rows.push(row);
-                columns.push(col);
-                values.push(val);
-            }
+    /// Constructs the sparse matrix representation of the operator-like object as a scipy COO matrix with a given number of spins, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToSparseMatrixOperator::sparse_matrix_coo]. Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix in COO form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, (Vec<usize>, Vec<usize>)))` - The matrix representation of the operator-like object.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_coo_parallel(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let dimension = match number_spins {
+            None => 2usize.pow(self.number_spins() as u32),
+            Some(num_spins) => 2usize.pow(num_spins as u32),
+        };
+
+        coo_from_rows_parallel(dimension, |row| self.sparse_matrix_entries_on_row(row))
+    }
+
+    /// Constructs the sparse matrix of the operator-like object restricted to a symmetry sector in COO format.
+    ///
+    /// Restricting the matrix to a sector conserved by the operator reduces its size from 2^N to the number of basis states in the sector.
+    /// The rows and columns of the matrix correspond to the basis states of the sector in ascending order.
+    /// The conservation of the symmetry is checked in both directions, for entries in the rows of the sector with columns outside of it
+    /// and for entries in the rows outside of the sector with columns inside of it, so all 2^N rows are constructed but only the sector is stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `sector` - The symmetry sector to restrict the matrix to.
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((CooSparseMatrix, Vec<usize>))` - The restricted matrix and the index in the full basis of each basis state of the sector.
+    /// * `Err(StruqtureError::SymmetryNotConserved)` - A non-zero entry couples a state of the sector to a state outside of it.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_coo_sector(
+        &'a self,
+        sector: SpinSymmetrySector,
+        number_spins: Option<usize>,
+    ) -> Result<(CooSparseMatrix, Vec<usize>), StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        let basis = sector.basis(number_spins);
+        let matrix = block_coo_from_rows(2usize.pow(number_spins as u32), &basis, |row| {
+            self.sparse_matrix_entries_on_row(row)
+        })?;
+        Ok((matrix, basis))
+    }
+
+    /// Constructs the sparse matrix representation of the operator-like object with a given number of spins in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of the operator-like object.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csr(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
+        }
+        csr_from_rows(2usize.pow(number_spins as u32), |row| {
+            self.sparse_matrix_entries_on_row(row)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the operator-like object with a given number of spins in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of the operator-like object.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_csc(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
+        }
+        csc_from_rows(2usize.pow(number_spins as u32), |row| {
+            self.sparse_matrix_entries_on_row(row)
+        })
+    }
+
+    /// Constructs the dense matrix representation of the operator-like object with a given number of spins.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the dense matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of the operator-like object.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_matrix(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
+        }
+        let dimension = spin_dense_dimension(number_spins, 1)?;
+        dense_from_rows(dimension, |row| self.sparse_matrix_entries_on_row(row))
+    }
+
+    /// Applies the operator-like object to a state vector without constructing its matrix.
+    ///
+    /// The action is computed term by term from the bit patterns of the Pauli products,
+    /// with the same basis convention as the sparse matrix representation.
+    /// The number of spins is inferred from the length of the state.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state vector the operator-like object is applied to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The state vector after the application of the operator-like object.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the state is not 2^N for N at least the number of spins of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn apply_to_state(&'a self, state: &[Complex64]) -> Result<Vec<Complex64>, StruqtureError> {
+        checked_state_dimension(state.len(), 1, self.current_number_spins())?;
+        let mut terms: Vec<PauliTerm> = Vec::with_capacity(self.len());
+        for (index, value) in self.iter() {
+            let value = CalculatorComplex::from(value.clone());
+            terms.push(pauli_term(
+                index
+                    .iter()
+                    .map(|(spin, operator)| (*spin, SingleSpinOperator::from(*operator))),
+                Complex64::new(*value.re.float()?, *value.im.float()?),
+            ));
+        }
+        Ok(apply_pauli_terms(&terms, state))
+    }
+
+    /// Returns the expectation value <psi|O|psi> of the operator-like object O for a state vector.
+    ///
+    /// The expectation value is evaluated term by term without constructing the matrix of O.
+    /// Since PauliProducts are hermitian, the expectation value of each product is real and
+    /// symbolic coefficients are kept in the result.
+    /// The number of spins is inferred from the length of the state.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state vector psi.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self::Value)` - The expectation value of the operator-like object.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the state is not 2^N for N at least the number of spins of Self.
+    fn expectation_value(&'a self, state: &[Complex64]) -> Result<Self::Value, StruqtureError>
+    where
+        Self::Value: From<f64> + Add<Output = Self::Value> + Mul<f64, Output = Self::Value>,
+    {
+        checked_state_dimension(state.len(), 1, self.current_number_spins())?;
+        let mut expectation = Self::Value::from(0.0);
+        for (index, value) in self.iter() {
+            let term = pauli_term(
+                index
+                    .iter()
+                    .map(|(spin, operator)| (*spin, SingleSpinOperator::from(*operator))),
+                Complex64::new(1.0, 0.0),
+            );
+            expectation = expectation + value.clone() * pauli_term_expectation(&term, state).re;
+        }
+        Ok(expectation)
+    }
+
+    /// Returns the expectation value Tr(p O) of the operator-like object O for a density matrix.
+    ///
+    /// The expectation value is evaluated term by term without constructing the matrix of O.
+    /// The density matrix p is assumed to be hermitian, so that the expectation value of each
+    /// PauliProduct is real.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The density matrix p flattened in row-major form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self::Value)` - The expectation value of the operator-like object.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the density matrix is not 4^N for N at least the number of spins of Self.
+    fn expectation_value_density_matrix(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Self::Value, StruqtureError>
+    where
+        Self::Value: From<f64> + Add<Output = Self::Value> + Mul<f64, Output = Self::Value>,
+    {
+        let dimension =
+            checked_state_dimension(density_matrix.len(), 2, self.current_number_spins())?;
+        let mut expectation = Self::Value::from(0.0);
+        for (index, value) in self.iter() {
+            let term = pauli_term(
+                index
+                    .iter()
+                    .map(|(spin, operator)| (*spin, SingleSpinOperator::from(*operator))),
+                Complex64::new(1.0, 0.0),
+            );
+            expectation =
+                expectation + value.clone() * pauli_term_trace(&term, density_matrix, dimension).re;
+        }
+        Ok(expectation)
+    }
+
+    /// Returns the lowest eigenvalues of the hermitian operator-like object.
+    ///
+    /// The eigenvalues are computed with the Lanczos method, applying the operator-like object
+    /// row by row with [ToSparseMatrixOperator::sparse_matrix_entries_on_row] without constructing its matrix.
+    /// Degenerate eigenvalues are returned with their multiplicity.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_eigenvalues` - The number of lowest eigenvalues, at most 2^N.
+    /// * `number_spins` - The number of spins N of the Hilbert space.
+    /// * `tolerance` - The tolerance of the residual |O v - lambda v| relative to max(1, |lambda|).
+    /// * `maximal_iterations` - The maximal number of applications of the operator-like object.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<f64>)` - The lowest eigenvalues in ascending order.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(StruqtureError::NoConvergence)` - An eigenvalue did not converge within the maximal number of iterations.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn lowest_eigenvalues(
+        &'a self,
+        number_eigenvalues: usize,
+        number_spins: Option<usize>,
+        tolerance: f64,
+        maximal_iterations: usize,
+    ) -> Result<Vec<f64>, StruqtureError> {
+        Ok(self
+            .lowest_eigenstates(
+                number_eigenvalues,
+                number_spins,
+                tolerance,
+                maximal_iterations,
+            )?
+            .into_iter()
+            .map(|(eigenvalue, _)| eigenvalue)
+            .collect())
+    }
+
+    /// Returns the lowest eigenvalues and eigenvectors of the hermitian operator-like object.
+    ///
+    /// The eigenpairs are computed with the Lanczos method, applying the operator-like object
+    /// row by row with [ToSparseMatrixOperator::sparse_matrix_entries_on_row] without constructing its matrix.
+    /// Degenerate eigenvalues are returned with their multiplicity and orthonormal eigenvectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_eigenvalues` - The number of lowest eigenvalues, at most 2^N.
+    /// * `number_spins` - The number of spins N of the Hilbert space.
+    /// * `tolerance` - The tolerance of the residual |O v - lambda v| relative to max(1, |lambda|).
+    /// * `maximal_iterations` - The maximal number of applications of the operator-like object.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(f64, Vec<Complex64>)>)` - The lowest eigenvalues in ascending order with their normalized eigenvectors.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(StruqtureError::NoConvergence)` - An eigenvalue did not converge within the maximal number of iterations.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn lowest_eigenstates(
+        &'a self,
+        number_eigenvalues: usize,
+        number_spins: Option<usize>,
+        tolerance: f64,
+        maximal_iterations: usize,
+    ) -> Result<Vec<(f64, Vec<Complex64>)>, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
         }
-        Ok((values, (rows, columns)))
+        lanczos_lowest(
+            |vector| apply_rows(vector, |row| self.sparse_matrix_entries_on_row(row)),
+            2usize.pow(number_spins as u32),
+            number_eigenvalues,
+            tolerance,
+            LANCZOS_KRYLOV_DIMENSION,
+            maximal_iterations,
+        )
     }
 
     /// Constructs the sparse matrix entries for one row of the sparse matrix.
@@ -333,6 +647,9 @@ where
     }
 }
 
+/// Dimension of the Krylov subspace after which the Lanczos method of the eigensolvers is restarted.
+const LANCZOS_KRYLOV_DIMENSION: usize = 50;
+
 pub trait ToSparseMatrixSuperOperator<'a>: OperateOnSpins<'a> + PartialEq + Clone {
     /// Constructs the sparse matrix representation of the superoperator as a HashMap.
     ///
@@ -398,22 +715,157 @@ pub trait ToSparseMatrixSuperOperator<'a>: OperateOnSpins<'a> + PartialEq + Clon
             None => self.number_spins(),
             Some(num_spins) => num_spins,
         };
-        let capacity = dimension;
-        let mut values: Vec<Complex64> = Vec::with_capacity(capacity);
-        let mut rows: Vec<usize> = Vec::with_capacity(capacity);
-        let mut columns: Vec<usize> = Vec::with_capacity(capacity);
+        coo_from_rows(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_spins)
+        })
+    }
 
-        for row in 0..dimension.pow(2) {
-            for (col, val) in self
-                .sparse_matrix_superoperator_entries_on_row(row, number_spins)?
-                .into_iter()
-            {
-                rows.push(row);
-                columns.push(col);
-                values.push(val);
-            }
+    /// Constructs the sparse matrix of the superoperator restricted to a symmetry sector in COO format.
+    ///
+    /// The rows and columns of the matrix correspond to the entries of the flattened density matrix in the sector in ascending order.
+    /// The conservation of the symmetry is checked in both directions, for entries in the rows of the sector with columns outside of it
+    /// and for entries in the rows outside of the sector with columns inside of it, so all 4^N rows are constructed but only the sector is stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `sector` - The symmetry sector of density matrices to restrict the superoperator to.
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((CooSparseMatrix, Vec<usize>))` - The restricted matrix and the index in the flattened density matrix of each entry of the sector.
+    /// * `Err(StruqtureError::SymmetryNotConserved)` - A non-zero entry couples a state of the sector to a state outside of it.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_coo_sector(
+        &'a self,
+        sector: SpinSuperoperatorSector,
+        number_spins: Option<usize>,
+    ) -> Result<(CooSparseMatrix, Vec<usize>), StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        let basis = sector.basis(number_spins);
+        let matrix = block_coo_from_rows(4usize.pow(number_spins as u32), &basis, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_spins)
+        })?;
+        Ok((matrix, basis))
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in COO representation, constructing the rows in parallel.
+    ///
+    /// The result is identical to [ToSparseMatrixSuperOperator::sparse_matrix_superoperator_coo]. Requires the `rayon` feature.
+    ///
+    /// The superoperator for the operator O is defined as the Matrix S so that
+    /// `flatten(-i [O, p]) = S flatten(p)` wher `[,]` is the commutator, `p` is a matrix
+    /// and `flatten` flattens a matrix into a vector in row-major form.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix in COO form.
+    ///
+    /// # Returns
+    ///
+    /// * `(Vec<Complex64>, (Vec<usize>, Vec<usize>)` - The matrix representation of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    #[cfg(feature = "rayon")]
+    fn sparse_matrix_superoperator_coo_parallel(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<CooSparseMatrix, StruqtureError>
+    where
+        Self: Sync,
+    {
+        let dimension = match number_spins {
+            None => 2usize.pow(self.current_number_spins() as u32),
+            Some(num_spins) => 2usize.pow(num_spins as u32),
+        };
+        let number_spins = match number_spins {
+            None => self.number_spins(),
+            Some(num_spins) => num_spins,
+        };
+        coo_from_rows_parallel(dimension.pow(2), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_spins)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in CSR format.
+    ///
+    /// The column indices of each row are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csr(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<CsrSparseMatrix, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
+        }
+        csr_from_rows(4usize.pow(number_spins as u32), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_spins)
+        })
+    }
+
+    /// Constructs the sparse matrix representation of the superoperator in CSC format.
+    ///
+    /// The row indices of each column are sorted and unique.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the sparse matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<Complex64>, Vec<usize>, Vec<usize>))` - The matrix representation of Self.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn sparse_matrix_superoperator_csc(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<CscSparseMatrix, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
+        }
+        csc_from_rows(4usize.pow(number_spins as u32), |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_spins)
+        })
+    }
+
+    /// Constructs the dense matrix representation of the superoperator.
+    ///
+    /// The superoperator for the operator O is defined as the Matrix S so that
+    /// `flatten(-i [O, p]) = S flatten(p)` wher `[,]` is the commutator, `p` is a matrix
+    /// and `flatten` flattens a matrix into a vector in row-major form.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins for which to construct the dense matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense matrix representation of Self.
+    /// * `Err(StruqtureError::NumberSpinsExceeded)` - Number of spins in entry exceeds number of spins in system.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn dense_superoperator(
+        &'a self,
+        number_spins: Option<usize>,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let number_spins = number_spins.unwrap_or_else(|| self.number_spins());
+        if number_spins < self.current_number_spins() {
+            return Err(StruqtureError::NumberSpinsExceeded);
         }
-        Ok((values, (rows, columns)))
+        let dimension = spin_dense_dimension(number_spins, 2)?;
+        dense_from_rows(dimension, |row| {
+            self.sparse_matrix_superoperator_entries_on_row(row, number_spins)
+        })
     }
 
     /// Constructs the sparse matrix entries for one row of the sparse matrix superoperator.
@@ -433,6 +885,134 @@ pub trait ToSparseMatrixSuperOperator<'a>: OperateOnSpins<'a> + PartialEq + Clon
         number_spins: usize,
     ) -> Result<HashMap<usize, Complex<f64>>, StruqtureError>;
 
+    /// Applies the superoperator to a flattened density matrix without constructing its matrix.
+    ///
+    /// The action is computed term by term from the bit patterns of the Pauli and decoherence products.
+    /// The density matrix is flattened in row-major form, as for the sparse matrix representation of the superoperator,
+    /// and the number of spins is inferred from its length.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix the superoperator is applied to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix after the application of the superoperator.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the density matrix is not 4^N for N at least the number of spins of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError>;
+
+    /// Evolves a flattened density matrix under the superoperator with the adaptive RK45 method.
+    ///
+    /// The density matrix p(t) solves `d/dt flatten(p) = S flatten(p)` for the superoperator S of
+    /// [ToSparseMatrixSuperOperator::sparse_matrix_superoperator], with `flatten` the row-major flattening.
+    /// The equation is integrated with the Dormand-Prince method, applying the superoperator
+    /// without constructing its matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `time` - The evolution time.
+    /// * `tolerance` - The (positive) tolerance of the local error of each integration step, relative to the magnitude of the entries.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the density matrix is not 4^N for N at least the number of spins of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix(
+        &'a self,
+        density_matrix: &[Complex64],
+        time: f64,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        runge_kutta_45_evolution(
+            |state| self.apply_superoperator(state),
+            density_matrix,
+            time,
+            tolerance,
+        )
+    }
+
+    /// Evolves a flattened density matrix under the superoperator with Krylov subspace exponentiation.
+    ///
+    /// The density matrix p(t) = exp(t S) p(0) is computed for the superoperator S of
+    /// [ToSparseMatrixSuperOperator::sparse_matrix_superoperator], with p flattened in row-major form.
+    /// The exponential is approximated in Krylov subspaces constructed by applying the superoperator
+    /// without constructing its matrix, splitting the evolution time into steps to reach the tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `density_matrix` - The flattened density matrix at time zero.
+    /// * `time` - The evolution time.
+    /// * `krylov_dimension` - The maximal dimension of the Krylov subspaces.
+    /// * `tolerance` - The (positive) tolerance of the error relative to the norm of the density matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Complex64>)` - The flattened density matrix at the evolution time.
+    /// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the density matrix is not 4^N for N at least the number of spins of Self.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn evolve_density_matrix_krylov(
+        &'a self,
+        density_matrix: &[Complex64],
+        time: f64,
+        krylov_dimension: usize,
+        tolerance: f64,
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        checked_state_dimension(density_matrix.len(), 2, self.current_number_spins())?;
+        krylov_evolution(
+            |state| self.apply_superoperator(state),
+            density_matrix,
+            time,
+            krylov_dimension,
+            tolerance,
+        )
+    }
+
+    /// Returns the steady state of the superoperator.
+    ///
+    /// The steady state is the density matrix p with `S flatten(p) = 0` and `Tr(p) = 1` for the
+    /// superoperator S of [ToSparseMatrixSuperOperator::sparse_matrix_superoperator].
+    /// Adding the rank-one term |v><t| to S, with the maximally mixed state v and the trace functional t,
+    /// removes the null space of S, and the resulting linear system `(S + |v><t|) flatten(p) = v`
+    /// is solved with the restarted GMRES method, applying the superoperator without constructing its matrix.
+    /// The steady state is assumed to be unique.
+    ///
+    /// The steady state is returned as a dense matrix, so the dimension 2^N of the Hilbert space
+    /// is limited to [crate::MAXIMAL_DENSE_DIMENSION] like for [ToSparseMatrixOperator::dense_matrix].
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The tolerance of the residual of the linear system relative to the norm of v.
+    /// * `maximal_iterations` - The maximal number of applications of the superoperator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - The dense steady-state density matrix for the number of spins of Self.
+    /// * `Err(StruqtureError::DimensionTooLarge)` - The dimension 2^N exceeds the maximal dimension of dense matrices.
+    /// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+    /// * `Err(StruqtureError::NoConvergence)` - The solver did not converge within the maximal number of iterations.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    fn steady_state(
+        &'a self,
+        tolerance: f64,
+        maximal_iterations: usize,
+    ) -> Result<Array2<Complex64>, StruqtureError> {
+        let dimension = spin_dense_dimension(self.number_spins(), 1)?;
+        steady_state(
+            |vector| self.apply_superoperator(vector),
+            dimension,
+            tolerance,
+            maximal_iterations,
+        )
+    }
+
     /// Return the unitary part of the superoperator in the sparse COO format.
     ///
     /// # Returns
@@ -452,6 +1032,401 @@ pub trait ToSparseMatrixSuperOperator<'a>: OperateOnSpins<'a> + PartialEq + Clon
     ) -> Result<Vec<(CooSparseMatrix, CooSparseMatrix, Complex64)>, StruqtureError>;
 }
 
+/// Returns the dimension of a dense matrix acting on `copies` copies of the Hilbert space of `number_spins` spins.
+///
+/// # Arguments
+///
+/// * `number_spins` - The number of spins of the Hilbert space.
+/// * `copies` - The number of copies of the Hilbert space (1 for operators, 2 for superoperators).
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The dimension of the dense matrix.
+/// * `Err(StruqtureError::DimensionTooLarge)` - The dimension exceeds the maximal dimension of dense matrices.
+/// * `Err(StruqtureError::HilbertSpaceTooLarge)` - The dimension does not fit into usize.
+fn spin_dense_dimension(number_spins: usize, copies: u32) -> Result<usize, StruqtureError> {
+    dense_dimension(
+        std::iter::repeat(2).take(number_spins),
+        copies,
+        number_spins,
+        0,
+    )
+}
+
+/// Returns the sign picked up when exchanging the order of two Pauli-type products.
+///
+/// Products of single-spin Pauli operators either commute or anticommute: they anticommute
+/// when they act with different non-identity operators on an odd number of spins.
+///
+/// # Arguments
+///
+/// * `left` - The left product.
+/// * `right` - The right product.
+///
+/// # Returns
+///
+/// * `f64` - 1.0 if the products commute, -1.0 if they anticommute.
+pub(crate) fn spin_exchange_sign<T>(left: &T, right: &T) -> f64
+where
+    T: SpinIndex,
+    T::SingleSpinType: PartialEq,
+{
+    let differing = left
+        .iter()
+        .filter(|(index, operator)| {
+            matches!(right.get(index), Some(right_operator) if right_operator != operator)
+        })
+        .count();
+    if differing % 2 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Partitions PauliProducts into groups of mutually commuting products with greedy graph colouring.
+///
+/// Two products conflict when they do not commute, either qubit-wise or as operators.
+/// The products are coloured in the order of decreasing number of conflicts (Welsh-Powell order),
+/// each one joining the first group without a conflicting product.
+///
+/// # Arguments
+///
+/// * `products` - The PauliProducts to partition.
+/// * `qubit_wise` - Whether the products of a group have to commute qubit-wise.
+///
+/// # Returns
+///
+/// * `Vec<Vec<usize>>` - The positions in `products` of the products of each group.
+pub(crate) fn group_commuting_products(
+    products: &[&PauliProduct],
+    qubit_wise: bool,
+) -> Vec<Vec<usize>> {
+    let commute = |left: &PauliProduct, right: &PauliProduct| {
+        if qubit_wise {
+            left.iter().all(|(index, operator)| match right.get(index) {
+                Some(right_operator) => right_operator == operator,
+                None => true,
+            })
+        } else {
+            spin_exchange_sign(left, right) == 1.0
+        }
+    };
+    let conflicts: Vec<Vec<usize>> = products
+        .iter()
+        .map(|left| {
+            products
+                .iter()
+                .enumerate()
+                .filter(|(_, right)| !commute(left, right))
+                .map(|(index, _)| index)
+                .collect()
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..products.len()).collect();
+    order.sort_by(|left, right| {
+        conflicts[*right]
+            .len()
+            .cmp(&conflicts[*left].len())
+            .then_with(|| products[*left].cmp(products[*right]))
+    });
+    let mut colours: Vec<Option<usize>> = vec![None; products.len()];
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for index in order {
+        let colour = (0..groups.len())
+            .find(|colour| {
+                conflicts[index]
+                    .iter()
+                    .all(|neighbour| colours[*neighbour] != Some(*colour))
+            })
+            .unwrap_or(groups.len());
+        if colour == groups.len() {
+            groups.push(Vec::new());
+        }
+        groups[colour].push(index);
+        colours[index] = Some(colour);
+    }
+    groups
+}
+
+/// Returns the single-spin Pauli operators a group of qubit-wise commuting PauliProducts acts with.
+///
+/// # Arguments
+///
+/// * `products` - The qubit-wise commuting PauliProducts.
+///
+/// # Returns
+///
+/// * `PauliProduct` - The Pauli operator measured on each spin the products act on.
+pub(crate) fn measurement_basis<'b, I>(products: I) -> PauliProduct
+where
+    I: IntoIterator<Item = &'b PauliProduct>,
+{
+    products
+        .into_iter()
+        .flat_map(|product| product.iter())
+        .fold(PauliProduct::new(), |basis, (index, operator)| {
+            basis.set_pauli(*index, *operator)
+        })
+}
+
+/// Pauli-type term in the form (flip_mask, sign_mask, prefactor).
+///
+/// The term maps the basis state |c> with c = r ^ flip_mask to
+/// prefactor * (-1)^popcount(r & sign_mask) |r>.
+pub(crate) type PauliTerm = (usize, usize, Complex64);
+
+/// Returns the bit-pattern representation of a product of single spin operators.
+///
+/// # Arguments
+///
+/// * `operators` - The single spin operators of the product and the spins they act on.
+/// * `value` - The prefactor of the product.
+///
+/// # Returns
+///
+/// * `PauliTerm` - The bit-pattern representation of the product.
+pub(crate) fn pauli_term<I>(operators: I, value: Complex64) -> PauliTerm
+where
+    I: IntoIterator<Item = (usize, SingleSpinOperator)>,
+{
+    let mut flip_mask: usize = 0;
+    let mut sign_mask: usize = 0;
+    let mut prefactor = value;
+    for (spin, operator) in operators {
+        match operator {
+            SingleSpinOperator::X => flip_mask |= 1usize << spin,
+            SingleSpinOperator::Y => {
+                flip_mask |= 1usize << spin;
+                sign_mask |= 1usize << spin;
+                prefactor *= Complex64::new(0.0, -1.0);
+            }
+            SingleSpinOperator::Z => sign_mask |= 1usize << spin,
+            SingleSpinOperator::Identity => (),
+        }
+    }
+    (flip_mask, sign_mask, prefactor)
+}
+
+/// Returns the bit-pattern representation of a DecoherenceProduct with unit prefactor.
+///
+/// # Arguments
+///
+/// * `product` - The DecoherenceProduct.
+///
+/// # Returns
+///
+/// * `PauliTerm` - The bit-pattern representation of the product.
+pub(crate) fn decoherence_term(product: &DecoherenceProduct) -> PauliTerm {
+    let mut flip_mask: usize = 0;
+    let mut sign_mask: usize = 0;
+    for (spin, operator) in product.iter() {
+        match operator {
+            SingleDecoherenceOperator::X => flip_mask |= 1usize << spin,
+            SingleDecoherenceOperator::IY => {
+                flip_mask |= 1usize << spin;
+                sign_mask |= 1usize << spin;
+            }
+            SingleDecoherenceOperator::Z => sign_mask |= 1usize << spin,
+            SingleDecoherenceOperator::Identity => (),
+        }
+    }
+    (flip_mask, sign_mask, Complex64::new(1.0, 0.0))
+}
+
+/// Returns the sign (-1)^popcount(state & sign_mask).
+#[inline]
+fn parity_sign(state: usize, sign_mask: usize) -> f64 {
+    if (state & sign_mask).count_ones() % 2 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Checks the length of a state and returns the dimension of the Hilbert space of the spins.
+///
+/// # Arguments
+///
+/// * `length` - The length of the state.
+/// * `copies` - The number of copies of the Hilbert space (1 for state vectors, 2 for flattened density matrices).
+/// * `number_spins` - The minimal number of spins of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The dimension of the Hilbert space of the spins.
+/// * `Err(StruqtureError::MissmatchedStateLength)` - The length is not 2^(copies * N) for N at least number_spins.
+pub(crate) fn checked_state_dimension(
+    length: usize,
+    copies: u32,
+    number_spins: usize,
+) -> Result<usize, StruqtureError> {
+    let exponent = length.trailing_zeros();
+    if length.is_power_of_two()
+        && exponent % copies == 0
+        && (exponent / copies) as usize >= number_spins
+    {
+        Ok(1usize << (exponent / copies))
+    } else {
+        Err(StruqtureError::MissmatchedStateLength {
+            length,
+            number_spins,
+        })
+    }
+}
+
+/// Returns the expectation value <psi|T|psi> of a Pauli-type term T.
+///
+/// # Arguments
+///
+/// * `term` - The Pauli-type term.
+/// * `state` - The state vector psi.
+///
+/// # Returns
+///
+/// * `Complex64` - The expectation value of the term.
+pub(crate) fn pauli_term_expectation(term: &PauliTerm, state: &[Complex64]) -> Complex64 {
+    let (flip_mask, sign_mask, prefactor) = term;
+    let expectation: Complex64 = state
+        .iter()
+        .enumerate()
+        .map(|(row, entry)| entry.conj() * parity_sign(row, *sign_mask) * state[row ^ flip_mask])
+        .sum();
+    prefactor * expectation
+}
+
+/// Returns the trace Tr(p T) of a Pauli-type term T with a density matrix p.
+///
+/// # Arguments
+///
+/// * `term` - The Pauli-type term.
+/// * `density_matrix` - The density matrix p flattened in row-major form.
+/// * `dimension` - The dimension of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Complex64` - The trace of the product of the density matrix and the term.
+pub(crate) fn pauli_term_trace(
+    term: &PauliTerm,
+    density_matrix: &[Complex64],
+    dimension: usize,
+) -> Complex64 {
+    let (flip_mask, sign_mask, prefactor) = term;
+    // Tr(p T) = sum_r T[r, r ^ flip_mask] p[r ^ flip_mask, r]
+    let trace: Complex64 = (0..dimension)
+        .map(|row| {
+            parity_sign(row, *sign_mask) * density_matrix[(row ^ flip_mask) * dimension + row]
+        })
+        .sum();
+    prefactor * trace
+}
+
+/// Applies a sum of Pauli-type terms to a state vector.
+///
+/// # Arguments
+///
+/// * `terms` - The Pauli-type terms of the operator.
+/// * `state` - The state vector.
+///
+/// # Returns
+///
+/// * `Vec<Complex64>` - The state vector after the application of the operator.
+pub(crate) fn apply_pauli_terms(terms: &[PauliTerm], state: &[Complex64]) -> Vec<Complex64> {
+    let mut result: Vec<Complex64> = vec![Complex64::default(); state.len()];
+    for (flip_mask, sign_mask, prefactor) in terms.iter() {
+        for (row, entry) in result.iter_mut().enumerate() {
+            *entry += prefactor * parity_sign(row, *sign_mask) * state[row ^ flip_mask];
+        }
+    }
+    result
+}
+
+/// Applies the commutator superoperator -i[H, p] of a sum of Pauli-type terms to a flattened density matrix.
+///
+/// # Arguments
+///
+/// * `terms` - The Pauli-type terms of the operator H.
+/// * `density_matrix` - The density matrix p flattened in row-major form.
+/// * `number_spins` - The minimal number of spins of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Complex64>)` - The flattened density matrix -i[H, p].
+/// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the density matrix is not 4^N for N at least number_spins.
+pub(crate) fn apply_commutator_terms(
+    terms: &[PauliTerm],
+    density_matrix: &[Complex64],
+    number_spins: usize,
+) -> Result<Vec<Complex64>, StruqtureError> {
+    let dimension = checked_state_dimension(density_matrix.len(), 2, number_spins)?;
+    let mut result: Vec<Complex64> = vec![Complex64::default(); density_matrix.len()];
+    let minus_i = Complex64::new(0.0, -1.0);
+    for (flip_mask, sign_mask, prefactor) in terms.iter() {
+        for (row, entry) in result.iter_mut().enumerate() {
+            let left = row / dimension;
+            let right = row % dimension;
+            // -i H p
+            *entry += minus_i
+                * prefactor
+                * parity_sign(left, *sign_mask)
+                * density_matrix[(left ^ flip_mask) * dimension + right];
+            // +i p H
+            *entry -= minus_i
+                * prefactor
+                * parity_sign(right ^ flip_mask, *sign_mask)
+                * density_matrix[left * dimension + (right ^ flip_mask)];
+        }
+    }
+    Ok(result)
+}
+
+/// Applies the Lindblad superoperator of a sum of Lindblad terms to a flattened density matrix.
+///
+/// Each term ((L, R), rate) contributes rate * (L p R^dagger - 1/2 R^dagger L p - 1/2 p R^dagger L).
+///
+/// # Arguments
+///
+/// * `terms` - The Lindblad terms in the form (left, right, rate) with unit-prefactor left and right products.
+/// * `density_matrix` - The density matrix p flattened in row-major form.
+/// * `number_spins` - The minimal number of spins of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Complex64>)` - The flattened density matrix after the application of the Lindblad superoperator.
+/// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the density matrix is not 4^N for N at least number_spins.
+pub(crate) fn apply_lindblad_terms(
+    terms: &[(PauliTerm, PauliTerm, Complex64)],
+    density_matrix: &[Complex64],
+    number_spins: usize,
+) -> Result<Vec<Complex64>, StruqtureError> {
+    let dimension = checked_state_dimension(density_matrix.len(), 2, number_spins)?;
+    let mut result: Vec<Complex64> = vec![Complex64::default(); density_matrix.len()];
+    for ((left_flip, left_sign, _), (right_flip, right_sign, _), rate) in terms.iter() {
+        for (row, entry) in result.iter_mut().enumerate() {
+            let left = row / dimension;
+            let right = row % dimension;
+            // L p R^dagger
+            *entry += rate
+                * parity_sign(left, *left_sign)
+                * parity_sign(right, *right_sign)
+                * density_matrix[(left ^ left_flip) * dimension + (right ^ right_flip)];
+            // -1/2 R^dagger L p
+            let intermediate = left ^ right_flip;
+            *entry -= 0.5
+                * rate
+                * parity_sign(intermediate, *left_sign ^ *right_sign)
+                * density_matrix[(intermediate ^ left_flip) * dimension + right];
+            // -1/2 p R^dagger L
+            let intermediate = right ^ left_flip;
+            *entry -= 0.5
+                * rate
+                * parity_sign(intermediate, *left_sign ^ *right_sign)
+                * density_matrix[left * dimension + (intermediate ^ right_flip)];
+        }
+    }
+    Ok(result)
+}
+
 /// Trait for Hermitian operations on spins.
 ///
 /// # Example
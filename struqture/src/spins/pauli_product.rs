@@ -10,6 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::CliffordTableau;
 use crate::{CorrespondsTo, GetValue, SpinIndex, StruqtureError, SymmetricIndex};
 use num_complex::Complex64;
 use qoqo_calculator::CalculatorComplex;
@@ -593,6 +594,19 @@ impl PauliProduct {
             items: TinyVec::<[(usize, SingleSpinOperator); 5]>::with_capacity(cap),
         }
     }
+
+    /// Conjugates the PauliProduct P with a Clifford unitary U.
+    ///
+    /// # Arguments
+    ///
+    /// * `tableau` - The CliffordTableau of the Clifford unitary U.
+    ///
+    /// # Returns
+    ///
+    /// * `(PauliProduct, f64)` - The PauliProduct and sign (1.0 or -1.0) of U P U^dagger.
+    pub fn conjugate_by_clifford(&self, tableau: &CliffordTableau) -> (PauliProduct, f64) {
+        tableau.conjugate(self)
+    }
 }
 
 /// Implements the default function (Default trait) of PauliProduct (an empty PauliProduct).
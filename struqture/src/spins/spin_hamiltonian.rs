@@ -10,10 +10,14 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{OperateOnSpins, SpinOperator, ToSparseMatrixOperator, ToSparseMatrixSuperOperator};
-use crate::spins::{HermitianOperateOnSpins, PauliProduct, SpinIndex};
+use super::{
+    apply_commutator_terms, group_commuting_products, measurement_basis, pauli_term,
+    spin_exchange_sign, OperateOnSpins, PauliTerm, SpinOperator, ToSparseMatrixOperator,
+    ToSparseMatrixSuperOperator,
+};
+use crate::spins::{CliffordTableau, HermitianOperateOnSpins, PauliProduct, SpinIndex};
 use crate::{
-    CooSparseMatrix, GetValue, OperateOnDensityMatrix, OperateOnState, StruqtureError,
+    commutation, CooSparseMatrix, GetValue, OperateOnDensityMatrix, OperateOnState, StruqtureError,
     StruqtureVersion,
 };
 use num_complex::Complex64;
@@ -200,6 +204,21 @@ impl<'a> ToSparseMatrixSuperOperator<'a> for SpinHamiltonian {
         )
     }
 
+    // From trait
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let mut terms: Vec<PauliTerm> = Vec::with_capacity(self.len());
+        for (product, value) in self.iter() {
+            terms.push(pauli_term(
+                product.iter().copied(),
+                Complex64::new(*value.float()?, 0.0),
+            ));
+        }
+        apply_commutator_terms(&terms, density_matrix, self.current_number_spins())
+    }
+
     // From trait
     fn unitary_sparse_matrix_coo(&'a self) -> Result<crate::CooSparseMatrix, StruqtureError> {
         self.sparse_matrix_coo(None)
@@ -252,6 +271,147 @@ impl SpinHamiltonian {
             internal_map: HashMap::with_capacity(capacity),
         }
     }
+
+    /// Computes the commutator `self * other - other * self`.
+    ///
+    /// Products of Pauli operators either commute or anticommute, so each pair of
+    /// PauliProducts contributes either twice its product or nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The SpinHamiltonian to commute with.
+    ///
+    /// # Returns
+    ///
+    /// * `SpinOperator` - The commutator of self and other.
+    pub fn commutator(&self, other: &SpinHamiltonian) -> SpinOperator {
+        self.commutation(other, -1.0)
+    }
+
+    /// Computes the anticommutator `self * other + other * self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The SpinHamiltonian to anticommute with.
+    ///
+    /// # Returns
+    ///
+    /// * `SpinOperator` - The anticommutator of self and other.
+    pub fn anticommutator(&self, other: &SpinHamiltonian) -> SpinOperator {
+        self.commutation(other, 1.0)
+    }
+
+    /// Conjugates the SpinHamiltonian with a Clifford unitary U by mapping each PauliProduct P to U P U^dagger.
+    ///
+    /// # Arguments
+    ///
+    /// * `tableau` - The CliffordTableau of the Clifford unitary U.
+    ///
+    /// # Returns
+    ///
+    /// * `SpinHamiltonian` - The conjugated SpinHamiltonian.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    pub fn conjugate_by_clifford(&self, tableau: &CliffordTableau) -> SpinHamiltonian {
+        let mut op = SpinHamiltonian::with_capacity(self.len());
+        for (pp, value) in self.iter() {
+            let (image, sign) = pp.conjugate_by_clifford(tableau);
+            op.add_operator_product(image, value.clone() * sign)
+                .expect("Internal bug in add_operator_product");
+        }
+        op
+    }
+
+    /// Partitions the SpinHamiltonian into groups of qubit-wise commuting PauliProducts.
+    ///
+    /// PauliProducts commute qubit-wise when they act with the same Pauli operator on every spin they share,
+    /// so that all products of a group can be measured simultaneously in a product basis.
+    /// The groups are found with greedy colouring of the graph of products that do not commute qubit-wise.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(SpinHamiltonian, PauliProduct)>` - The groups and the Pauli operator to measure on each spin for each group.
+    pub fn qubit_wise_commuting_groups(&self) -> Vec<(SpinHamiltonian, PauliProduct)> {
+        self.groups(true)
+            .into_iter()
+            .map(|group| {
+                let basis = measurement_basis(group.keys());
+                (group, basis)
+            })
+            .collect()
+    }
+
+    /// Partitions the SpinHamiltonian into groups of commuting PauliProducts.
+    ///
+    /// The groups are found with greedy colouring of the graph of products that do not commute.
+    /// Fully commuting groups are usually larger than qubit-wise commuting ones,
+    /// but measuring them simultaneously requires a Clifford transformation to a product basis.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SpinHamiltonian>` - The groups of commuting PauliProducts.
+    pub fn commuting_groups(&self) -> Vec<SpinHamiltonian> {
+        self.groups(false)
+    }
+
+    /// Partitions the SpinHamiltonian into groups of commuting PauliProducts.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_wise` - Whether the products of a group have to commute qubit-wise.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SpinHamiltonian>` - The groups of commuting PauliProducts.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn groups(&self, qubit_wise: bool) -> Vec<SpinHamiltonian> {
+        let products: Vec<&PauliProduct> = self.keys().collect();
+        group_commuting_products(&products, qubit_wise)
+            .into_iter()
+            .map(|group| {
+                let mut op = SpinHamiltonian::with_capacity(group.len());
+                for index in group {
+                    op.add_operator_product(
+                        products[index].clone(),
+                        self.get(products[index]).clone(),
+                    )
+                    .expect("Internal bug in add_operator_product");
+                }
+                op
+            })
+            .collect()
+    }
+
+    /// Computes `self * other + sign * other * self` product by product.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The SpinHamiltonian on the right-hand side.
+    /// * `sign` - The sign of the reversed product.
+    ///
+    /// # Returns
+    ///
+    /// * `SpinOperator` - The resulting SpinOperator.
+    ///
+    /// # Panics
+    ///
+    /// * Internal error in add_operator_product.
+    fn commutation(&self, other: &SpinHamiltonian, sign: f64) -> SpinOperator {
+        commutation(
+            self,
+            other,
+            sign,
+            SpinOperator::new(),
+            |left, right| Some(spin_exchange_sign(left, right)),
+            |(product, coefficient)| Ok(vec![(product, CalculatorComplex::from(coefficient))]),
+        )
+        .expect("Internal bug in add_operator_product")
+    }
 }
 
 impl TryFrom<SpinOperator> for SpinHamiltonian {
@@ -201,6 +201,14 @@ impl<'a> ToSparseMatrixSuperOperator<'a> for SpinHamiltonianSystem {
         )
     }
 
+    // From trait
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        self.hamiltonian.apply_superoperator(density_matrix)
+    }
+
     // From trait
     fn unitary_sparse_matrix_coo(&'a self) -> Result<CooSparseMatrix, StruqtureError> {
         self.sparse_matrix_coo(self.number_spins)
@@ -295,6 +303,43 @@ impl SpinHamiltonianSystem {
             }),
         }
     }
+
+    /// Partitions the SpinHamiltonianSystem into groups of qubit-wise commuting PauliProducts.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(SpinHamiltonianSystem, PauliProduct)>` - The groups and the Pauli operator to measure on each spin for each group.
+    pub fn qubit_wise_commuting_groups(&self) -> Vec<(SpinHamiltonianSystem, PauliProduct)> {
+        self.hamiltonian
+            .qubit_wise_commuting_groups()
+            .into_iter()
+            .map(|(hamiltonian, basis)| {
+                (
+                    SpinHamiltonianSystem {
+                        number_spins: self.number_spins,
+                        hamiltonian,
+                    },
+                    basis,
+                )
+            })
+            .collect()
+    }
+
+    /// Partitions the SpinHamiltonianSystem into groups of commuting PauliProducts.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SpinHamiltonianSystem>` - The groups of commuting PauliProducts.
+    pub fn commuting_groups(&self) -> Vec<SpinHamiltonianSystem> {
+        self.hamiltonian
+            .commuting_groups()
+            .into_iter()
+            .map(|hamiltonian| SpinHamiltonianSystem {
+                number_spins: self.number_spins,
+                hamiltonian,
+            })
+            .collect()
+    }
 }
 
 /// Implements the negative sign function of SpinHamiltonianSystem.
@@ -10,18 +10,23 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{OperateOnSpins, SingleDecoherenceOperator, ToSparseMatrixSuperOperator};
+use super::{
+    apply_lindblad_terms, decoherence_term, OperateOnSpins, PauliTerm, SingleDecoherenceOperator,
+    ToSparseMatrixSuperOperator,
+};
+use crate::iterative_solvers::hermitian_eigensystem;
 use crate::spins::{DecoherenceOperator, DecoherenceProduct};
 use crate::{
     CooSparseMatrix, OperateOnDensityMatrix, SpinIndex, StruqtureError, StruqtureVersion,
     SymmetricIndex,
 };
 use itertools::Itertools;
+use ndarray::Array2;
 use num_complex::Complex64;
 use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter, Keys, Values};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{self, Write};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops;
@@ -234,6 +239,22 @@ impl<'a> ToSparseMatrixSuperOperator<'a> for SpinLindbladNoiseOperator {
         Ok(entries)
     }
 
+    // From trait
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let mut terms: Vec<(PauliTerm, PauliTerm, Complex64)> = Vec::with_capacity(self.len());
+        for ((left, right), value) in self.iter() {
+            terms.push((
+                decoherence_term(left),
+                decoherence_term(right),
+                Complex64::new(*value.re.float()?, *value.im.float()?),
+            ));
+        }
+        apply_lindblad_terms(&terms, density_matrix, self.current_number_spins())
+    }
+
     // From trait
     fn unitary_sparse_matrix_coo(&'a self) -> Result<CooSparseMatrix, StruqtureError> {
         Ok((vec![], (vec![], vec![])) as CooSparseMatrix)
@@ -352,6 +373,65 @@ impl SpinLindbladNoiseOperator {
         }
         new_noise
     }
+
+    /// Returns the jump operators obtained by diagonalizing the rate matrix of the noise.
+    ///
+    /// The noise terms ((L_i, L_j), gamma_ij) define a hermitian rate matrix gamma over the
+    /// DecoherenceProducts occurring in the noise. With the eigendecomposition
+    /// gamma = U diag(lambda) U^†, the same noise is described by the jump operators
+    /// J_k = sqrt(lambda_k) sum_i U_ik L_i acting with unit rate.
+    /// Eigenvalues vanishing within numerical precision do not produce a jump operator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<DecoherenceOperator>)` - The jump operators.
+    /// * `Err(StruqtureError::NonHermitianRateMatrix)` - The rate matrix is not hermitian.
+    /// * `Err(StruqtureError::NegativeRate)` - The rate matrix has a negative eigenvalue.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    pub fn jump_operators(&self) -> Result<Vec<DecoherenceOperator>, StruqtureError> {
+        let products: Vec<&DecoherenceProduct> = self
+            .keys()
+            .flat_map(|(left, right)| [left, right])
+            .collect::<BTreeSet<&DecoherenceProduct>>()
+            .into_iter()
+            .collect();
+        let positions: HashMap<&DecoherenceProduct, usize> = products
+            .iter()
+            .enumerate()
+            .map(|(position, product)| (*product, position))
+            .collect();
+        let dimension = products.len();
+        let mut rates: Array2<Complex64> = Array2::zeros((dimension, dimension));
+        for ((left, right), rate) in self.iter() {
+            rates[[positions[left], positions[right]]] +=
+                Complex64::new(*rate.re.float()?, *rate.im.float()?);
+        }
+        let tolerance = 1e-10 * rates.iter().map(|rate| rate.norm()).fold(0.0, f64::max);
+        for ((row, column), rate) in rates.indexed_iter() {
+            if (rate - rates[[column, row]].conj()).norm() > tolerance {
+                return Err(StruqtureError::NonHermitianRateMatrix);
+            }
+        }
+        let (eigenvalues, eigenvectors) = hermitian_eigensystem(&rates);
+        let mut jump_operators: Vec<DecoherenceOperator> = Vec::new();
+        for (index, eigenvalue) in eigenvalues.into_iter().enumerate() {
+            if eigenvalue < -tolerance {
+                return Err(StruqtureError::NegativeRate { eigenvalue });
+            }
+            if eigenvalue <= tolerance {
+                continue;
+            }
+            let mut jump_operator = DecoherenceOperator::with_capacity(dimension);
+            for (product, coefficient) in products.iter().zip(eigenvectors.column(index)) {
+                jump_operator.add_operator_product(
+                    (*product).clone(),
+                    CalculatorComplex::from(coefficient * eigenvalue.sqrt()),
+                )?;
+            }
+            jump_operators.push(jump_operator);
+        }
+        Ok(jump_operators)
+    }
 }
 
 /// Implements the negative sign function of SpinLindbladNoiseOperator.
@@ -10,7 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{DecoherenceProduct, ToSparseMatrixSuperOperator};
+use super::{DecoherenceOperator, DecoherenceProduct, ToSparseMatrixSuperOperator};
 use crate::spins::{OperateOnSpins, SpinIndex, SpinLindbladNoiseOperator};
 use crate::{CooSparseMatrix, OperateOnDensityMatrix, StruqtureError};
 use num_complex::Complex64;
@@ -191,6 +191,14 @@ impl<'a> ToSparseMatrixSuperOperator<'a> for SpinLindbladNoiseSystem {
             .sparse_matrix_superoperator_entries_on_row(row, number_spins)
     }
 
+    // From trait
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        self.operator.apply_superoperator(density_matrix)
+    }
+
     // From trait
     fn unitary_sparse_matrix_coo(&'a self) -> Result<CooSparseMatrix, StruqtureError> {
         Ok((vec![], (vec![], vec![])) as CooSparseMatrix)
@@ -261,6 +269,18 @@ impl SpinLindbladNoiseSystem {
         &self.operator
     }
 
+    /// Returns the jump operators obtained by diagonalizing the rate matrix of the noise.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<DecoherenceOperator>)` - The jump operators.
+    /// * `Err(StruqtureError::NonHermitianRateMatrix)` - The rate matrix is not hermitian.
+    /// * `Err(StruqtureError::NegativeRate)` - The rate matrix has a negative eigenvalue.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    pub fn jump_operators(&self) -> Result<Vec<DecoherenceOperator>, StruqtureError> {
+        self.operator.jump_operators()
+    }
+
     /// Creates a SpinLindbladNoiseSystem from a SpinLindbladNoiseOperator and an optional number of spins.
     ///
     /// # Arguments
@@ -11,10 +11,15 @@
 // limitations under the License.
 
 use super::SpinLindbladNoiseSystem;
-use crate::spins::{OperateOnSpins, SpinHamiltonianSystem, ToSparseMatrixSuperOperator};
-use crate::{CooSparseMatrix, OpenSystem, OperateOnDensityMatrix, StruqtureError};
+use crate::spins::{
+    apply_pauli_terms, checked_state_dimension, decoherence_term, pauli_term,
+    pauli_term_expectation, DecoherenceProduct, OperateOnSpins, PauliProduct, PauliTerm,
+    SpinHamiltonianSystem, SpinOperator, ToSparseMatrixSuperOperator,
+};
+use crate::{CooSparseMatrix, OpenSystem, OperateOnDensityMatrix, SpinIndex, StruqtureError};
 use num_complex::Complex64;
-use qoqo_calculator::CalculatorFloat;
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::{self, Write};
@@ -149,6 +154,19 @@ impl<'a> ToSparseMatrixSuperOperator<'a> for SpinLindbladOpenSystem {
         Ok(system_row)
     }
 
+    // From trait
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        let mut result = self.system.apply_superoperator(density_matrix)?;
+        let noise_result = self.noise.apply_superoperator(density_matrix)?;
+        for (entry, noise_entry) in result.iter_mut().zip(noise_result) {
+            *entry += noise_entry;
+        }
+        Ok(result)
+    }
+
     // From trait
     fn unitary_sparse_matrix_coo(&'a self) -> Result<CooSparseMatrix, StruqtureError> {
         self.system.unitary_sparse_matrix_coo()
@@ -192,6 +210,266 @@ impl SpinLindbladOpenSystem {
             noise: SpinLindbladNoiseSystem::new(number_spins),
         }
     }
+
+    /// Returns the effective non-hermitian Hamiltonian of the open system.
+    ///
+    /// The effective Hamiltonian H_eff = H - i/2 sum_ij gamma_ij R_j^† L_i generates the deterministic
+    /// evolution between quantum jumps in quantum-trajectory methods. The DecoherenceProducts of
+    /// the noise terms are converted to PauliProducts, picking up a factor i for every iY operator.
+    ///
+    /// # Returns
+    ///
+    /// * `SpinOperator` - The effective non-hermitian Hamiltonian.
+    ///
+    /// # Panics
+    ///
+    /// * Internal bug in add_operator_product.
+    pub fn effective_hamiltonian(&self) -> SpinOperator {
+        let mut effective = SpinOperator::from(self.system.operator().clone());
+        for ((left, right), rate) in self.noise.iter() {
+            let (left_product, left_prefactor) =
+                DecoherenceProduct::decoherence_to_spin(left.clone());
+            let (right_product, right_prefactor) =
+                DecoherenceProduct::decoherence_to_spin(right.clone());
+            // PauliProducts are hermitian, so R^† only conjugates the prefactor of R
+            let (product, prefactor) = right_product * left_product;
+            let prefactor =
+                right_prefactor.conj() * left_prefactor * prefactor * Complex64::new(0.0, -0.5);
+            effective
+                .add_operator_product(product, rate.clone() * prefactor)
+                .expect("Internal bug in add_operator_product");
+        }
+        effective
+    }
+
+    /// Simulates the open system with the Monte-Carlo wavefunction (quantum trajectory) method.
+    ///
+    /// Instead of the 4^N dimensional density matrix, state vectors of dimension 2^N are
+    /// propagated with the effective non-hermitian Hamiltonian and interrupted by quantum jumps.
+    /// The jump operators are obtained by diagonalizing the rate matrix of the noise.
+    /// A jump occurs when the squared norm of the state falls below a uniformly drawn random number,
+    /// the jump operator is chosen with a probability proportional to the squared norm of the jumped state.
+    /// The evolution between two times of the grid is integrated with fourth-order Runge-Kutta substeps,
+    /// which also set the time resolution of the jumps.
+    /// All random numbers are drawn from `rng`, so for a given state of the random number generator the result is deterministic.
+    /// A run is reproduced by passing the same arguments and a generator seeded with the same seed,
+    /// for example `StdRng::seed_from_u64(seed)` of the rand crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_state` - The (non-zero) state vector at the first time of the grid, normalized before the propagation.
+    /// * `times` - The time grid in ascending order at which the observables are recorded.
+    /// * `observables` - The observables O whose expectation values are averaged over the trajectories.
+    /// * `number_trajectories` - The number of trajectories.
+    /// * `number_substeps` - The number of integration substeps between two times of the grid.
+    /// * `rng` - The random number generator, for example a seeded StdRng.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Vec<Complex64>>)` - The averaged expectation values `<O>`, indexed by the time first and the observable second.
+    /// * `Err(StruqtureError::MissmatchedStateLength)` - The length of the state is not 2^N for N at least the number of spins.
+    /// * `Err(StruqtureError::UnsortedTimeGrid)` - The times are not in ascending order.
+    /// * `Err(StruqtureError::NonHermitianRateMatrix)` - The rate matrix of the noise is not hermitian.
+    /// * `Err(StruqtureError::NegativeRate)` - The rate matrix of the noise has a negative eigenvalue.
+    /// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+    pub fn monte_carlo_wavefunction<R: Rng>(
+        &self,
+        initial_state: &[Complex64],
+        times: &[f64],
+        observables: &[SpinOperator],
+        number_trajectories: usize,
+        number_substeps: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<Complex64>>, StruqtureError> {
+        let number_spins = observables
+            .iter()
+            .map(|observable| observable.current_number_spins())
+            .fold(self.number_spins(), usize::max);
+        checked_state_dimension(initial_state.len(), 1, number_spins)?;
+        if times.windows(2).any(|window| window[1] < window[0]) {
+            return Err(StruqtureError::UnsortedTimeGrid);
+        }
+
+        let pauli_product_term = |product: &PauliProduct| {
+            pauli_term(
+                product.iter().map(|(spin, operator)| (*spin, *operator)),
+                Complex64::new(1.0, 0.0),
+            )
+        };
+        // The generator -i H_eff of the evolution between jumps
+        let generator: Vec<PauliTerm> =
+            sorted_terms(self.effective_hamiltonian().iter(), pauli_product_term)?
+                .into_iter()
+                .map(|(flip_mask, sign_mask, prefactor)| {
+                    (flip_mask, sign_mask, prefactor * Complex64::new(0.0, -1.0))
+                })
+                .collect();
+        let jump_operators = self
+            .noise
+            .jump_operators()?
+            .iter()
+            .map(|jump_operator| sorted_terms(jump_operator.iter(), decoherence_term))
+            .collect::<Result<Vec<Vec<PauliTerm>>, StruqtureError>>()?;
+        let observables = observables
+            .iter()
+            .map(|observable| sorted_terms(observable.iter(), pauli_product_term))
+            .collect::<Result<Vec<Vec<PauliTerm>>, StruqtureError>>()?;
+
+        let initial_norm = squared_norm(initial_state).sqrt();
+        let mut averages: Vec<Vec<Complex64>> =
+            vec![vec![Complex64::default(); observables.len()]; times.len()];
+        for _ in 0..number_trajectories {
+            let mut state: Vec<Complex64> = initial_state
+                .iter()
+                .map(|entry| entry / initial_norm)
+                .collect();
+            let mut threshold: f64 = rng.gen();
+            for (time_index, average) in averages.iter_mut().enumerate() {
+                if time_index > 0 {
+                    let step = (times[time_index] - times[time_index - 1]) / number_substeps as f64;
+                    for _ in 0..number_substeps {
+                        state = runge_kutta_step(&generator, &state, step);
+                        if squared_norm(&state) <= threshold {
+                            if let Some(jumped_state) = quantum_jump(&jump_operators, &state, rng) {
+                                state = jumped_state;
+                            }
+                            threshold = rng.gen();
+                        }
+                    }
+                }
+                let norm = squared_norm(&state);
+                for (entry, observable) in average.iter_mut().zip(observables.iter()) {
+                    *entry += observable
+                        .iter()
+                        .map(|term| pauli_term_expectation(term, &state))
+                        .sum::<Complex64>()
+                        / norm;
+                }
+            }
+        }
+        for entry in averages.iter_mut().flatten() {
+            *entry /= number_trajectories.max(1) as f64;
+        }
+        Ok(averages)
+    }
+}
+
+/// Returns the Pauli-type terms of an operator sorted by their products.
+///
+/// Sorting makes the floating point summation order independent of the hashing of the operator.
+///
+/// # Arguments
+///
+/// * `items` - The products and values of the operator.
+/// * `term` - The function returning the unit-prefactor Pauli-type term of a product.
+///
+/// # Returns
+///
+/// * `Ok(Vec<PauliTerm>)` - The sorted Pauli-type terms.
+/// * `Err(CalculatorError)` - CalculatorFloat could not be converted to f64.
+fn sorted_terms<'a, T, I, F>(items: I, term: F) -> Result<Vec<PauliTerm>, StruqtureError>
+where
+    T: Ord + 'a,
+    I: Iterator<Item = (&'a T, &'a CalculatorComplex)>,
+    F: Fn(&T) -> PauliTerm,
+{
+    let mut items: Vec<(&T, &CalculatorComplex)> = items.collect();
+    items.sort_by(|left, right| left.0.cmp(right.0));
+    items
+        .into_iter()
+        .map(|(product, value)| {
+            let (flip_mask, sign_mask, prefactor) = term(product);
+            Ok((
+                flip_mask,
+                sign_mask,
+                prefactor * Complex64::new(*value.re.float()?, *value.im.float()?),
+            ))
+        })
+        .collect()
+}
+
+/// Returns the squared norm of a state vector.
+fn squared_norm(state: &[Complex64]) -> f64 {
+    state.iter().map(|entry| entry.norm_sqr()).sum()
+}
+
+/// Integrates d/dt psi = G psi over one time step with the fourth-order Runge-Kutta method.
+///
+/// # Arguments
+///
+/// * `generator` - The Pauli-type terms of the generator G.
+/// * `state` - The state vector psi at the beginning of the step.
+/// * `step` - The time step.
+///
+/// # Returns
+///
+/// * `Vec<Complex64>` - The state vector at the end of the step.
+fn runge_kutta_step(generator: &[PauliTerm], state: &[Complex64], step: f64) -> Vec<Complex64> {
+    let shifted = |increment: &[Complex64], factor: f64| -> Vec<Complex64> {
+        state
+            .iter()
+            .zip(increment.iter())
+            .map(|(entry, increment)| entry + increment * factor)
+            .collect()
+    };
+    let k1 = apply_pauli_terms(generator, state);
+    let k2 = apply_pauli_terms(generator, &shifted(&k1, 0.5 * step));
+    let k3 = apply_pauli_terms(generator, &shifted(&k2, 0.5 * step));
+    let k4 = apply_pauli_terms(generator, &shifted(&k3, step));
+    state
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            entry + (k1[index] + 2.0 * k2[index] + 2.0 * k3[index] + k4[index]) * (step / 6.0)
+        })
+        .collect()
+}
+
+/// Applies a randomly chosen quantum jump to a state vector.
+///
+/// The jump operator J is chosen with a probability proportional to |J psi|^2.
+///
+/// # Arguments
+///
+/// * `jump_operators` - The Pauli-type terms of the jump operators.
+/// * `state` - The state vector psi.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// * `Some(Vec<Complex64>)` - The normalized state vector after the jump.
+/// * `None` - No jump operator has a non-zero action on the state.
+fn quantum_jump<R: Rng>(
+    jump_operators: &[Vec<PauliTerm>],
+    state: &[Complex64],
+    rng: &mut R,
+) -> Option<Vec<Complex64>> {
+    let weights: Vec<f64> = jump_operators
+        .iter()
+        .map(|terms| squared_norm(&apply_pauli_terms(terms, state)))
+        .collect();
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut draw = rng.gen::<f64>() * total;
+    let mut chosen = 0;
+    for (index, weight) in weights.iter().enumerate() {
+        if *weight > 0.0 {
+            chosen = index;
+            if draw < *weight {
+                break;
+            }
+            draw -= weight;
+        }
+    }
+    let norm = weights[chosen].sqrt();
+    Some(
+        apply_pauli_terms(&jump_operators[chosen], state)
+            .into_iter()
+            .map(|entry| entry / norm)
+            .collect(),
+    )
 }
 
 /// Implements the negative sign function of SpinLindbladOpenSystem.
@@ -209,6 +209,14 @@ impl<'a> ToSparseMatrixSuperOperator<'a> for SpinSystem {
         )
     }
 
+    // From trait
+    fn apply_superoperator(
+        &'a self,
+        density_matrix: &[Complex64],
+    ) -> Result<Vec<Complex64>, StruqtureError> {
+        self.operator.apply_superoperator(density_matrix)
+    }
+
     // From trait
     fn unitary_sparse_matrix_coo(&'a self) -> Result<CooSparseMatrix, StruqtureError> {
         self.operator.sparse_matrix_coo(self.number_spins)
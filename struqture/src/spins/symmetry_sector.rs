@@ -0,0 +1,251 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Symmetry sectors of the Hilbert space of spins.
+///
+/// A spin is up in the eigenstate |0> of Z with eigenvalue 1 and down in the eigenstate |1>,
+/// so the basis state with index i of N spins has N - popcount(i) spins up.
+/// Operators commuting with the total magnetization conserve the number of spins up,
+/// operators commuting with the product of all Z operators conserve the parity.
+///
+/// # Example
+///
+/// ```
+/// use struqture::spins::SpinSymmetrySector;
+///
+/// // The states of two spins with one spin up are |01> and |10>
+/// assert_eq!(SpinSymmetrySector::NumberUp(1).basis(2), vec![1, 2]);
+/// assert_eq!(SpinSymmetrySector::OddParity.basis(2), vec![1, 2]);
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpinSymmetrySector {
+    /// The basis states with the given number of spins up.
+    NumberUp(usize),
+    /// The basis states with an even number of spins down (eigenvalue 1 of the product of all Z operators).
+    EvenParity,
+    /// The basis states with an odd number of spins down (eigenvalue -1 of the product of all Z operators).
+    OddParity,
+}
+
+impl SpinSymmetrySector {
+    /// Returns whether a basis state lies in the symmetry sector.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The index of the basis state.
+    /// * `number_spins` - The number of spins of the Hilbert space.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the basis state lies in the sector.
+    pub fn contains(&self, state: usize, number_spins: usize) -> bool {
+        let number_down = state.count_ones() as usize;
+        match self {
+            SpinSymmetrySector::NumberUp(number_up) => number_down + number_up == number_spins,
+            SpinSymmetrySector::EvenParity => number_down % 2 == 0,
+            SpinSymmetrySector::OddParity => number_down % 2 == 1,
+        }
+    }
+
+    /// Returns the basis states of the symmetry sector.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins of the Hilbert space.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The indices of the basis states in the sector in ascending order.
+    pub fn basis(&self, number_spins: usize) -> Vec<usize> {
+        match self {
+            SpinSymmetrySector::NumberUp(number_up) => match number_spins.checked_sub(*number_up) {
+                Some(number_down) => states_with_number_down(number_down, number_spins),
+                None => Vec::new(),
+            },
+            SpinSymmetrySector::EvenParity => states_with_parity(false, number_spins),
+            SpinSymmetrySector::OddParity => states_with_parity(true, number_spins),
+        }
+    }
+}
+
+/// Returns the basis states with a fixed number of spins down in ascending order.
+///
+/// The states are enumerated directly as the integers with `number_down` set bits (Gosper's hack)
+/// instead of filtering all 2^N basis states.
+///
+/// # Arguments
+///
+/// * `number_down` - The number of spins down (set bits) of the states.
+/// * `number_spins` - The number of spins of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - The indices of the basis states in ascending order.
+fn states_with_number_down(number_down: usize, number_spins: usize) -> Vec<usize> {
+    if number_down > number_spins {
+        return Vec::new();
+    }
+    if number_down == 0 {
+        return vec![0];
+    }
+    let dimension = 2usize.pow(number_spins as u32);
+    let mut states: Vec<usize> = Vec::new();
+    let mut state = 2usize.pow(number_down as u32) - 1;
+    while state < dimension {
+        states.push(state);
+        // Moves the lowest block of set bits up by one and packs the remaining bits at the bottom
+        let lowest_bit = state & state.wrapping_neg();
+        let carry = state + lowest_bit;
+        state = (((carry ^ state) >> 2) / lowest_bit) | carry;
+    }
+    states
+}
+
+/// Returns the basis states with an even or odd number of spins down in ascending order.
+///
+/// The highest N - 1 spins are free and the lowest spin fixes the parity, so the states are enumerated
+/// directly instead of filtering all 2^N basis states.
+///
+/// # Arguments
+///
+/// * `odd` - Whether the states have an odd number of spins down.
+/// * `number_spins` - The number of spins of the Hilbert space.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - The indices of the basis states in ascending order.
+fn states_with_parity(odd: bool, number_spins: usize) -> Vec<usize> {
+    match number_spins.checked_sub(1) {
+        Some(number_free) => (0..2usize.pow(number_free as u32))
+            .map(|free| {
+                let lowest = (free.count_ones() % 2 == 1) != odd;
+                (free << 1) | lowest as usize
+            })
+            .collect(),
+        None if odd => Vec::new(),
+        None => vec![0],
+    }
+}
+
+/// Symmetry sectors of flattened density matrices of spins.
+///
+/// A superoperator with a (weak) symmetry does not mix the entries |i><j| of the density matrix
+/// with different differences between the quantum numbers of the ket i and the bra j.
+/// Besides Hamiltonians conserving the quantity, this includes noise like spin relaxation,
+/// which changes the number of spins up in ket and bra simultaneously.
+/// The sector without difference contains the populations and the steady state.
+///
+/// The entry |i><j| has the index i * 2^N + j in the flattened density matrix.
+///
+/// # Example
+///
+/// ```
+/// use struqture::spins::SpinSuperoperatorSector;
+///
+/// // The populations and coherences between |01> and |10> of two spins
+/// assert_eq!(
+///     SpinSuperoperatorSector::NumberUpDifference(0).basis(2),
+///     vec![0, 5, 6, 9, 10, 15]
+/// );
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpinSuperoperatorSector {
+    /// The entries |i><j| where the ket i has the given number of spins up more than the bra j.
+    NumberUpDifference(isize),
+    /// The entries |i><j| where the ket and the bra have the same parity.
+    EqualParity,
+    /// The entries |i><j| where the ket and the bra have opposite parities.
+    OppositeParity,
+}
+
+impl SpinSuperoperatorSector {
+    /// Returns whether an entry of the density matrix lies in the symmetry sector.
+    ///
+    /// # Arguments
+    ///
+    /// * `ket` - The index i of the ket of the entry |i><j|.
+    /// * `bra` - The index j of the bra of the entry |i><j|.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the entry lies in the sector.
+    pub fn contains(&self, ket: usize, bra: usize) -> bool {
+        // Spins down are ones in the binary representation
+        let ket_down = ket.count_ones() as isize;
+        let bra_down = bra.count_ones() as isize;
+        match self {
+            SpinSuperoperatorSector::NumberUpDifference(difference) => {
+                bra_down - ket_down == *difference
+            }
+            SpinSuperoperatorSector::EqualParity => (ket_down - bra_down) % 2 == 0,
+            SpinSuperoperatorSector::OppositeParity => (ket_down - bra_down) % 2 != 0,
+        }
+    }
+
+    /// Returns the entries of the flattened density matrix in the symmetry sector.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_spins` - The number of spins of the Hilbert space.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The indices i * 2^N + j of the entries |i><j| in the sector in ascending order.
+    pub fn basis(&self, number_spins: usize) -> Vec<usize> {
+        let dimension = 2usize.pow(number_spins as u32);
+        // The bras in the sector only depend on the number of spins down (or the parity) of the ket
+        let (bras, kets): (Vec<Vec<usize>>, Vec<usize>) = match self {
+            SpinSuperoperatorSector::NumberUpDifference(difference) => {
+                let bras: Vec<Vec<usize>> = (0..=number_spins)
+                    .map(
+                        |ket_down| match (ket_down as isize + difference).try_into() {
+                            Ok(bra_down) => states_with_number_down(bra_down, number_spins),
+                            Err(_) => Vec::new(),
+                        },
+                    )
+                    .collect();
+                let mut kets: Vec<usize> = (0..=number_spins)
+                    .filter(|ket_down| !bras[*ket_down].is_empty())
+                    .flat_map(|ket_down| states_with_number_down(ket_down, number_spins))
+                    .collect();
+                kets.sort_unstable();
+                (bras, kets)
+            }
+            SpinSuperoperatorSector::EqualParity => (
+                vec![
+                    states_with_parity(false, number_spins),
+                    states_with_parity(true, number_spins),
+                ],
+                (0..dimension).collect(),
+            ),
+            SpinSuperoperatorSector::OppositeParity => (
+                vec![
+                    states_with_parity(true, number_spins),
+                    states_with_parity(false, number_spins),
+                ],
+                (0..dimension).collect(),
+            ),
+        };
+        kets.into_iter()
+            .flat_map(|ket| {
+                let ket_down = ket.count_ones() as usize;
+                let bras = match self {
+                    SpinSuperoperatorSector::NumberUpDifference(_) => &bras[ket_down],
+                    _ => &bras[ket_down % 2],
+                };
+                bras.iter().map(move |bra| ket * dimension + bra)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,659 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Symbolic differentiation and parameter enumeration of the string expressions of CalculatorFloat values.
+//!
+//! The expressions are parsed into a small expression tree, differentiated and printed back
+//! into a string that can be evaluated by qoqo_calculator.
+
+use crate::StruqtureError;
+use qoqo_calculator::CalculatorFloat;
+use std::collections::HashSet;
+
+/// Expression tree of a symbolic expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+    Subtract(Box<Expression>, Box<Expression>),
+    Multiply(Box<Expression>, Box<Expression>),
+    Divide(Box<Expression>, Box<Expression>),
+    Power(Box<Expression>, Box<Expression>),
+    Function(String, Vec<Expression>),
+}
+
+/// Token of a symbolic expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(char),
+    LeftParenthesis,
+    RightParenthesis,
+    Comma,
+    Assign,
+    EndOfExpression,
+    Invalid,
+}
+
+/// Returns the derivative of a CalculatorFloat with respect to a parameter.
+///
+/// # Arguments
+///
+/// * `value` - The CalculatorFloat to differentiate.
+/// * `parameter` - The name of the parameter.
+///
+/// # Returns
+///
+/// * `Ok(CalculatorFloat)` - The derivative, numeric if it does not depend on any parameter.
+/// * `Err(StruqtureError::ParsingError)` - The expression could not be parsed.
+/// * `Err(StruqtureError::NonDifferentiableFunction)` - The expression contains a function that cannot be differentiated.
+pub(crate) fn differentiate(
+    value: &CalculatorFloat,
+    parameter: &str,
+) -> Result<CalculatorFloat, StruqtureError> {
+    match value {
+        CalculatorFloat::Float(_) => Ok(CalculatorFloat::ZERO),
+        CalculatorFloat::Str(expression) => {
+            let tokens = tokenize(expression);
+            let mut parser = Parser {
+                tokens,
+                position: 0,
+                expression,
+            };
+            let tree = parser.parse()?;
+            match derivative(&tree, parameter, expression)? {
+                Expression::Number(number) => Ok(CalculatorFloat::Float(number)),
+                derived => Ok(CalculatorFloat::Str(derived.to_string())),
+            }
+        }
+    }
+}
+
+/// Returns the names of all parameters in a symbolic expression.
+///
+/// Every name that is not followed by an opening parenthesis is a parameter,
+/// names followed by an opening parenthesis are functions.
+/// Names assigned with `name = ...;` are local variables after the assignment, uses before its end are parameters.
+///
+/// # Arguments
+///
+/// * `expression` - The symbolic expression.
+///
+/// # Returns
+///
+/// * `HashSet<String>` - The names of the parameters in the expression.
+pub(crate) fn free_parameters(expression: &str) -> HashSet<String> {
+    let tokens = tokenize(expression);
+    let mut parameters: HashSet<String> = HashSet::new();
+    let mut assigned: HashSet<&String> = HashSet::new();
+    // The assigned name only becomes a local variable at the end of its assignment
+    let mut assignment: Option<&String> = None;
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Name(name) => match tokens.get(index + 1) {
+                Some(Token::LeftParenthesis) => (),
+                Some(Token::Assign) => assignment = Some(name),
+                _ => {
+                    if !assigned.contains(name) {
+                        parameters.insert(name.clone());
+                    }
+                }
+            },
+            Token::EndOfExpression => {
+                if let Some(name) = assignment.take() {
+                    assigned.insert(name);
+                }
+            }
+            _ => (),
+        }
+    }
+    parameters
+}
+
+/// Creates the parsing error for an expression.
+fn parsing_error(expression: &str, msg: &str) -> StruqtureError {
+    StruqtureError::ParsingError {
+        target_type: "CalculatorFloat".to_string(),
+        msg: format!("{}: {}", msg, expression),
+    }
+}
+
+/// Splits an expression into tokens.
+///
+/// Unknown characters and malformed number literals become invalid tokens that are rejected by the parser.
+fn tokenize(expression: &str) -> Vec<Token> {
+    let characters: Vec<char> = expression.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut position = 0;
+    while position < characters.len() {
+        let character = characters[position];
+        if character.is_whitespace() {
+            position += 1;
+        } else if character.is_ascii_digit() || character == '.' {
+            let start = position;
+            while position < characters.len()
+                && (characters[position].is_ascii_digit() || characters[position] == '.')
+            {
+                position += 1;
+            }
+            if position < characters.len()
+                && (characters[position] == 'e' || characters[position] == 'E')
+            {
+                let mut exponent_position = position + 1;
+                if exponent_position < characters.len()
+                    && (characters[exponent_position] == '+'
+                        || characters[exponent_position] == '-')
+                {
+                    exponent_position += 1;
+                }
+                if exponent_position < characters.len()
+                    && characters[exponent_position].is_ascii_digit()
+                {
+                    position = exponent_position;
+                    while position < characters.len() && characters[position].is_ascii_digit() {
+                        position += 1;
+                    }
+                }
+            }
+            let literal: String = characters[start..position].iter().collect();
+            match literal.parse() {
+                Ok(number) => tokens.push(Token::Number(number)),
+                Err(_) => tokens.push(Token::Invalid),
+            }
+        } else if character.is_alphabetic() || character == '_' {
+            let start = position;
+            while position < characters.len()
+                && (characters[position].is_alphanumeric() || characters[position] == '_')
+            {
+                position += 1;
+            }
+            tokens.push(Token::Name(characters[start..position].iter().collect()));
+        } else {
+            match character {
+                '*' if position + 1 < characters.len() && characters[position + 1] == '*' => {
+                    tokens.push(Token::Operator('^'));
+                    position += 1;
+                }
+                '+' | '-' | '*' | '/' | '^' => tokens.push(Token::Operator(character)),
+                '(' => tokens.push(Token::LeftParenthesis),
+                ')' => tokens.push(Token::RightParenthesis),
+                ',' => tokens.push(Token::Comma),
+                '=' => tokens.push(Token::Assign),
+                ';' => tokens.push(Token::EndOfExpression),
+                _ => tokens.push(Token::Invalid),
+            }
+            position += 1;
+        }
+    }
+    tokens
+}
+
+/// Recursive descent parser for symbolic expressions.
+struct Parser<'b> {
+    tokens: Vec<Token>,
+    position: usize,
+    expression: &'b str,
+}
+
+impl<'b> Parser<'b> {
+    /// Parses the complete token sequence.
+    fn parse(&mut self) -> Result<Expression, StruqtureError> {
+        let tree = self.parse_sum()?;
+        if self.position != self.tokens.len() {
+            return Err(parsing_error(self.expression, "Unexpected token"));
+        }
+        Ok(tree)
+    }
+
+    /// Returns the current token without consuming it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// Parses sums and differences.
+    fn parse_sum(&mut self) -> Result<Expression, StruqtureError> {
+        let mut tree = self.parse_product()?;
+        while let Some(Token::Operator(operator)) = self.peek() {
+            let operator = *operator;
+            if operator != '+' && operator != '-' {
+                break;
+            }
+            self.position += 1;
+            let right = self.parse_product()?;
+            tree = if operator == '+' {
+                Expression::Add(Box::new(tree), Box::new(right))
+            } else {
+                Expression::Subtract(Box::new(tree), Box::new(right))
+            };
+        }
+        Ok(tree)
+    }
+
+    /// Parses products and quotients.
+    fn parse_product(&mut self) -> Result<Expression, StruqtureError> {
+        let mut tree = self.parse_unary()?;
+        while let Some(Token::Operator(operator)) = self.peek() {
+            let operator = *operator;
+            if operator != '*' && operator != '/' {
+                break;
+            }
+            self.position += 1;
+            let right = self.parse_unary()?;
+            tree = if operator == '*' {
+                Expression::Multiply(Box::new(tree), Box::new(right))
+            } else {
+                Expression::Divide(Box::new(tree), Box::new(right))
+            };
+        }
+        Ok(tree)
+    }
+
+    /// Parses unary signs.
+    fn parse_unary(&mut self) -> Result<Expression, StruqtureError> {
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.position += 1;
+                Ok(Expression::Negate(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::Operator('+')) => {
+                self.position += 1;
+                self.parse_unary()
+            }
+            _ => self.parse_power(),
+        }
+    }
+
+    /// Parses right-associative powers.
+    fn parse_power(&mut self) -> Result<Expression, StruqtureError> {
+        let base = self.parse_atom()?;
+        if let Some(Token::Operator('^')) = self.peek() {
+            self.position += 1;
+            let exponent = self.parse_unary()?;
+            return Ok(Expression::Power(Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    /// Parses numbers, variables, function calls and parenthesised expressions.
+    fn parse_atom(&mut self) -> Result<Expression, StruqtureError> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| parsing_error(self.expression, "Unexpected end of expression"))?;
+        self.position += 1;
+        match token {
+            Token::Number(number) => Ok(Expression::Number(number)),
+            Token::Name(name) => {
+                if self.peek() != Some(&Token::LeftParenthesis) {
+                    return Ok(Expression::Variable(name));
+                }
+                self.position += 1;
+                let mut arguments: Vec<Expression> = vec![self.parse_sum()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.position += 1;
+                    arguments.push(self.parse_sum()?);
+                }
+                self.expect_right_parenthesis()?;
+                Ok(Expression::Function(name, arguments))
+            }
+            Token::LeftParenthesis => {
+                let tree = self.parse_sum()?;
+                self.expect_right_parenthesis()?;
+                Ok(tree)
+            }
+            _ => Err(parsing_error(self.expression, "Unexpected token")),
+        }
+    }
+
+    /// Consumes a closing parenthesis.
+    fn expect_right_parenthesis(&mut self) -> Result<(), StruqtureError> {
+        if self.peek() != Some(&Token::RightParenthesis) {
+            return Err(parsing_error(
+                self.expression,
+                "Missing closing parenthesis",
+            ));
+        }
+        self.position += 1;
+        Ok(())
+    }
+}
+
+// Constructors that fold numeric constants and neutral elements
+fn number(value: f64) -> Expression {
+    Expression::Number(value)
+}
+
+fn function(name: &str, argument: Expression) -> Expression {
+    Expression::Function(name.to_string(), vec![argument])
+}
+
+fn negate(value: Expression) -> Expression {
+    match value {
+        Expression::Number(number) => Expression::Number(-number),
+        Expression::Negate(inner) => *inner,
+        other => Expression::Negate(Box::new(other)),
+    }
+}
+
+fn add(left: Expression, right: Expression) -> Expression {
+    match (left, right) {
+        (Expression::Number(a), Expression::Number(b)) => Expression::Number(a + b),
+        (Expression::Number(a), other) | (other, Expression::Number(a)) if a == 0.0 => other,
+        (left, Expression::Negate(right)) => subtract(left, *right),
+        (left, right) => Expression::Add(Box::new(left), Box::new(right)),
+    }
+}
+
+fn subtract(left: Expression, right: Expression) -> Expression {
+    match (left, right) {
+        (Expression::Number(a), Expression::Number(b)) => Expression::Number(a - b),
+        (left, Expression::Number(0.0)) => left,
+        (Expression::Number(0.0), right) => negate(right),
+        (left, right) => Expression::Subtract(Box::new(left), Box::new(right)),
+    }
+}
+
+fn multiply(left: Expression, right: Expression) -> Expression {
+    match (left, right) {
+        (Expression::Number(a), Expression::Number(b)) => Expression::Number(a * b),
+        (Expression::Number(a), _) | (_, Expression::Number(a)) if a == 0.0 => number(0.0),
+        (Expression::Number(a), other) | (other, Expression::Number(a)) if a == 1.0 => other,
+        (Expression::Number(a), other) | (other, Expression::Number(a)) if a == -1.0 => {
+            negate(other)
+        }
+        (left, right) => Expression::Multiply(Box::new(left), Box::new(right)),
+    }
+}
+
+fn divide(left: Expression, right: Expression) -> Expression {
+    match (left, right) {
+        (Expression::Number(a), Expression::Number(b)) if b != 0.0 => Expression::Number(a / b),
+        (Expression::Number(0.0), _) => number(0.0),
+        (left, Expression::Number(1.0)) => left,
+        (left, right) => Expression::Divide(Box::new(left), Box::new(right)),
+    }
+}
+
+fn power(base: Expression, exponent: Expression) -> Expression {
+    match (base, exponent) {
+        (_, Expression::Number(0.0)) => number(1.0),
+        (base, Expression::Number(1.0)) => base,
+        (base, exponent) => Expression::Power(Box::new(base), Box::new(exponent)),
+    }
+}
+
+/// Returns whether an expression depends on the parameter.
+fn depends_on(tree: &Expression, parameter: &str) -> bool {
+    match tree {
+        Expression::Number(_) => false,
+        Expression::Variable(name) => name == parameter,
+        Expression::Negate(inner) => depends_on(inner, parameter),
+        Expression::Add(left, right)
+        | Expression::Subtract(left, right)
+        | Expression::Multiply(left, right)
+        | Expression::Divide(left, right)
+        | Expression::Power(left, right) => {
+            depends_on(left, parameter) || depends_on(right, parameter)
+        }
+        Expression::Function(_, arguments) => arguments
+            .iter()
+            .any(|argument| depends_on(argument, parameter)),
+    }
+}
+
+/// Returns the derivative of an expression tree with respect to the parameter.
+fn derivative(
+    tree: &Expression,
+    parameter: &str,
+    expression: &str,
+) -> Result<Expression, StruqtureError> {
+    if !depends_on(tree, parameter) {
+        return Ok(number(0.0));
+    }
+    Ok(match tree {
+        Expression::Number(_) => number(0.0),
+        Expression::Variable(_) => number(1.0),
+        Expression::Negate(inner) => negate(derivative(inner, parameter, expression)?),
+        Expression::Add(left, right) => add(
+            derivative(left, parameter, expression)?,
+            derivative(right, parameter, expression)?,
+        ),
+        Expression::Subtract(left, right) => subtract(
+            derivative(left, parameter, expression)?,
+            derivative(right, parameter, expression)?,
+        ),
+        Expression::Multiply(left, right) => add(
+            multiply(derivative(left, parameter, expression)?, (**right).clone()),
+            multiply((**left).clone(), derivative(right, parameter, expression)?),
+        ),
+        Expression::Divide(left, right) => subtract(
+            divide(derivative(left, parameter, expression)?, (**right).clone()),
+            divide(
+                multiply((**left).clone(), derivative(right, parameter, expression)?),
+                multiply((**right).clone(), (**right).clone()),
+            ),
+        ),
+        Expression::Power(base, exponent) => {
+            if depends_on(exponent, parameter) {
+                // d(u^v) = u^v * (v' * log(u) + v * u' / u)
+                multiply(
+                    tree.clone(),
+                    add(
+                        multiply(
+                            derivative(exponent, parameter, expression)?,
+                            function("log", (**base).clone()),
+                        ),
+                        divide(
+                            multiply(
+                                (**exponent).clone(),
+                                derivative(base, parameter, expression)?,
+                            ),
+                            (**base).clone(),
+                        ),
+                    ),
+                )
+            } else {
+                // d(u^c) = c * u^(c - 1) * u'
+                multiply(
+                    multiply(
+                        (**exponent).clone(),
+                        power(
+                            (**base).clone(),
+                            subtract((**exponent).clone(), number(1.0)),
+                        ),
+                    ),
+                    derivative(base, parameter, expression)?,
+                )
+            }
+        }
+        Expression::Function(name, arguments) => {
+            let number_arguments = match name.as_str() {
+                "atan2" | "hypot" | "pow" | "max" | "min" => 2,
+                _ => 1,
+            };
+            if arguments.len() != number_arguments {
+                return Err(parsing_error(
+                    expression,
+                    &format!("Wrong number of arguments of function {}", name),
+                ));
+            }
+            if number_arguments == 2 {
+                let first = arguments[0].clone();
+                let second = arguments[1].clone();
+                if name == "pow" {
+                    return derivative(
+                        &Expression::Power(Box::new(first), Box::new(second)),
+                        parameter,
+                        expression,
+                    );
+                }
+                let first_derivative = derivative(&first, parameter, expression)?;
+                let second_derivative = derivative(&second, parameter, expression)?;
+                return Ok(match name.as_str() {
+                    // d atan2(y, x) = (x * y' - y * x') / (x^2 + y^2)
+                    "atan2" => divide(
+                        subtract(
+                            multiply(second.clone(), first_derivative),
+                            multiply(first.clone(), second_derivative),
+                        ),
+                        add(
+                            multiply(first.clone(), first),
+                            multiply(second.clone(), second),
+                        ),
+                    ),
+                    // d hypot(x, y) = (x * x' + y * y') / hypot(x, y)
+                    "hypot" => divide(
+                        add(
+                            multiply(first, first_derivative),
+                            multiply(second, second_derivative),
+                        ),
+                        tree.clone(),
+                    ),
+                    // The derivatives of max and min are averaged where both arguments are equal
+                    "max" => add(
+                        multiply(
+                            function("theta", subtract(first.clone(), second.clone())),
+                            first_derivative,
+                        ),
+                        multiply(
+                            function("theta", subtract(second, first)),
+                            second_derivative,
+                        ),
+                    ),
+                    _ => add(
+                        multiply(
+                            function("theta", subtract(second.clone(), first.clone())),
+                            first_derivative,
+                        ),
+                        multiply(
+                            function("theta", subtract(first, second)),
+                            second_derivative,
+                        ),
+                    ),
+                });
+            }
+            let argument = arguments[0].clone();
+            let inner = derivative(&argument, parameter, expression)?;
+            let outer = match name.as_str() {
+                "sin" => function("cos", argument),
+                "cos" => negate(function("sin", argument)),
+                "tan" => divide(
+                    number(1.0),
+                    multiply(function("cos", argument.clone()), function("cos", argument)),
+                ),
+                "exp" => function("exp", argument),
+                "exp2" => multiply(number(std::f64::consts::LN_2), function("exp2", argument)),
+                "expm1" => function("exp", argument),
+                "log" => divide(number(1.0), argument),
+                "log10" => divide(number(std::f64::consts::LOG10_E), argument),
+                "sqrt" => divide(number(0.5), function("sqrt", argument)),
+                "cbrt" => divide(
+                    number(1.0 / 3.0),
+                    multiply(
+                        function("cbrt", argument.clone()),
+                        function("cbrt", argument),
+                    ),
+                ),
+                "sinh" => function("cosh", argument),
+                "cosh" => function("sinh", argument),
+                "tanh" => divide(
+                    number(1.0),
+                    multiply(
+                        function("cosh", argument.clone()),
+                        function("cosh", argument),
+                    ),
+                ),
+                "asin" => divide(
+                    number(1.0),
+                    function(
+                        "sqrt",
+                        subtract(number(1.0), multiply(argument.clone(), argument)),
+                    ),
+                ),
+                "acos" => divide(
+                    number(-1.0),
+                    function(
+                        "sqrt",
+                        subtract(number(1.0), multiply(argument.clone(), argument)),
+                    ),
+                ),
+                "atan" => divide(
+                    number(1.0),
+                    add(number(1.0), multiply(argument.clone(), argument)),
+                ),
+                "asinh" | "arsinh" => divide(
+                    number(1.0),
+                    function(
+                        "sqrt",
+                        add(multiply(argument.clone(), argument), number(1.0)),
+                    ),
+                ),
+                "acosh" | "arcosh" => divide(
+                    number(1.0),
+                    function(
+                        "sqrt",
+                        subtract(multiply(argument.clone(), argument), number(1.0)),
+                    ),
+                ),
+                "atanh" | "artanh" => divide(
+                    number(1.0),
+                    subtract(number(1.0), multiply(argument.clone(), argument)),
+                ),
+                "erf" => multiply(
+                    number(std::f64::consts::FRAC_2_SQRT_PI),
+                    function("exp", negate(multiply(argument.clone(), argument))),
+                ),
+                "abs" => function("sign", argument),
+                _ => {
+                    return Err(StruqtureError::NonDifferentiableFunction {
+                        function: name.clone(),
+                    })
+                }
+            };
+            multiply(outer, inner)
+        }
+    })
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::Number(number) => {
+                if number.is_sign_negative() {
+                    write!(f, "({})", number)
+                } else {
+                    write!(f, "{}", number)
+                }
+            }
+            Expression::Variable(name) => write!(f, "{}", name),
+            Expression::Negate(inner) => write!(f, "(-{})", inner),
+            Expression::Add(left, right) => write!(f, "({} + {})", left, right),
+            Expression::Subtract(left, right) => write!(f, "({} - {})", left, right),
+            Expression::Multiply(left, right) => write!(f, "{} * {}", left, right),
+            Expression::Divide(left, right) => write!(f, "{} / ({})", left, right),
+            Expression::Power(base, exponent) => write!(f, "({})^({})", base, exponent),
+            Expression::Function(name, arguments) => {
+                write!(f, "{}(", name)?;
+                for (index, argument) in arguments.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", argument)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integrators for linear equations of motion d/dt v = G v.
+//!
+//! The generator G is only accessed through its action on vectors, so that flattened density
+//! matrices can be evolved without constructing the matrix of the superoperator.
+
+use crate::iterative_solvers::{norm, scalar_product};
+use crate::StruqtureError;
+use ndarray::{s, Array2};
+use num_complex::Complex64;
+
+/// Runge-Kutta matrix of the Dormand-Prince method.
+///
+/// The generators are time-independent, so the nodes of the method are not needed.
+/// The last row contains the fifth-order weights, which makes the last stage of a step
+/// the first stage of the next one.
+const DORMAND_PRINCE_MATRIX: [[f64; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [0.2, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Differences between the fifth- and fourth-order weights of the Dormand-Prince method.
+const DORMAND_PRINCE_ERROR_WEIGHTS: [f64; 7] = [
+    35.0 / 384.0 - 5179.0 / 57600.0,
+    0.0,
+    500.0 / 1113.0 - 7571.0 / 16695.0,
+    125.0 / 192.0 - 393.0 / 640.0,
+    -2187.0 / 6784.0 + 92097.0 / 339200.0,
+    11.0 / 84.0 - 187.0 / 2100.0,
+    -1.0 / 40.0,
+];
+
+/// Checks that the tolerance of an integrator is a positive finite number.
+///
+/// A vanishing or NaN tolerance would make the step-size control of the integrators loop forever.
+fn check_tolerance(tolerance: f64) -> Result<(), StruqtureError> {
+    if tolerance > 0.0 && tolerance.is_finite() {
+        Ok(())
+    } else {
+        Err(StruqtureError::InvalidTolerance { tolerance })
+    }
+}
+
+/// Evolves a vector under a linear generator with the adaptive Dormand-Prince RK45 method.
+///
+/// The step size is adapted so that the local error of each step, estimated from the embedded
+/// fourth-order solution, stays below the tolerance relative to the magnitude of the entries.
+///
+/// # Arguments
+///
+/// * `generator` - The action of the generator G on a vector.
+/// * `initial` - The vector v at time zero.
+/// * `time` - The evolution time.
+/// * `tolerance` - The tolerance of the local error of each step.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Complex64>)` - The vector v at the evolution time.
+/// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+/// * `Err(StruqtureError)` - The generator could not be applied to the vector.
+pub(crate) fn runge_kutta_45_evolution<F>(
+    generator: F,
+    initial: &[Complex64],
+    time: f64,
+    tolerance: f64,
+) -> Result<Vec<Complex64>, StruqtureError>
+where
+    F: Fn(&[Complex64]) -> Result<Vec<Complex64>, StruqtureError>,
+{
+    check_tolerance(tolerance)?;
+    let mut state = initial.to_vec();
+    let minimal_step = f64::EPSILON * time.abs();
+    let mut elapsed = 0.0;
+    let mut step = time;
+    // The first stage of a step is the last stage of the previous accepted step
+    let mut derivative = generator(&state)?;
+    while (time - elapsed).abs() > minimal_step {
+        if step.abs() > (time - elapsed).abs() {
+            step = time - elapsed;
+        }
+        let mut stages: Vec<Vec<Complex64>> = Vec::with_capacity(7);
+        stages.push(derivative.clone());
+        for (stage, weights) in DORMAND_PRINCE_MATRIX.iter().enumerate().skip(1) {
+            let mut intermediate = state.clone();
+            for (previous, weight) in stages.iter().zip(weights.iter().take(stage)) {
+                if *weight != 0.0 {
+                    for (entry, increment) in intermediate.iter_mut().zip(previous.iter()) {
+                        *entry += increment * (weight * step);
+                    }
+                }
+            }
+            stages.push(generator(&intermediate)?);
+        }
+        // The fifth-order solution is the intermediate state of the last stage
+        let mut next = state.clone();
+        for (previous, weight) in stages.iter().zip(DORMAND_PRINCE_MATRIX[6].iter()) {
+            for (entry, increment) in next.iter_mut().zip(previous.iter()) {
+                *entry += increment * (weight * step);
+            }
+        }
+        let mut error = vec![Complex64::default(); state.len()];
+        for (stage, weight) in stages.iter().zip(DORMAND_PRINCE_ERROR_WEIGHTS.iter()) {
+            for (entry, increment) in error.iter_mut().zip(stage.iter()) {
+                *entry += increment * (weight * step);
+            }
+        }
+        let error_norm = error
+            .iter()
+            .zip(state.iter().zip(next.iter()))
+            .map(|(error, (old, new))| {
+                error.norm() / (tolerance * (1.0 + old.norm().max(new.norm())))
+            })
+            .fold(0.0, f64::max);
+        if error_norm <= 1.0 || step.abs() <= minimal_step {
+            elapsed += step;
+            state = next;
+            derivative = stages.pop().unwrap_or_default();
+        }
+        let factor = if error_norm > 0.0 {
+            (0.9 * error_norm.powf(-0.2)).clamp(0.2, 5.0)
+        } else {
+            5.0
+        };
+        step *= factor;
+    }
+    Ok(state)
+}
+
+/// Evolves a vector under a linear generator with Krylov subspace exponentiation.
+///
+/// In each step the exponential exp(t G) v is approximated by beta V exp(t H) e_1, where the columns
+/// of V span the Krylov subspace of v constructed with the Arnoldi iteration and H is the projection
+/// of the generator onto the subspace. The step is halved until the a-posteriori error estimate
+/// beta t h_(m+1,m) |(phi_1(t H))_(m,1)| is below the tolerance relative to the norm beta of v,
+/// scaled by the fraction of the evolution time covered by the step.
+/// When the Krylov subspace is invariant under the generator, the exponential is exact.
+///
+/// # Arguments
+///
+/// * `generator` - The action of the generator G on a vector.
+/// * `initial` - The vector v at time zero.
+/// * `time` - The evolution time.
+/// * `krylov_dimension` - The maximal dimension m of the Krylov subspace.
+/// * `tolerance` - The tolerance of the error relative to the norm of the vector.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Complex64>)` - The vector v at the evolution time.
+/// * `Err(StruqtureError::InvalidTolerance)` - The tolerance is not a positive finite number.
+/// * `Err(StruqtureError)` - The generator could not be applied to the vector.
+pub(crate) fn krylov_evolution<F>(
+    generator: F,
+    initial: &[Complex64],
+    time: f64,
+    krylov_dimension: usize,
+    tolerance: f64,
+) -> Result<Vec<Complex64>, StruqtureError>
+where
+    F: Fn(&[Complex64]) -> Result<Vec<Complex64>, StruqtureError>,
+{
+    check_tolerance(tolerance)?;
+    let krylov_dimension = krylov_dimension.max(1).min(initial.len().max(1));
+    let mut state = initial.to_vec();
+    let minimal_step = f64::EPSILON * time.abs();
+    let mut elapsed = 0.0;
+    let mut step = time;
+    while (time - elapsed).abs() > minimal_step {
+        let beta = norm(&state);
+        if beta == 0.0 {
+            break;
+        }
+        // Arnoldi iteration with modified Gram-Schmidt orthogonalization
+        let mut basis: Vec<Vec<Complex64>> = vec![state.iter().map(|entry| entry / beta).collect()];
+        let mut hessenberg: Array2<Complex64> =
+            Array2::zeros((krylov_dimension + 1, krylov_dimension));
+        let mut size = krylov_dimension;
+        let mut invariant = false;
+        for column in 0..krylov_dimension {
+            let mut vector = generator(&basis[column])?;
+            let initial_norm = norm(&vector);
+            for (row, basis_vector) in basis.iter().enumerate() {
+                let overlap = scalar_product(basis_vector, &vector);
+                hessenberg[[row, column]] = overlap;
+                for (entry, basis_entry) in vector.iter_mut().zip(basis_vector.iter()) {
+                    *entry -= overlap * basis_entry;
+                }
+            }
+            let next_norm = norm(&vector);
+            hessenberg[[column + 1, column]] = Complex64::new(next_norm, 0.0);
+            if next_norm <= 1e-12 * initial_norm || next_norm == 0.0 {
+                size = column + 1;
+                invariant = true;
+                break;
+            }
+            if column + 1 < krylov_dimension {
+                basis.push(vector.iter().map(|entry| entry / next_norm).collect());
+            }
+        }
+        // Augmenting the projection with the residual row h_(m+1,m) makes the entry (m+1, 1) of
+        // its exponential the a-posteriori error estimate t h_(m+1,m) (phi_1(t H))_(m,1)
+        let mut augmented: Array2<Complex64> = Array2::zeros((size + 1, size + 1));
+        augmented
+            .slice_mut(s![.., 0..size])
+            .assign(&hessenberg.slice(s![0..=size, 0..size]));
+
+        if step.abs() > (time - elapsed).abs() {
+            step = time - elapsed;
+        }
+        let exponential = loop {
+            let exponential = dense_exponential(&augmented.mapv(|entry| entry * step));
+            let error = if invariant {
+                0.0
+            } else {
+                beta * exponential[[size, 0]].norm()
+            };
+            if error <= tolerance * beta * (step / time).abs() || step.abs() <= minimal_step {
+                break exponential;
+            }
+            step *= 0.5;
+        };
+        state = vec![Complex64::default(); state.len()];
+        for (basis_vector, coefficient) in basis.iter().zip(exponential.column(0).iter()) {
+            for (entry, basis_entry) in state.iter_mut().zip(basis_vector.iter()) {
+                *entry += coefficient * beta * basis_entry;
+            }
+        }
+        elapsed += step;
+        step *= 2.0;
+    }
+    Ok(state)
+}
+
+/// Returns the exponential of a small dense matrix.
+///
+/// The exponential is computed with a Taylor series after scaling the matrix to a norm below 1/2,
+/// followed by repeated squaring.
+///
+/// # Arguments
+///
+/// * `matrix` - The square matrix.
+///
+/// # Returns
+///
+/// * `Array2<Complex64>` - The exponential of the matrix.
+pub(crate) fn dense_exponential(matrix: &Array2<Complex64>) -> Array2<Complex64> {
+    let dimension = matrix.nrows();
+    let matrix_norm = matrix
+        .rows()
+        .into_iter()
+        .map(|row| row.iter().map(|entry| entry.norm()).sum::<f64>())
+        .fold(0.0, f64::max);
+    let squarings = if matrix_norm > 0.5 {
+        (matrix_norm / 0.5).log2().ceil() as i32
+    } else {
+        0
+    };
+    let scaled = matrix.mapv(|entry| entry / 2.0_f64.powi(squarings));
+    let mut result: Array2<Complex64> = Array2::eye(dimension);
+    let mut term: Array2<Complex64> = Array2::eye(dimension);
+    for order in 1..=30 {
+        term = term.dot(&scaled).mapv(|entry| entry / order as f64);
+        result += &term;
+        let term_norm = term.iter().map(|entry| entry.norm()).fold(0.0, f64::max);
+        if term_norm <= f64::EPSILON {
+            break;
+        }
+    }
+    for _ in 0..squarings {
+        result = result.dot(&result);
+    }
+    result
+}
@@ -17,8 +17,8 @@ use serde_test::{assert_tokens, Configure, Token};
 use std::collections::BTreeMap;
 use std::str::FromStr;
 use struqture::bosons::{
-    BosonHamiltonianSystem, BosonLindbladNoiseSystem, BosonLindbladOpenSystem, BosonProduct,
-    HermitianBosonProduct,
+    BosonHamiltonianSystem, BosonLindbladNoiseSystem, BosonLindbladOpenSystem, BosonOperator,
+    BosonProduct, HermitianBosonProduct,
 };
 use struqture::prelude::*;
 use struqture::ModeIndex;
@@ -854,3 +854,46 @@ fn test_truncate() {
     let comparison_system2 = system.truncate(0.5);
     assert_eq!(test_system2, comparison_system2);
 }
+
+// Test the effective non-hermitian Hamiltonian of the BosonLindbladOpenSystem
+#[test]
+fn effective_hamiltonian() {
+    let mut slos = BosonLindbladOpenSystem::new(Some(2));
+    slos.system_mut()
+        .set(
+            HermitianBosonProduct::new([0], [1]).unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                BosonProduct::new([], [0]).unwrap(),
+                BosonProduct::new([], [0]).unwrap(),
+            ),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+
+    // The hermitian conjugate of the system term is added and the decay gives -i n_0
+    let mut expected = BosonOperator::new();
+    expected
+        .set(
+            BosonProduct::new([0], [1]).unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    expected
+        .set(
+            BosonProduct::new([1], [0]).unwrap(),
+            CalculatorComplex::new(1.0, -0.5),
+        )
+        .unwrap();
+    expected
+        .set(
+            BosonProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::new(0.0, -1.0),
+        )
+        .unwrap();
+    assert_eq!(slos.effective_hamiltonian(), expected);
+}
@@ -19,7 +19,9 @@ use std::collections::BTreeMap;
 use std::iter::{FromIterator, IntoIterator};
 use std::str::FromStr;
 use struqture::bosons::{BosonHamiltonian, BosonOperator, BosonProduct, HermitianBosonProduct};
-use struqture::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState};
+use struqture::{
+    ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError,
+};
 use test_case::test_case;
 
 // Test the new function of the BosonOperator
@@ -248,6 +250,66 @@ fn mul_so_so_same_index() {
     assert_eq!(so_0 * so_1, so_0_1);
 }
 
+// Test the commutator and anticommutator of BosonOperators against the explicit products
+#[test]
+fn commutator_anticommutator() {
+    let mut so_0 = BosonOperator::new();
+    so_0.add_operator_product(
+        BosonProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    so_0.add_operator_product(
+        BosonProduct::new([2], []).unwrap(),
+        CalculatorComplex::new(0.0, 0.5),
+    )
+    .unwrap();
+    let mut so_1 = BosonOperator::new();
+    so_1.add_operator_product(
+        BosonProduct::new([], [3]).unwrap(),
+        CalculatorComplex::from(1.5),
+    )
+    .unwrap();
+    so_1.add_operator_product(
+        BosonProduct::new([1], [0]).unwrap(),
+        CalculatorComplex::from(0.25),
+    )
+    .unwrap();
+    so_1.add_operator_product(
+        BosonProduct::new([], [2]).unwrap(),
+        CalculatorComplex::from(-1.0),
+    )
+    .unwrap();
+
+    let forward = so_0.clone() * so_1.clone();
+    let backward = so_1.clone() * so_0.clone();
+    assert_eq!(so_0.commutator(&so_1), forward.clone() - backward.clone());
+    assert_eq!(so_0.anticommutator(&so_1), forward + backward);
+
+    let mut creator = BosonOperator::new();
+    creator
+        .add_operator_product(
+            BosonProduct::new([0], []).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut annihilator = BosonOperator::new();
+    annihilator
+        .add_operator_product(
+            BosonProduct::new([], [0]).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut identity = BosonOperator::new();
+    identity
+        .add_operator_product(
+            BosonProduct::new([], []).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    assert_eq!(annihilator.commutator(&creator), identity);
+}
+
 // Test the multiplication: BosonOperator * Calculatorcomplex
 #[test]
 fn mul_so_cc() {
@@ -512,3 +574,34 @@ fn serde_compact() {
         ],
     );
 }
+
+// Test the normal_order function of the BosonOperator
+#[test_case("a0 c0", &[("I", 1.0), ("c0a0", 1.0)]; "contraction")]
+#[test_case("a1 c0 a0 c2", &[("c0c2a0a1", 1.0)]; "commuting")]
+#[test_case("a0 a0 c0 c0", &[("I", 2.0), ("c0a0", 4.0), ("c0c0a0a0", 1.0)]; "double_contraction")]
+#[test_case("c0 c0", &[("c0c0", 1.0)]; "double_creator")]
+#[test_case("I", &[("I", 1.0)]; "identity")]
+#[test_case("", &[("I", 1.0)]; "empty")]
+fn normal_order(operators: &str, expected_terms: &[(&str, f64)]) {
+    let mut expected = BosonOperator::new();
+    for (product, value) in expected_terms {
+        expected
+            .add_operator_product(
+                BosonProduct::from_str(product).unwrap(),
+                CalculatorComplex::from(*value),
+            )
+            .unwrap();
+    }
+    assert_eq!(BosonOperator::normal_order(operators).unwrap(), expected);
+}
+
+// Test the normal_order function of the BosonOperator with invalid input
+#[test_case("b0"; "wrong_operator")]
+#[test_case("c0 a"; "missing_index")]
+#[test_case("c0 a-1"; "negative_index")]
+fn normal_order_error(operators: &str) {
+    assert!(matches!(
+        BosonOperator::normal_order(operators),
+        Err(StruqtureError::FromStringFailed { .. })
+    ));
+}
@@ -0,0 +1,453 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the truncated Fock-space sparse matrices of bosonic objects
+
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorComplex;
+use std::collections::HashMap;
+use struqture::bosons::{
+    BosonCutoff, BosonHamiltonianSystem, BosonLindbladNoiseSystem, BosonLindbladOpenSystem,
+    BosonOperator, BosonProduct, BosonSystem, HermitianBosonProduct,
+};
+use struqture::prelude::*;
+use struqture::{CooSparseMatrix, StruqtureError, MAXIMAL_DENSE_DIMENSION};
+
+// Helper function to collect a COO matrix into a map from (row, column) to value
+fn coo_to_map(matrix: CooSparseMatrix) -> HashMap<(usize, usize), Complex64> {
+    let (values, (rows, columns)) = matrix;
+    let mut map: HashMap<(usize, usize), Complex64> = HashMap::new();
+    for ((value, row), column) in values.into_iter().zip(rows).zip(columns) {
+        *map.entry((row, column)).or_insert(Complex64::new(0.0, 0.0)) += value;
+    }
+    map.retain(|_, value| value.norm() > 1e-12);
+    map
+}
+
+// Test the sqrt(n) matrix elements of an annihilator
+#[test]
+fn sparse_matrix_annihilator() {
+    let mut bo = BosonOperator::new();
+    bo.add_operator_product(
+        BosonProduct::new([], [0]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+
+    let matrix = coo_to_map(bo.sparse_matrix_coo(&BosonCutoff::Global(3)).unwrap());
+    let expected: HashMap<(usize, usize), Complex64> = [
+        ((0, 1), Complex64::new(1.0, 0.0)),
+        ((1, 2), Complex64::new(2.0_f64.sqrt(), 0.0)),
+        ((2, 3), Complex64::new(3.0_f64.sqrt(), 0.0)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(matrix, expected);
+}
+
+// Test the number operators with a per-mode cutoff
+#[test]
+fn sparse_matrix_per_mode_cutoff() {
+    let mut bhs = BosonHamiltonianSystem::new(Some(2));
+    bhs.add_operator_product(
+        HermitianBosonProduct::new([0], [0]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    bhs.add_operator_product(
+        HermitianBosonProduct::new([1], [1]).unwrap(),
+        CalculatorComplex::from(0.5),
+    )
+    .unwrap();
+
+    let matrix = coo_to_map(
+        bhs.sparse_matrix_coo(&BosonCutoff::PerMode(vec![2, 1]))
+            .unwrap(),
+    );
+    let mut expected: HashMap<(usize, usize), Complex64> = HashMap::new();
+    for n1 in 0..2 {
+        for n0 in 0..3 {
+            let index = n0 + 3 * n1;
+            let value = n0 as f64 + 0.5 * n1 as f64;
+            if value != 0.0 {
+                expected.insert((index, index), Complex64::new(value, 0.0));
+            }
+        }
+    }
+    assert_eq!(matrix, expected);
+}
+
+// Test that the matrix of a hopping Hamiltonian is hermitian and truncated at the cutoff
+#[test]
+fn sparse_matrix_hopping_hermitian() {
+    let mut bhs = BosonHamiltonianSystem::new(Some(2));
+    bhs.add_operator_product(
+        HermitianBosonProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+
+    let matrix = coo_to_map(bhs.sparse_matrix_coo(&BosonCutoff::Global(2)).unwrap());
+    for ((row, column), value) in matrix.iter() {
+        assert_eq!(matrix.get(&(*column, *row)), Some(&value.conj()));
+    }
+    // b_0^† b_1 |n_0=1, n_1=1> = sqrt(2) |n_0=2, n_1=0>
+    assert_eq!(
+        matrix.get(&(2, 4)),
+        Some(&(Complex64::new(1.0, 0.5) * 2.0_f64.sqrt()))
+    );
+    // b_0^† b_1 |n_0=1, n_1=1> leaves the truncated space with a cutoff of one
+    let truncated = coo_to_map(bhs.sparse_matrix_coo(&BosonCutoff::Global(1)).unwrap());
+    let expected: HashMap<(usize, usize), Complex64> = [
+        ((1, 2), Complex64::new(1.0, 0.5)),
+        ((2, 1), Complex64::new(1.0, -0.5)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(truncated, expected);
+    assert_eq!(
+        bhs.sparse_matrix_entries_on_row(2, &[2, 2]).unwrap(),
+        [(4, Complex64::new(1.0, 0.5) * 2.0_f64.sqrt())]
+            .into_iter()
+            .collect::<HashMap<usize, Complex64>>()
+    );
+}
+
+// Test that the CSC matrix constructed directly from the rows has the same entries as the COO matrix
+#[test]
+fn sparse_matrix_csc() {
+    let mut bhs = BosonHamiltonianSystem::new(Some(2));
+    bhs.add_operator_product(
+        HermitianBosonProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    bhs.add_operator_product(
+        HermitianBosonProduct::new([1], [1]).unwrap(),
+        CalculatorComplex::from(0.3),
+    )
+    .unwrap();
+    let cutoff = BosonCutoff::PerMode(vec![2, 1]);
+
+    let (values, rows, column_pointers) = bhs.sparse_matrix_csc(&cutoff).unwrap();
+    assert_eq!(column_pointers.len(), 7);
+    let mut csc: Vec<(usize, usize, Complex64)> = Vec::new();
+    for column in 0..6 {
+        let column_rows = &rows[column_pointers[column]..column_pointers[column + 1]];
+        assert!(column_rows.windows(2).all(|pair| pair[0] < pair[1]));
+        for index in column_pointers[column]..column_pointers[column + 1] {
+            csc.push((rows[index], column, values[index]));
+        }
+    }
+    let (values, (rows, columns)) = bhs.sparse_matrix_coo(&cutoff).unwrap();
+    let mut coo: Vec<(usize, usize, Complex64)> = rows
+        .into_iter()
+        .zip(columns)
+        .zip(values)
+        .map(|((row, column), value)| (row, column, value))
+        .collect();
+    csc.sort_by_key(|(row, column, _)| (*row, *column));
+    coo.sort_by_key(|(row, column, _)| (*row, *column));
+    assert_eq!(csc, coo);
+}
+
+// Test the superoperator of an open system with decay of a single mode
+#[test]
+fn sparse_matrix_superoperator_open_system() {
+    let mut noise = BosonLindbladNoiseSystem::new(Some(1));
+    let annihilator = BosonProduct::new([], [0]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut system = BosonHamiltonianSystem::new(Some(1));
+    system
+        .add_operator_product(
+            HermitianBosonProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+    let open_system = BosonLindbladOpenSystem::group(system, noise).unwrap();
+
+    let matrix = coo_to_map(
+        open_system
+            .sparse_matrix_superoperator_coo(&BosonCutoff::Global(1))
+            .unwrap(),
+    );
+    let expected: HashMap<(usize, usize), Complex64> = [
+        ((0, 3), Complex64::new(1.0, 0.0)),
+        ((1, 1), Complex64::new(-0.5, 2.0)),
+        ((2, 2), Complex64::new(-0.5, -2.0)),
+        ((3, 3), Complex64::new(-1.0, 0.0)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(matrix, expected);
+}
+
+// Test the failure of the sparse matrix construction with a per-mode cutoff covering too few modes
+#[test]
+fn sparse_matrix_number_modes_exceeded() {
+    let mut bs = BosonSystem::new(None);
+    bs.add_operator_product(
+        BosonProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+
+    assert_eq!(
+        bs.sparse_matrix_coo(&BosonCutoff::PerMode(vec![2])),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert_eq!(
+        bs.sparse_matrix_superoperator_coo(&BosonCutoff::PerMode(vec![2])),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert!(bs.sparse_matrix_coo(&BosonCutoff::Global(2)).is_ok());
+    // Rows constructed with explicit cutoffs do not silently drop terms on modes without a cutoff
+    assert_eq!(
+        bs.sparse_matrix_entries_on_row(0, &[2]),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert_eq!(
+        bs.sparse_matrix_superoperator_entries_on_row(0, &[2]),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+}
+
+// Test the evolve_density_matrix functions against the analytic decay and precession of a single mode
+#[test]
+fn evolve_density_matrix() {
+    let mut noise = BosonLindbladNoiseSystem::new(Some(1));
+    let annihilator = BosonProduct::new([], [0]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut system = BosonHamiltonianSystem::new(Some(1));
+    system
+        .add_operator_product(
+            HermitianBosonProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+    let open_system = BosonLindbladOpenSystem::group(system, noise).unwrap();
+    let cutoff = BosonCutoff::Global(1);
+
+    let density_matrix = vec![Complex64::new(0.5, 0.0); 4];
+    let time: f64 = 0.8;
+    let decay = (-time).exp();
+    let coherence = Complex64::new(-0.5, 2.0) * time;
+    let expected = [
+        Complex64::new(1.0 - 0.5 * decay, 0.0),
+        0.5 * coherence.exp(),
+        0.5 * coherence.conj().exp(),
+        Complex64::new(0.5 * decay, 0.0),
+    ];
+
+    let runge_kutta = open_system
+        .evolve_density_matrix(&density_matrix, &cutoff, time, 1e-10)
+        .unwrap();
+    let krylov = open_system
+        .evolve_density_matrix_krylov(&density_matrix, &cutoff, time, 4, 1e-10)
+        .unwrap();
+    for index in 0..4 {
+        assert!((runge_kutta[index] - expected[index]).norm() < 1e-8);
+        assert!((krylov[index] - expected[index]).norm() < 1e-8);
+    }
+
+    assert_eq!(
+        open_system.evolve_density_matrix(&density_matrix, &BosonCutoff::Global(2), time, 1e-10),
+        Err(StruqtureError::MissmatchedDensityMatrixLength {
+            length: 4,
+            dimension: 3
+        })
+    );
+    assert_eq!(
+        open_system.evolve_density_matrix_krylov(
+            &density_matrix,
+            &BosonCutoff::PerMode(vec![]),
+            time,
+            4,
+            1e-10
+        ),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+
+    for tolerance in [0.0, -1e-10, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            open_system.evolve_density_matrix(&density_matrix, &cutoff, time, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+        assert!(matches!(
+            open_system.evolve_density_matrix_krylov(&density_matrix, &cutoff, time, 4, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+    }
+}
+
+// Test that the dense matrices agree with the sparse matrices and fail for too large truncated Fock spaces
+#[test]
+fn dense_matrix() {
+    let mut bo = BosonOperator::new();
+    bo.add_operator_product(
+        BosonProduct::new([1], [0]).unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    bo.add_operator_product(
+        BosonProduct::new([0], [0]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    let cutoff = BosonCutoff::PerMode(vec![2, 1]);
+
+    let dense = bo.dense_matrix(&cutoff).unwrap();
+    assert_eq!(dense.dim(), (6, 6));
+    let sparse = coo_to_map(bo.sparse_matrix_coo(&cutoff).unwrap());
+    for ((row, column), value) in dense.indexed_iter() {
+        assert_eq!(
+            *value,
+            sparse
+                .get(&(row, column))
+                .copied()
+                .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+        );
+    }
+    let dense_superoperator = bo.dense_superoperator(&cutoff).unwrap();
+    let sparse_superoperator = coo_to_map(bo.sparse_matrix_superoperator_coo(&cutoff).unwrap());
+    for ((row, column), value) in dense_superoperator.indexed_iter() {
+        assert_eq!(
+            *value,
+            sparse_superoperator
+                .get(&(row, column))
+                .copied()
+                .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+        );
+    }
+
+    assert_eq!(
+        bo.dense_matrix(&BosonCutoff::PerMode(vec![2])),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert_eq!(
+        bo.dense_superoperator(&BosonCutoff::Global(10)),
+        Err(StruqtureError::DimensionTooLarge {
+            dimension: 14641,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+    assert_eq!(
+        bo.dense_matrix(&BosonCutoff::PerMode(vec![usize::MAX, 1])),
+        Err(StruqtureError::HilbertSpaceTooLarge {
+            number_spins: 0,
+            number_modes: 2,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+}
+
+// Test that the parallel construction returns the same matrices as the serial construction
+#[cfg(feature = "rayon")]
+#[test]
+fn sparse_matrix_coo_parallel() {
+    let mut system = BosonHamiltonianSystem::new(Some(5));
+    system
+        .add_operator_product(
+            HermitianBosonProduct::new([0], [4]).unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    system
+        .add_operator_product(
+            HermitianBosonProduct::new([2, 2], [2, 2]).unwrap(),
+            CalculatorComplex::from(0.7),
+        )
+        .unwrap();
+    let mut noise = BosonLindbladNoiseSystem::new(Some(5));
+    for (mode, rate) in [(1, 0.3), (3, 0.2)] {
+        let annihilator = BosonProduct::new([], [mode]).unwrap();
+        noise
+            .add_operator_product(
+                (annihilator.clone(), annihilator),
+                CalculatorComplex::from(rate),
+            )
+            .unwrap();
+    }
+    let open_system = BosonLindbladOpenSystem::group(system.clone(), noise).unwrap();
+    let cutoff = BosonCutoff::Global(2);
+
+    assert_eq!(
+        system.sparse_matrix_coo_parallel(&cutoff),
+        system.sparse_matrix_coo(&cutoff)
+    );
+    // The 3^10 rows of the superoperators span several chunks of the parallel construction
+    assert_eq!(
+        system.sparse_matrix_superoperator_coo_parallel(&cutoff),
+        system.sparse_matrix_superoperator_coo(&cutoff)
+    );
+    assert_eq!(
+        open_system.sparse_matrix_superoperator_coo_parallel(&cutoff),
+        open_system.sparse_matrix_superoperator_coo(&cutoff)
+    );
+}
+
+// Test the steady_state function of a driven and damped mode with the dense superoperator
+#[test]
+fn steady_state() {
+    let mut noise = BosonLindbladNoiseSystem::new(Some(1));
+    let annihilator = BosonProduct::new([], [0]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut system = BosonHamiltonianSystem::new(Some(1));
+    system
+        .add_operator_product(
+            HermitianBosonProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+    system
+        .add_operator_product(
+            HermitianBosonProduct::new([], [0]).unwrap(),
+            CalculatorComplex::from(0.3),
+        )
+        .unwrap();
+    let open_system = BosonLindbladOpenSystem::group(system, noise).unwrap();
+    let cutoff = BosonCutoff::Global(4);
+
+    let steady_state = open_system.steady_state(&cutoff, 1e-12, 500).unwrap();
+    assert_eq!(steady_state.dim(), (5, 5));
+    let trace: Complex64 = (0..5).map(|index| steady_state[[index, index]]).sum();
+    assert!((trace - Complex64::new(1.0, 0.0)).norm() < 1e-10);
+    // The steady state is annihilated by the superoperator
+    let superoperator = open_system.dense_superoperator(&cutoff).unwrap();
+    let flattened = steady_state.iter().copied().collect::<Vec<Complex64>>();
+    for row in 0..25 {
+        let entry: Complex64 = (0..25)
+            .map(|column| superoperator[[row, column]] * flattened[column])
+            .sum();
+        assert!(entry.norm() < 1e-9);
+    }
+
+    assert_eq!(
+        open_system.steady_state(&BosonCutoff::PerMode(vec![]), 1e-12, 500),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+}
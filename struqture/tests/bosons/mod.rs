@@ -28,3 +28,5 @@ mod bosonic_open_system;
 pub use bosonic_open_system::*;
 mod bosonic_hamiltonian_system;
 pub use bosonic_hamiltonian_system::*;
+mod bosonic_sparse_matrices;
+pub use bosonic_sparse_matrices::*;
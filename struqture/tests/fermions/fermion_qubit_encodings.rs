@@ -0,0 +1,220 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for public API of the fermion to qubit encodings
+
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use std::collections::HashMap;
+use struqture::fermions::{
+    BravyiKitaevEncoding, FermionHamiltonian, FermionHamiltonianSystem, FermionOperator,
+    FermionProduct, FermionQubitEncoding, FermionSystem, HermitianFermionProduct,
+    JordanWignerEncoding, ParityEncoding,
+};
+use struqture::mappings::JordanWignerFermionToSpin;
+use struqture::prelude::*;
+use struqture::spins::{PauliProduct, SpinHamiltonian, SpinOperator};
+use struqture::StruqtureError;
+use test_case::test_case;
+
+// Test that the JordanWignerEncoding agrees with the Jordan-Wigner mapping
+#[test]
+fn jordan_wigner_encoding_agrees_with_mapping() {
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([0, 3], [1]).unwrap(),
+        CalculatorComplex::new(1.0, -0.5),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([2], [2]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+
+    let encoded = JordanWignerEncoding
+        .encode_fermion_operator(&fo, 4, None)
+        .unwrap();
+    assert_eq!(encoded, fo.jordan_wigner());
+}
+
+// Test the number operators in the different encodings
+#[test_case(&JordanWignerEncoding, 3, PauliProduct::new().z(3); "jordan_wigner")]
+#[test_case(&ParityEncoding, 3, PauliProduct::new().z(2).z(3); "parity")]
+#[test_case(&ParityEncoding, 0, PauliProduct::new().z(0); "parity_first_mode")]
+#[test_case(&BravyiKitaevEncoding, 3, PauliProduct::new().z(1).z(2).z(3); "bravyi_kitaev")]
+#[test_case(&BravyiKitaevEncoding, 2, PauliProduct::new().z(2); "bravyi_kitaev_even")]
+fn number_operator(encoding: &dyn FermionQubitEncoding, mode: usize, pauli_z: PauliProduct) {
+    let encoded = encoding
+        .encode_fermion_product(&FermionProduct::new([mode], [mode]).unwrap(), 4)
+        .unwrap();
+
+    let mut expected = SpinOperator::new();
+    expected
+        .add_operator_product(PauliProduct::new(), CalculatorComplex::from(0.5))
+        .unwrap();
+    expected
+        .add_operator_product(pauli_z, CalculatorComplex::from(-0.5))
+        .unwrap();
+    assert_eq!(encoded, expected);
+}
+
+// Test that the encoded operators fulfill the canonical anticommutation relations
+#[test_case(&JordanWignerEncoding, 5; "jordan_wigner")]
+#[test_case(&ParityEncoding, 5; "parity")]
+#[test_case(&BravyiKitaevEncoding, 5; "bravyi_kitaev")]
+#[test_case(&BravyiKitaevEncoding, 8; "bravyi_kitaev_power_of_two")]
+fn anticommutation_relations(encoding: &dyn FermionQubitEncoding, number_modes: usize) {
+    let mut identity = SpinOperator::new();
+    identity
+        .add_operator_product(PauliProduct::new(), CalculatorComplex::from(1.0))
+        .unwrap();
+    for i in 0..number_modes {
+        let annihilator_i = encoding
+            .encode_fermion_product(&FermionProduct::new([], [i]).unwrap(), number_modes)
+            .unwrap();
+        for j in 0..number_modes {
+            let creator_j = encoding
+                .encode_fermion_product(&FermionProduct::new([j], []).unwrap(), number_modes)
+                .unwrap();
+            let annihilator_j = encoding
+                .encode_fermion_product(&FermionProduct::new([], [j]).unwrap(), number_modes)
+                .unwrap();
+
+            let mixed = annihilator_i.clone() * creator_j.clone()
+                + creator_j.clone() * annihilator_i.clone();
+            if i == j {
+                assert_eq!(mixed, identity);
+            } else {
+                assert_eq!(mixed, SpinOperator::new());
+            }
+            let annihilators = annihilator_i.clone() * annihilator_j.clone()
+                + annihilator_j * annihilator_i.clone();
+            assert_eq!(annihilators, SpinOperator::new());
+        }
+    }
+}
+
+// Test that the Bravyi-Kitaev encoding has a logarithmic Pauli weight
+#[test]
+fn bravyi_kitaev_pauli_weight() {
+    let number_modes = 16;
+    for mode in 0..number_modes {
+        let (gamma_even, gamma_odd) = BravyiKitaevEncoding.majorana_operators(mode, number_modes);
+        assert!(gamma_even.len() <= 5);
+        assert!(gamma_odd.len() <= 5);
+    }
+    let (gamma_even, _) = JordanWignerEncoding.majorana_operators(15, number_modes);
+    assert_eq!(gamma_even.len(), 16);
+}
+
+// Test the encoding of a FermionHamiltonian with a mode ordering
+#[test]
+fn encode_hamiltonian_mode_ordering() {
+    let mut fh = FermionHamiltonian::new();
+    fh.add_operator_product(
+        HermitianFermionProduct::new([0], [2]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    let mode_ordering: HashMap<usize, usize> = [(0, 1), (1, 0)].into_iter().collect();
+
+    let mut fh_reordered = FermionHamiltonian::new();
+    fh_reordered
+        .add_operator_product(
+            HermitianFermionProduct::new([1], [2]).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+
+    let mut expected = SpinHamiltonian::new();
+    expected
+        .add_operator_product(PauliProduct::new().x(1).x(2), CalculatorFloat::from(0.5))
+        .unwrap();
+    expected
+        .add_operator_product(PauliProduct::new().y(1).y(2), CalculatorFloat::from(0.5))
+        .unwrap();
+
+    let encoded = JordanWignerEncoding
+        .encode_fermion_hamiltonian(&fh, 3, Some(&mode_ordering))
+        .unwrap();
+    assert_eq!(encoded, expected);
+    assert_eq!(
+        encoded,
+        JordanWignerEncoding
+            .encode_fermion_hamiltonian(&fh_reordered, 3, None)
+            .unwrap()
+    );
+}
+
+// Test the failure of the encoding for invalid inputs
+#[test]
+fn encode_errors() {
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([0], [3]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ParityEncoding.encode_fermion_operator(&fo, 3, None),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+
+    let not_closed: HashMap<usize, usize> = [(0, 1)].into_iter().collect();
+    assert_eq!(
+        ParityEncoding.encode_fermion_operator(&fo, 4, Some(&not_closed)),
+        Err(StruqtureError::RemappingFailed { key: 1 })
+    );
+
+    let not_injective: HashMap<usize, usize> = [(0, 1), (1, 1)].into_iter().collect();
+    assert_eq!(
+        ParityEncoding.encode_fermion_operator(&fo, 4, Some(&not_injective)),
+        Err(StruqtureError::RemappingFailed { key: 1 })
+    );
+}
+
+// Test that the number of spins of encoded systems is the number of modes
+#[test]
+fn encode_systems() {
+    let mut fhs = FermionHamiltonianSystem::new(Some(4));
+    fhs.add_operator_product(
+        HermitianFermionProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    let shs = BravyiKitaevEncoding
+        .encode_fermion_hamiltonian_system(&fhs, None)
+        .unwrap();
+    assert_eq!(shs.number_spins(), 4);
+    assert_eq!(
+        shs.operator(),
+        &BravyiKitaevEncoding
+            .encode_fermion_hamiltonian(fhs.hamiltonian(), 4, None)
+            .unwrap()
+    );
+
+    let mut fs = FermionSystem::new(Some(4));
+    fs.add_operator_product(
+        FermionProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    let ss = ParityEncoding.encode_fermion_system(&fs, None).unwrap();
+    assert_eq!(ss.number_spins(), 4);
+    assert_eq!(
+        ss.operator(),
+        &ParityEncoding
+            .encode_fermion_operator(fs.operator(), 4, None)
+            .unwrap()
+    );
+}
@@ -13,7 +13,7 @@
 //! Integration test for public API of FermionHamiltonian
 
 use bincode::{deserialize, serialize};
-use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use qoqo_calculator::{Calculator, CalculatorComplex, CalculatorFloat};
 use serde_test::{assert_tokens, Configure, Token};
 use std::collections::BTreeMap;
 use std::iter::{FromIterator, IntoIterator};
@@ -563,3 +563,32 @@ fn serde_compact() {
         ],
     );
 }
+
+// Test the symbolic derivative of the FermionHamiltonian
+#[test]
+fn derivative() {
+    let hopping = HermitianFermionProduct::new([0], [1]).unwrap();
+    let number = HermitianFermionProduct::new([1], [1]).unwrap();
+    let mut fh = FermionHamiltonian::new();
+    fh.set(hopping.clone(), CalculatorComplex::new("theta^2", "-theta"))
+        .unwrap();
+    fh.set(number.clone(), CalculatorComplex::new("mu", 0.0))
+        .unwrap();
+
+    let derivative = fh.derivative("theta").unwrap();
+    assert_eq!(derivative.len(), 1);
+    assert_eq!(derivative.get(&hopping).im, CalculatorFloat::from(-1.0));
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", 1.5);
+    assert_eq!(
+        derivative
+            .substitute_parameters(&calculator)
+            .unwrap()
+            .get(&hopping),
+        &CalculatorComplex::new(3.0, -1.0)
+    );
+    assert_eq!(
+        fh.derivative("mu").unwrap().get(&number),
+        &CalculatorComplex::from(1.0)
+    );
+}
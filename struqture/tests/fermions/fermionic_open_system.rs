@@ -18,7 +18,7 @@ use std::collections::BTreeMap;
 use std::str::FromStr;
 use struqture::fermions::{
     FermionHamiltonianSystem, FermionLindbladNoiseSystem, FermionLindbladOpenSystem,
-    FermionProduct, HermitianFermionProduct,
+    FermionOperator, FermionProduct, HermitianFermionProduct,
 };
 use struqture::prelude::*;
 use struqture::ModeIndex;
@@ -861,3 +861,46 @@ fn test_truncate() {
     let comparison_system2 = system.truncate(0.5);
     assert_eq!(test_system2, comparison_system2);
 }
+
+// Test the effective non-hermitian Hamiltonian of the FermionLindbladOpenSystem
+#[test]
+fn effective_hamiltonian() {
+    let mut slos = FermionLindbladOpenSystem::new(Some(2));
+    slos.system_mut()
+        .set(
+            HermitianFermionProduct::new([0], [1]).unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                FermionProduct::new([], [0]).unwrap(),
+                FermionProduct::new([], [0]).unwrap(),
+            ),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+
+    // The hermitian conjugate of the system term is added and the decay gives -i n_0
+    let mut expected = FermionOperator::new();
+    expected
+        .set(
+            FermionProduct::new([0], [1]).unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    expected
+        .set(
+            FermionProduct::new([1], [0]).unwrap(),
+            CalculatorComplex::new(1.0, -0.5),
+        )
+        .unwrap();
+    expected
+        .set(
+            FermionProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::new(0.0, -1.0),
+        )
+        .unwrap();
+    assert_eq!(slos.effective_hamiltonian(), expected);
+}
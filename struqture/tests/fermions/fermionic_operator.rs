@@ -21,7 +21,9 @@ use std::str::FromStr;
 use struqture::fermions::{
     FermionHamiltonian, FermionOperator, FermionProduct, HermitianFermionProduct,
 };
-use struqture::{ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState};
+use struqture::{
+    ModeIndex, OperateOnDensityMatrix, OperateOnModes, OperateOnState, StruqtureError,
+};
 use test_case::test_case;
 
 // Test the new function of the FermionOperator
@@ -254,6 +256,77 @@ fn mul_so_so_same_index() {
     assert_eq!(so_0 * so_1, so_0_1);
 }
 
+// Test the commutator and anticommutator of FermionOperators against the explicit products
+#[test]
+fn commutator_anticommutator() {
+    let mut so_0 = FermionOperator::new();
+    so_0.add_operator_product(
+        FermionProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    so_0.add_operator_product(
+        FermionProduct::new([2], []).unwrap(),
+        CalculatorComplex::new(0.0, 0.5),
+    )
+    .unwrap();
+    let mut so_1 = FermionOperator::new();
+    so_1.add_operator_product(
+        FermionProduct::new([], [3]).unwrap(),
+        CalculatorComplex::from(1.5),
+    )
+    .unwrap();
+    so_1.add_operator_product(
+        FermionProduct::new([1], [0]).unwrap(),
+        CalculatorComplex::from(0.25),
+    )
+    .unwrap();
+    so_1.add_operator_product(
+        FermionProduct::new([2], [3]).unwrap(),
+        CalculatorComplex::from(-1.0),
+    )
+    .unwrap();
+
+    let forward = so_0.clone() * so_1.clone();
+    let backward = so_1.clone() * so_0.clone();
+    assert_eq!(so_0.commutator(&so_1), forward.clone() - backward.clone());
+    assert_eq!(so_0.anticommutator(&so_1), forward + backward);
+
+    let mut creator = FermionOperator::new();
+    creator
+        .add_operator_product(
+            FermionProduct::new([0], []).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut annihilator = FermionOperator::new();
+    annihilator
+        .add_operator_product(
+            FermionProduct::new([], [0]).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut identity = FermionOperator::new();
+    identity
+        .add_operator_product(
+            FermionProduct::new([], []).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    assert_eq!(creator.anticommutator(&annihilator), identity);
+    let mut other_annihilator = FermionOperator::new();
+    other_annihilator
+        .add_operator_product(
+            FermionProduct::new([], [1]).unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    assert_eq!(
+        creator.anticommutator(&other_annihilator),
+        FermionOperator::new()
+    );
+}
+
 // Test the multiplication: FermionOperator * Calculatorcomplex
 #[test]
 fn mul_so_cc() {
@@ -519,3 +592,34 @@ fn serde_compact() {
         ],
     );
 }
+
+// Test the normal_order function of the FermionOperator
+#[test_case("a0 c0", &[("I", 1.0), ("c0a0", -1.0)]; "contraction")]
+#[test_case("a1 c0 a0 c2", &[("c0c2a0a1", 1.0)]; "anticommuting")]
+#[test_case("a1c0a0c1", &[("c0a0", 1.0), ("c0c1a0a1", 1.0)]; "two_contractions")]
+#[test_case("c0 c0", &[]; "pauli_exclusion")]
+#[test_case("I", &[("I", 1.0)]; "identity")]
+#[test_case("", &[("I", 1.0)]; "empty")]
+fn normal_order(operators: &str, expected_terms: &[(&str, f64)]) {
+    let mut expected = FermionOperator::new();
+    for (product, value) in expected_terms {
+        expected
+            .add_operator_product(
+                FermionProduct::from_str(product).unwrap(),
+                CalculatorComplex::from(*value),
+            )
+            .unwrap();
+    }
+    assert_eq!(FermionOperator::normal_order(operators).unwrap(), expected);
+}
+
+// Test the normal_order function of the FermionOperator with invalid input
+#[test_case("b0"; "wrong_operator")]
+#[test_case("c0 a"; "missing_index")]
+#[test_case("c0 a-1"; "negative_index")]
+fn normal_order_error(operators: &str) {
+    assert!(matches!(
+        FermionOperator::normal_order(operators),
+        Err(StruqtureError::FromStringFailed { .. })
+    ));
+}
@@ -0,0 +1,466 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the Fock-space sparse matrices of fermionic objects
+
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorComplex;
+use std::collections::HashMap;
+use struqture::fermions::{
+    FermionHamiltonianSystem, FermionLindbladNoiseSystem, FermionLindbladOpenSystem,
+    FermionOperator, FermionProduct, FermionSystem, HermitianFermionProduct,
+};
+use struqture::mappings::JordanWignerFermionToSpin;
+use struqture::prelude::*;
+use struqture::{CooSparseMatrix, StruqtureError, MAXIMAL_DENSE_DIMENSION};
+
+// Helper function to collect a COO matrix into a map from (row, column) to value
+fn coo_to_map(matrix: CooSparseMatrix) -> HashMap<(usize, usize), Complex64> {
+    let (values, (rows, columns)) = matrix;
+    let mut map: HashMap<(usize, usize), Complex64> = HashMap::new();
+    for ((value, row), column) in values.into_iter().zip(rows).zip(columns) {
+        *map.entry((row, column)).or_insert(Complex64::new(0.0, 0.0)) += value;
+    }
+    map.retain(|_, value| value.norm() > 1e-12);
+    map
+}
+
+// Helper function to compare two COO matrices up to numerical precision
+fn assert_coo_close(left: CooSparseMatrix, right: CooSparseMatrix) {
+    let left = coo_to_map(left);
+    let right = coo_to_map(right);
+    assert_eq!(left.len(), right.len());
+    for (key, value) in left.iter() {
+        let other = right.get(key).copied().unwrap_or_default();
+        assert!((value - other).norm() < 1e-12);
+    }
+}
+
+// Test the fermionic sign of a hopping term passing an occupied mode
+#[test]
+fn sparse_matrix_fermionic_sign() {
+    let mut fs = FermionSystem::new(Some(3));
+    fs.add_operator_product(
+        FermionProduct::new([2], [0]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+
+    let matrix = coo_to_map(fs.sparse_matrix_coo(None).unwrap());
+    let expected: HashMap<(usize, usize), Complex64> = [
+        ((4, 1), Complex64::new(1.0, 0.0)),
+        ((6, 3), Complex64::new(-1.0, 0.0)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(matrix, expected);
+
+    let row = fs.sparse_matrix_entries_on_row(6).unwrap();
+    assert_eq!(row.get(&3), Some(&Complex64::new(-1.0, 0.0)));
+    assert_eq!(row.len(), 1);
+
+    let values = vec![Complex64::new(1.0, 0.0), Complex64::new(-1.0, 0.0)];
+    assert_eq!(
+        fs.sparse_matrix_csr(None).unwrap(),
+        (values.clone(), vec![1, 3], vec![0, 0, 0, 0, 0, 1, 1, 2, 2])
+    );
+    assert_eq!(
+        fs.sparse_matrix_csc(None).unwrap(),
+        (values, vec![4, 6], vec![0, 0, 1, 1, 2, 2, 2, 2, 2])
+    );
+}
+
+// Test that the Fock-space matrices agree with the matrices of the Jordan-Wigner transformed operators
+#[test]
+fn sparse_matrix_agrees_with_jordan_wigner() {
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([0, 2], [1]).unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([1], [0, 2]).unwrap(),
+        CalculatorComplex::from(-2.0),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([2], [2]).unwrap(),
+        CalculatorComplex::from(0.3),
+    )
+    .unwrap();
+
+    assert_coo_close(
+        fo.sparse_matrix_coo(Some(3)).unwrap(),
+        fo.jordan_wigner().sparse_matrix_coo(Some(3)).unwrap(),
+    );
+
+    let mut fhs = FermionHamiltonianSystem::new(Some(3));
+    fhs.add_operator_product(
+        HermitianFermionProduct::new([0], [2]).unwrap(),
+        CalculatorComplex::new(0.5, -1.0),
+    )
+    .unwrap();
+    fhs.add_operator_product(
+        HermitianFermionProduct::new([1], [1]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    let shs = fhs.jordan_wigner();
+
+    assert_coo_close(
+        fhs.sparse_matrix_coo(None).unwrap(),
+        shs.sparse_matrix_coo(None).unwrap(),
+    );
+    assert_coo_close(
+        fhs.sparse_matrix_superoperator_coo(None).unwrap(),
+        shs.sparse_matrix_superoperator_coo(None).unwrap(),
+    );
+}
+
+// Test the term-by-term expectation values against the sparse matrix and the Jordan-Wigner transform
+#[test]
+fn expectation_value() {
+    let mut fs = FermionSystem::new(Some(3));
+    fs.add_operator_product(
+        FermionProduct::new([0, 2], [1]).unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    fs.add_operator_product(
+        FermionProduct::new([1], [0, 2]).unwrap(),
+        CalculatorComplex::from(-2.0),
+    )
+    .unwrap();
+    fs.add_operator_product(
+        FermionProduct::new([2], [2]).unwrap(),
+        CalculatorComplex::from(0.3),
+    )
+    .unwrap();
+
+    let state: Vec<Complex64> = (0..8)
+        .map(|k| Complex64::new((0.37 * k as f64).sin(), (0.71 * k as f64).cos()))
+        .collect();
+    let density_matrix: Vec<Complex64> = (0..64)
+        .map(|k| state[k / 8] * state[k % 8].conj())
+        .collect();
+    let mut expected = Complex64::default();
+    for ((row, column), value) in coo_to_map(fs.sparse_matrix_coo(None).unwrap()) {
+        expected += state[row].conj() * value * state[column];
+    }
+    for result in [
+        fs.expectation_value(&state).unwrap(),
+        fs.expectation_value_density_matrix(&density_matrix)
+            .unwrap(),
+    ] {
+        assert!((result.re.float().unwrap() - expected.re).abs() < 1e-12);
+        assert!((result.im.float().unwrap() - expected.im).abs() < 1e-12);
+    }
+
+    let mut fhs = FermionHamiltonianSystem::new(Some(3));
+    fhs.add_operator_product(
+        HermitianFermionProduct::new([0], [2]).unwrap(),
+        CalculatorComplex::new(0.5, -1.0),
+    )
+    .unwrap();
+    fhs.add_operator_product(
+        HermitianFermionProduct::new([1], [1]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    let expected = fhs.jordan_wigner().expectation_value(&state).unwrap();
+    for result in [
+        fhs.expectation_value(&state).unwrap(),
+        fhs.expectation_value_density_matrix(&density_matrix)
+            .unwrap(),
+    ] {
+        assert!((result.float().unwrap() - expected.float().unwrap()).abs() < 1e-12);
+    }
+
+    assert_eq!(
+        fhs.expectation_value(&state[..4]),
+        Err(StruqtureError::MissmatchedStateLengthModes {
+            length: 4,
+            number_modes: 3
+        })
+    );
+}
+
+// Test the superoperator of an open system with decay on a single mode
+#[test]
+fn sparse_matrix_superoperator_open_system() {
+    let mut noise = FermionLindbladNoiseSystem::new(Some(1));
+    let annihilator = FermionProduct::new([], [0]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut system = FermionHamiltonianSystem::new(Some(1));
+    system
+        .add_operator_product(
+            HermitianFermionProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+    let open_system = FermionLindbladOpenSystem::group(system, noise).unwrap();
+
+    let matrix = coo_to_map(open_system.sparse_matrix_superoperator_coo(None).unwrap());
+    let expected: HashMap<(usize, usize), Complex64> = [
+        ((0, 3), Complex64::new(1.0, 0.0)),
+        ((1, 1), Complex64::new(-0.5, 2.0)),
+        ((2, 2), Complex64::new(-0.5, -2.0)),
+        ((3, 3), Complex64::new(-1.0, 0.0)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(matrix, expected);
+}
+
+// Test the failure of the sparse matrix construction with too few modes
+#[test]
+fn sparse_matrix_number_modes_exceeded() {
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([0], [2]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+
+    assert_eq!(
+        fo.sparse_matrix_coo(Some(2)),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert_eq!(
+        fo.sparse_matrix_superoperator_coo(Some(2)),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+}
+
+// Test the evolve_density_matrix functions against the analytic decay and precession of a single mode
+#[test]
+fn evolve_density_matrix() {
+    let mut noise = FermionLindbladNoiseSystem::new(Some(1));
+    let annihilator = FermionProduct::new([], [0]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut system = FermionHamiltonianSystem::new(Some(1));
+    system
+        .add_operator_product(
+            HermitianFermionProduct::new([0], [0]).unwrap(),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+    let open_system = FermionLindbladOpenSystem::group(system, noise).unwrap();
+
+    let density_matrix = vec![Complex64::new(0.5, 0.0); 4];
+    let time: f64 = 0.8;
+    let decay = (-time).exp();
+    let coherence = Complex64::new(-0.5, 2.0) * time;
+    let expected = [
+        Complex64::new(1.0 - 0.5 * decay, 0.0),
+        0.5 * coherence.exp(),
+        0.5 * coherence.conj().exp(),
+        Complex64::new(0.5 * decay, 0.0),
+    ];
+
+    let runge_kutta = open_system
+        .evolve_density_matrix(&density_matrix, time, 1e-10)
+        .unwrap();
+    let krylov = open_system
+        .evolve_density_matrix_krylov(&density_matrix, time, 4, 1e-10)
+        .unwrap();
+    for index in 0..4 {
+        assert!((runge_kutta[index] - expected[index]).norm() < 1e-8);
+        assert!((krylov[index] - expected[index]).norm() < 1e-8);
+    }
+
+    let wrong_length = vec![Complex64::new(1.0, 0.0); 2];
+    assert_eq!(
+        open_system.evolve_density_matrix(&wrong_length, time, 1e-10),
+        Err(StruqtureError::MissmatchedStateLengthModes {
+            length: 2,
+            number_modes: 1
+        })
+    );
+    assert_eq!(
+        open_system.evolve_density_matrix_krylov(&wrong_length, time, 4, 1e-10),
+        Err(StruqtureError::MissmatchedStateLengthModes {
+            length: 2,
+            number_modes: 1
+        })
+    );
+
+    for tolerance in [0.0, -1e-10, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            open_system.evolve_density_matrix(&density_matrix, time, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+        assert!(matches!(
+            open_system.evolve_density_matrix_krylov(&density_matrix, time, 4, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+    }
+}
+
+// Test that the dense matrices agree with the sparse matrices and fail for too large Fock spaces
+#[test]
+fn dense_matrix() {
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([1], [0]).unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([0, 1], [0, 1]).unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+
+    let dense = fo.dense_matrix(Some(3)).unwrap();
+    assert_eq!(dense.dim(), (8, 8));
+    let sparse = coo_to_map(fo.sparse_matrix_coo(Some(3)).unwrap());
+    for ((row, column), value) in dense.indexed_iter() {
+        assert_eq!(
+            *value,
+            sparse
+                .get(&(row, column))
+                .copied()
+                .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+        );
+    }
+    let dense_superoperator = fo.dense_superoperator(None).unwrap();
+    let sparse_superoperator = coo_to_map(fo.sparse_matrix_superoperator_coo(None).unwrap());
+    for ((row, column), value) in dense_superoperator.indexed_iter() {
+        assert_eq!(
+            *value,
+            sparse_superoperator
+                .get(&(row, column))
+                .copied()
+                .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+        );
+    }
+
+    assert_eq!(
+        fo.dense_matrix(Some(1)),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert_eq!(
+        fo.dense_superoperator(Some(7)),
+        Err(StruqtureError::DimensionTooLarge {
+            dimension: 16384,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+    assert_eq!(
+        fo.dense_matrix(Some(100)),
+        Err(StruqtureError::HilbertSpaceTooLarge {
+            number_spins: 0,
+            number_modes: 100,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+}
+
+// Test that the parallel construction returns the same matrices as the serial construction
+#[cfg(feature = "rayon")]
+#[test]
+fn sparse_matrix_coo_parallel() {
+    let mut system = FermionHamiltonianSystem::new(Some(8));
+    system
+        .add_operator_product(
+            HermitianFermionProduct::new([0], [7]).unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    system
+        .add_operator_product(
+            HermitianFermionProduct::new([2, 4], [2, 4]).unwrap(),
+            CalculatorComplex::from(0.7),
+        )
+        .unwrap();
+    let mut noise = FermionLindbladNoiseSystem::new(Some(8));
+    for (mode, rate) in [(1, 0.3), (6, 0.2)] {
+        let annihilator = FermionProduct::new([], [mode]).unwrap();
+        noise
+            .add_operator_product(
+                (annihilator.clone(), annihilator),
+                CalculatorComplex::from(rate),
+            )
+            .unwrap();
+    }
+    let open_system = FermionLindbladOpenSystem::group(system.clone(), noise).unwrap();
+
+    assert_eq!(
+        system.sparse_matrix_coo_parallel(None),
+        system.sparse_matrix_coo(None)
+    );
+    // The 4^8 rows of the superoperators span several chunks of the parallel construction
+    assert_eq!(
+        system.sparse_matrix_superoperator_coo_parallel(None),
+        system.sparse_matrix_superoperator_coo(None)
+    );
+    assert_eq!(
+        open_system.sparse_matrix_superoperator_coo_parallel(None),
+        open_system.sparse_matrix_superoperator_coo(None)
+    );
+}
+
+// Test the steady_state function against the decay of a single mode into the empty state
+#[test]
+fn steady_state() {
+    let mut noise = FermionLindbladNoiseSystem::new(Some(2));
+    let annihilator = FermionProduct::new([], [0]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let annihilator = FermionProduct::new([], [1]).unwrap();
+    noise
+        .add_operator_product(
+            (annihilator.clone(), annihilator),
+            CalculatorComplex::from(0.5),
+        )
+        .unwrap();
+    let mut system = FermionHamiltonianSystem::new(Some(2));
+    system
+        .add_operator_product(
+            HermitianFermionProduct::new([0], [1]).unwrap(),
+            CalculatorComplex::from(0.7),
+        )
+        .unwrap();
+    let open_system = FermionLindbladOpenSystem::group(system, noise).unwrap();
+
+    let steady_state = open_system.steady_state(1e-12, 200).unwrap();
+    assert_eq!(steady_state.dim(), (4, 4));
+    for ((row, column), value) in steady_state.indexed_iter() {
+        let expected = if (row, column) == (0, 0) { 1.0 } else { 0.0 };
+        assert!((value - Complex64::new(expected, 0.0)).norm() < 1e-10);
+    }
+
+    // The dense steady state is limited to the maximal dimension of dense matrices
+    assert_eq!(
+        FermionLindbladOpenSystem::new(Some(13)).steady_state(1e-12, 200),
+        Err(StruqtureError::DimensionTooLarge {
+            dimension: 8192,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+}
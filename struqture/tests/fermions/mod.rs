@@ -13,6 +13,9 @@
 mod fermionic_product;
 pub use fermionic_product::*;
 
+mod fermion_qubit_encodings;
+pub use fermion_qubit_encodings::*;
+
 mod hermitian_fermionic_product;
 pub use hermitian_fermionic_product::*;
 
@@ -36,3 +39,9 @@ pub use fermionic_noise_system::*;
 
 mod fermionic_open_system;
 pub use fermionic_open_system::*;
+
+mod fermionic_sparse_matrices;
+pub use fermionic_sparse_matrices::*;
+
+mod spinful_layout;
+pub use spinful_layout::*;
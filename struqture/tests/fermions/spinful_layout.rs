@@ -0,0 +1,178 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the spinful fermion layout
+
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorComplex;
+use struqture::fermions::{
+    FermionHamiltonian, FermionOperator, FermionProduct, FermionSpin, HermitianFermionProduct,
+    SpinfulLayout, SpinfulOrdering,
+};
+use struqture::prelude::*;
+use struqture::StruqtureError;
+use test_case::test_case;
+
+// Helper function returning the real expectation value of a FermionHamiltonian for a state vector
+fn expectation(hamiltonian: &FermionHamiltonian, state: &[Complex64]) -> f64 {
+    *hamiltonian
+        .expectation_value(state)
+        .unwrap()
+        .float()
+        .unwrap()
+}
+
+// Helper function returning the basis state with the given occupied modes
+fn basis_state(modes: &[usize], number_modes: usize) -> Vec<Complex64> {
+    let mut state = vec![Complex64::new(0.0, 0.0); 2usize.pow(number_modes as u32)];
+    state[modes.iter().map(|mode| 1 << mode).sum::<usize>()] = Complex64::new(1.0, 0.0);
+    state
+}
+
+// Test the mapping between spin orbitals and modes
+#[test_case(SpinfulOrdering::Interleaved, &[(0, FermionSpin::Up), (0, FermionSpin::Down), (1, FermionSpin::Up), (1, FermionSpin::Down), (2, FermionSpin::Up), (2, FermionSpin::Down)]; "interleaved")]
+#[test_case(SpinfulOrdering::Blocked, &[(0, FermionSpin::Up), (1, FermionSpin::Up), (2, FermionSpin::Up), (0, FermionSpin::Down), (1, FermionSpin::Down), (2, FermionSpin::Down)]; "blocked")]
+fn mode_mapping(ordering: SpinfulOrdering, spin_orbitals: &[(usize, FermionSpin)]) {
+    let layout = SpinfulLayout::new(3, ordering);
+    assert_eq!(layout.number_orbitals(), 3);
+    assert_eq!(layout.number_modes(), 6);
+    assert_eq!(layout.ordering(), ordering);
+    for (mode, (orbital, spin)) in spin_orbitals.iter().enumerate() {
+        assert_eq!(layout.mode(*orbital, *spin), Ok(mode));
+        assert_eq!(layout.orbital_spin(mode), Ok((*orbital, *spin)));
+    }
+    assert_eq!(
+        layout.mode(3, FermionSpin::Up),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+    assert_eq!(
+        layout.orbital_spin(6),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+}
+
+// Test the formatting of FermionProducts with spin-orbital labels
+#[test]
+fn format_product() {
+    let layout = SpinfulLayout::new(2, SpinfulOrdering::Interleaved);
+    let product = FermionProduct::new([0, 1], [2, 3]).unwrap();
+    assert_eq!(layout.format_product(&product).unwrap(), "c0↑c0↓a1↑a1↓");
+    assert_eq!(
+        layout
+            .format_product(&FermionProduct::new([], []).unwrap())
+            .unwrap(),
+        "I"
+    );
+
+    let layout = SpinfulLayout::new(2, SpinfulOrdering::Blocked);
+    assert_eq!(layout.format_product(&product).unwrap(), "c0↑c1↑a0↓a1↓");
+    assert_eq!(
+        layout.format_product(&FermionProduct::new([4], []).unwrap()),
+        Err(StruqtureError::NumberModesExceeded)
+    );
+}
+
+// Test the number, S_z and S_+ operators
+#[test]
+fn number_s_z_s_plus() {
+    let layout = SpinfulLayout::new(2, SpinfulOrdering::Blocked);
+
+    let mut number_down = FermionHamiltonian::new();
+    let mut s_z = FermionHamiltonian::new();
+    let mut s_plus = FermionOperator::new();
+    for (up, down) in [(0, 2), (1, 3)] {
+        number_down
+            .add_operator_product(
+                HermitianFermionProduct::new([down], [down]).unwrap(),
+                CalculatorComplex::from(1.0),
+            )
+            .unwrap();
+        s_z.add_operator_product(
+            HermitianFermionProduct::new([up], [up]).unwrap(),
+            CalculatorComplex::from(0.5),
+        )
+        .unwrap();
+        s_z.add_operator_product(
+            HermitianFermionProduct::new([down], [down]).unwrap(),
+            CalculatorComplex::from(-0.5),
+        )
+        .unwrap();
+        s_plus
+            .add_operator_product(
+                FermionProduct::new([up], [down]).unwrap(),
+                CalculatorComplex::from(1.0),
+            )
+            .unwrap();
+    }
+    assert_eq!(layout.number_operator(FermionSpin::Down), number_down);
+    assert_eq!(layout.s_z(), s_z);
+    assert_eq!(layout.s_plus(), s_plus);
+    assert_eq!(layout.number_operator(FermionSpin::Up).len(), 2);
+}
+
+// Test the S^2 operator of a single orbital, S^2 = 3/4 (n_up + n_down) - 3/2 n_up n_down
+#[test]
+fn s_squared_single_orbital() {
+    let layout = SpinfulLayout::new(1, SpinfulOrdering::Interleaved);
+    let mut expected = FermionHamiltonian::new();
+    for (creators, annihilators, value) in [
+        (vec![0], vec![0], 0.75),
+        (vec![1], vec![1], 0.75),
+        (vec![0, 1], vec![0, 1], 1.5),
+    ] {
+        expected
+            .add_operator_product(
+                HermitianFermionProduct::new(creators, annihilators).unwrap(),
+                CalculatorComplex::from(value),
+            )
+            .unwrap();
+    }
+    assert_eq!(layout.s_squared(), expected);
+}
+
+// Test the S^2 operator of two orbitals on singlet and triplet states
+#[test_case(SpinfulOrdering::Interleaved; "interleaved")]
+#[test_case(SpinfulOrdering::Blocked; "blocked")]
+fn s_squared_two_orbitals(ordering: SpinfulOrdering) {
+    let layout = SpinfulLayout::new(2, ordering);
+    let s_squared = layout.s_squared();
+    let s_z = layout.s_z();
+    let mode = |orbital: usize, spin: FermionSpin| layout.mode(orbital, spin).unwrap();
+
+    // Both spins up is part of the triplet
+    let state = basis_state(&[mode(0, FermionSpin::Up), mode(1, FermionSpin::Up)], 4);
+    assert!((expectation(&s_squared, &state) - 2.0).abs() < 1e-12);
+    assert!((expectation(&s_z, &state) - 1.0).abs() < 1e-12);
+
+    // A doubly occupied orbital is a singlet
+    let state = basis_state(&[mode(0, FermionSpin::Up), mode(0, FermionSpin::Down)], 4);
+    assert!(expectation(&s_squared, &state).abs() < 1e-12);
+
+    // Opposite spins on different orbitals are an equal superposition of singlet and triplet
+    let up_down = basis_state(&[mode(0, FermionSpin::Up), mode(1, FermionSpin::Down)], 4);
+    let down_up = basis_state(&[mode(0, FermionSpin::Down), mode(1, FermionSpin::Up)], 4);
+    assert!((expectation(&s_squared, &up_down) - 1.0).abs() < 1e-12);
+    let mut values: Vec<f64> = [1.0, -1.0]
+        .iter()
+        .map(|sign| {
+            let state: Vec<Complex64> = up_down
+                .iter()
+                .zip(down_up.iter())
+                .map(|(first, second)| (*first + *second * *sign) / 2.0_f64.sqrt())
+                .collect();
+            expectation(&s_squared, &state)
+        })
+        .collect();
+    values.sort_by(|first, second| first.partial_cmp(second).unwrap());
+    assert!(values[0].abs() < 1e-12);
+    assert!((values[1] - 2.0).abs() < 1e-12);
+}
@@ -20,6 +20,12 @@ mod bosons;
 #[cfg(test)]
 mod fermions;
 
+#[cfg(test)]
+mod mappings;
+
+#[cfg(test)]
+mod models;
+
 #[cfg(test)]
 mod spins;
 
@@ -0,0 +1,261 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the Jordan-Wigner mappings
+
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use struqture::fermions::{
+    FermionHamiltonian, FermionHamiltonianSystem, FermionOperator, FermionProduct, FermionSystem,
+    HermitianFermionProduct,
+};
+use struqture::mappings::{JordanWignerFermionToSpin, JordanWignerSpinToFermion};
+use struqture::prelude::*;
+use struqture::spins::{PauliProduct, SpinHamiltonian, SpinOperator, SpinSystem};
+use test_case::test_case;
+
+// Test the Jordan-Wigner transformation of the number operator
+#[test]
+fn jordan_wigner_number_operator() {
+    let fp = FermionProduct::new([2], [2]).unwrap();
+
+    let mut so = SpinOperator::new();
+    so.add_operator_product(PauliProduct::new(), CalculatorComplex::from(0.5))
+        .unwrap();
+    so.add_operator_product(PauliProduct::new().z(2), CalculatorComplex::from(-0.5))
+        .unwrap();
+
+    assert_eq!(fp.jordan_wigner(), so);
+}
+
+// Test the Jordan-Wigner transformation of an annihilator including the parity string
+#[test]
+fn jordan_wigner_annihilator_parity_string() {
+    let fp = FermionProduct::new([], [2]).unwrap();
+
+    let mut so = SpinOperator::new();
+    so.add_operator_product(
+        PauliProduct::new().z(0).z(1).x(2),
+        CalculatorComplex::from(0.5),
+    )
+    .unwrap();
+    so.add_operator_product(
+        PauliProduct::new().z(0).z(1).y(2),
+        CalculatorComplex::new(0.0, 0.5),
+    )
+    .unwrap();
+
+    assert_eq!(fp.jordan_wigner(), so);
+}
+
+// Test the Jordan-Wigner transformation of two creators
+#[test]
+fn jordan_wigner_two_creators() {
+    let fp = FermionProduct::new([0, 1], []).unwrap();
+
+    let mut so = SpinOperator::new();
+    so.add_operator_product(PauliProduct::new().x(0).x(1), CalculatorComplex::from(0.25))
+        .unwrap();
+    so.add_operator_product(
+        PauliProduct::new().x(0).y(1),
+        CalculatorComplex::new(0.0, -0.25),
+    )
+    .unwrap();
+    so.add_operator_product(
+        PauliProduct::new().y(0).x(1),
+        CalculatorComplex::new(0.0, -0.25),
+    )
+    .unwrap();
+    so.add_operator_product(
+        PauliProduct::new().y(0).y(1),
+        CalculatorComplex::from(-0.25),
+    )
+    .unwrap();
+
+    assert_eq!(fp.jordan_wigner(), so);
+}
+
+// Test that the transformed operators fulfill the canonical anticommutation relations
+#[test_case(0, 0; "same mode")]
+#[test_case(0, 1; "neighbouring modes")]
+#[test_case(3, 1; "distant modes")]
+fn jordan_wigner_anticommutation(i: usize, j: usize) {
+    let annihilator = FermionProduct::new([], [i]).unwrap().jordan_wigner();
+    let creator = FermionProduct::new([j], []).unwrap().jordan_wigner();
+
+    let anticommutator = annihilator.clone() * creator.clone() + creator * annihilator;
+    let mut expected = SpinOperator::new();
+    if i == j {
+        expected
+            .add_operator_product(PauliProduct::new(), CalculatorComplex::from(1.0))
+            .unwrap();
+    }
+    assert_eq!(anticommutator, expected);
+}
+
+// Test the Jordan-Wigner transformation of a hopping term in a FermionHamiltonian
+#[test]
+fn jordan_wigner_hopping_hamiltonian() {
+    let mut fh = FermionHamiltonian::new();
+    fh.add_operator_product(
+        HermitianFermionProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(1.5),
+    )
+    .unwrap();
+    fh.add_operator_product(
+        HermitianFermionProduct::new([1], [1]).unwrap(),
+        CalculatorComplex::from(-1.0),
+    )
+    .unwrap();
+
+    let mut sh = SpinHamiltonian::new();
+    sh.add_operator_product(PauliProduct::new().x(0).x(1), CalculatorFloat::from(0.75))
+        .unwrap();
+    sh.add_operator_product(PauliProduct::new().y(0).y(1), CalculatorFloat::from(0.75))
+        .unwrap();
+    sh.add_operator_product(PauliProduct::new(), CalculatorFloat::from(-0.5))
+        .unwrap();
+    sh.add_operator_product(PauliProduct::new().z(1), CalculatorFloat::from(0.5))
+        .unwrap();
+
+    assert_eq!(fh.jordan_wigner(), sh);
+
+    let mut sh_product = SpinHamiltonian::new();
+    sh_product
+        .add_operator_product(PauliProduct::new().x(0).x(1), CalculatorFloat::from(0.5))
+        .unwrap();
+    sh_product
+        .add_operator_product(PauliProduct::new().y(0).y(1), CalculatorFloat::from(0.5))
+        .unwrap();
+    assert_eq!(
+        HermitianFermionProduct::new([0], [1])
+            .unwrap()
+            .jordan_wigner(),
+        sh_product
+    );
+}
+
+// Test the inverse Jordan-Wigner transformation of a PauliProduct
+#[test]
+fn inverse_jordan_wigner_xx() {
+    let pp = PauliProduct::new().x(0).x(1);
+
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([0, 1], []).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([1], [0]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([], [0, 1]).unwrap(),
+        CalculatorComplex::from(-1.0),
+    )
+    .unwrap();
+
+    assert_eq!(pp.jordan_wigner(), fo);
+}
+
+// Test that the Jordan-Wigner transformation and its inverse are consistent for FermionOperators
+#[test]
+fn jordan_wigner_round_trip_fermion_operator() {
+    let mut fo = FermionOperator::new();
+    fo.add_operator_product(
+        FermionProduct::new([0, 2], [1]).unwrap(),
+        CalculatorComplex::new(1.0, 2.0),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([3], [0, 3]).unwrap(),
+        CalculatorComplex::from(-0.5),
+    )
+    .unwrap();
+    fo.add_operator_product(
+        FermionProduct::new([], []).unwrap(),
+        CalculatorComplex::from(3.0),
+    )
+    .unwrap();
+
+    assert_eq!(fo.jordan_wigner().jordan_wigner(), fo);
+}
+
+// Test that the inverse Jordan-Wigner transformation and the transformation are consistent for SpinOperators
+#[test]
+fn jordan_wigner_round_trip_spin_operator() {
+    let mut so = SpinOperator::new();
+    so.add_operator_product(
+        PauliProduct::new().x(0).z(1).y(3),
+        CalculatorComplex::new(0.5, 1.0),
+    )
+    .unwrap();
+    so.add_operator_product(PauliProduct::new().y(2), CalculatorComplex::from(2.0))
+        .unwrap();
+
+    assert_eq!(so.jordan_wigner().jordan_wigner(), so);
+}
+
+// Test that the inverse Jordan-Wigner transformation and the transformation are consistent for SpinHamiltonians
+#[test]
+fn jordan_wigner_round_trip_spin_hamiltonian() {
+    let mut sh = SpinHamiltonian::new();
+    sh.add_operator_product(PauliProduct::new().x(0).x(1), CalculatorFloat::from(1.0))
+        .unwrap();
+    sh.add_operator_product(PauliProduct::new().y(0).y(1), CalculatorFloat::from(1.0))
+        .unwrap();
+    sh.add_operator_product(PauliProduct::new().z(0).z(2), CalculatorFloat::from(0.5))
+        .unwrap();
+    sh.add_operator_product(
+        PauliProduct::new().x(1).z(2).x(3),
+        CalculatorFloat::from(-2.0),
+    )
+    .unwrap();
+
+    let fh = sh.jordan_wigner();
+    assert_eq!(fh.jordan_wigner(), sh);
+}
+
+// Test that the number of modes and spins is kept consistent for systems
+#[test]
+fn jordan_wigner_systems() {
+    let mut fs = FermionSystem::new(Some(4));
+    fs.add_operator_product(
+        FermionProduct::new([0], [2]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    let ss = fs.jordan_wigner();
+    assert_eq!(ss.number_spins(), 4);
+    assert_eq!(ss.operator(), &fs.operator().jordan_wigner());
+    assert_eq!(ss.jordan_wigner(), fs);
+
+    let ss_none = SpinSystem::new(None);
+    assert_eq!(ss_none.jordan_wigner(), FermionSystem::new(None));
+
+    let mut fhs = FermionHamiltonianSystem::new(Some(3));
+    fhs.add_operator_product(
+        HermitianFermionProduct::new([0], [1]).unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    let shs = fhs.jordan_wigner();
+    assert_eq!(shs.number_spins(), 3);
+    assert_eq!(shs.operator(), &fhs.hamiltonian().jordan_wigner());
+    assert_eq!(shs.jordan_wigner(), fhs);
+}
@@ -20,7 +20,7 @@ use struqture::bosons::BosonProduct;
 use struqture::fermions::FermionProduct;
 use struqture::mixed_systems::{
     HermitianMixedProduct, MixedDecoherenceProduct, MixedHamiltonianSystem,
-    MixedLindbladNoiseSystem, MixedLindbladOpenSystem,
+    MixedLindbladNoiseSystem, MixedLindbladOpenSystem, MixedOperator, MixedProduct,
 };
 use struqture::prelude::*;
 use struqture::spins::{DecoherenceProduct, PauliProduct};
@@ -1379,3 +1379,65 @@ fn test_truncate() {
     let comparison_system2 = system.truncate(0.5);
     assert_eq!(test_system2, comparison_system2);
 }
+
+// Test the effective non-hermitian Hamiltonian of the MixedLindbladOpenSystem
+#[test]
+fn effective_hamiltonian() {
+    let mut slos = MixedLindbladOpenSystem::new([Some(1)], [Some(1)], [Some(1)]);
+    slos.system_mut()
+        .set(
+            HermitianMixedProduct::new(
+                [PauliProduct::new().z(0)],
+                [BosonProduct::new([0], [0]).unwrap()],
+                [FermionProduct::new([], []).unwrap()],
+            )
+            .unwrap(),
+            CalculatorComplex::from(0.5),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                MixedDecoherenceProduct::new(
+                    [DecoherenceProduct::new().iy(0)],
+                    [BosonProduct::new([], [0]).unwrap()],
+                    [FermionProduct::new([], []).unwrap()],
+                )
+                .unwrap(),
+                MixedDecoherenceProduct::new(
+                    [DecoherenceProduct::new().iy(0)],
+                    [BosonProduct::new([], [0]).unwrap()],
+                    [FermionProduct::new([], []).unwrap()],
+                )
+                .unwrap(),
+            ),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+
+    // (iY b_0)^† iY b_0 = b_0^† b_0
+    let mut expected = MixedOperator::new(1, 1, 1);
+    expected
+        .set(
+            MixedProduct::new(
+                [PauliProduct::new().z(0)],
+                [BosonProduct::new([0], [0]).unwrap()],
+                [FermionProduct::new([], []).unwrap()],
+            )
+            .unwrap(),
+            CalculatorComplex::from(0.5),
+        )
+        .unwrap();
+    expected
+        .set(
+            MixedProduct::new(
+                [PauliProduct::new()],
+                [BosonProduct::new([0], [0]).unwrap()],
+                [FermionProduct::new([], []).unwrap()],
+            )
+            .unwrap(),
+            CalculatorComplex::new(0.0, -1.0),
+        )
+        .unwrap();
+    assert_eq!(slos.effective_hamiltonian(), Ok(expected));
+}
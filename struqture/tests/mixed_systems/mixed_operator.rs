@@ -515,6 +515,71 @@ fn mul_so_so() {
     assert_eq!(mo_0 * mo_1, Ok(mo_0_1));
 }
 
+// Test the commutator and anticommutator of MixedOperators against the explicit products
+#[test]
+fn commutator_anticommutator() {
+    let mut mo_0 = MixedOperator::new(1, 1, 1);
+    mo_0.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().z(2)],
+            [BosonProduct::new([0], [3]).unwrap()],
+            [FermionProduct::new([0], []).unwrap()],
+        )
+        .unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+    mo_0.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().x(0)],
+            [BosonProduct::new([1], []).unwrap()],
+            [FermionProduct::new([1], [2]).unwrap()],
+        )
+        .unwrap(),
+        CalculatorComplex::new(0.0, 0.5),
+    )
+    .unwrap();
+    let mut mo_1 = MixedOperator::new(1, 1, 1);
+    mo_1.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().x(2)],
+            [BosonProduct::new([1], [2]).unwrap()],
+            [FermionProduct::new([], [3]).unwrap()],
+        )
+        .unwrap(),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    mo_1.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().y(0)],
+            [BosonProduct::new([], [1]).unwrap()],
+            [FermionProduct::new([2], []).unwrap()],
+        )
+        .unwrap(),
+        CalculatorComplex::from(-1.5),
+    )
+    .unwrap();
+
+    let forward = (mo_0.clone() * mo_1.clone()).unwrap();
+    let backward = (mo_1.clone() * mo_0.clone()).unwrap();
+    assert_eq!(mo_0.commutator(&mo_1), forward.clone() - backward.clone());
+    assert_eq!(mo_0.anticommutator(&mo_1), forward + backward);
+
+    let mo_2 = MixedOperator::new(2, 1, 1);
+    assert_eq!(
+        mo_0.commutator(&mo_2),
+        Err(StruqtureError::MissmatchedNumberSubsystems {
+            target_number_spin_subsystems: 1,
+            target_number_boson_subsystems: 1,
+            target_number_fermion_subsystems: 1,
+            actual_number_spin_subsystems: 2,
+            actual_number_boson_subsystems: 1,
+            actual_number_fermion_subsystems: 1,
+        })
+    );
+}
+
 // Test the Iter traits of FermionOperator: into_iter, from_iter and extend
 #[test]
 fn into_iter_from_iter_extend() {
@@ -0,0 +1,491 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the sparse matrices of mixed objects
+
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorComplex;
+use std::collections::HashMap;
+use struqture::bosons::{BosonCutoff, BosonProduct};
+use struqture::fermions::{FermionProduct, FermionSystem};
+use struqture::mixed_systems::{
+    HermitianMixedProduct, MixedDecoherenceProduct, MixedHamiltonianSystem, MixedHilbertSpace,
+    MixedLindbladNoiseSystem, MixedLindbladOpenSystem, MixedProduct, MixedSystem,
+};
+use struqture::prelude::*;
+use struqture::spins::{
+    DecoherenceProduct, PauliProduct, SpinHamiltonianSystem, SpinLindbladNoiseSystem, SpinSystem,
+};
+use struqture::{CooSparseMatrix, StruqtureError, MAXIMAL_DENSE_DIMENSION};
+
+// Helper function to collect a COO matrix into a map from (row, column) to value
+fn coo_to_map(matrix: CooSparseMatrix) -> HashMap<(usize, usize), Complex64> {
+    let (values, (rows, columns)) = matrix;
+    let mut map: HashMap<(usize, usize), Complex64> = HashMap::new();
+    for ((value, row), column) in values.into_iter().zip(rows).zip(columns) {
+        *map.entry((row, column)).or_insert(Complex64::new(0.0, 0.0)) += value;
+    }
+    map.retain(|_, value| value.norm() > 1e-12);
+    map
+}
+
+// Helper function to compare two COO matrices up to numerical precision
+fn assert_coo_close(left: CooSparseMatrix, right: CooSparseMatrix) {
+    let left = coo_to_map(left);
+    let right = coo_to_map(right);
+    assert_eq!(left.len(), right.len());
+    for (key, value) in left.iter() {
+        let other = right.get(key).copied().unwrap_or_default();
+        assert!((value - other).norm() < 1e-12);
+    }
+}
+
+// Test that a mixed system with a single spin subsystem reproduces the spin matrices
+#[test]
+fn sparse_matrix_spin_subsystem() {
+    let mut ms = MixedSystem::new([Some(2)], [], []);
+    let mut ss = SpinSystem::new(Some(2));
+    for (pp, value) in [
+        (
+            PauliProduct::new().x(0).y(1),
+            CalculatorComplex::new(1.0, 0.5),
+        ),
+        (PauliProduct::new().z(0), CalculatorComplex::from(-0.3)),
+        (PauliProduct::new().y(0), CalculatorComplex::from(2.0)),
+    ] {
+        ms.add_operator_product(
+            MixedProduct::new([pp.clone()], [], []).unwrap(),
+            value.clone(),
+        )
+        .unwrap();
+        ss.add_operator_product(pp, value).unwrap();
+    }
+    assert_coo_close(
+        ms.sparse_matrix_coo(&[]).unwrap(),
+        ss.sparse_matrix_coo(None).unwrap(),
+    );
+    assert_coo_close(
+        ms.sparse_matrix_superoperator_coo(&[]).unwrap(),
+        ss.sparse_matrix_superoperator_coo(None).unwrap(),
+    );
+
+    let mut mhs = MixedHamiltonianSystem::new([Some(2)], [], []);
+    let mut shs = SpinHamiltonianSystem::new(Some(2));
+    mhs.add_operator_product(
+        HermitianMixedProduct::new([PauliProduct::new().x(0).x(1)], [], []).unwrap(),
+        CalculatorComplex::from(0.7),
+    )
+    .unwrap();
+    shs.add_operator_product(PauliProduct::new().x(0).x(1), 0.7.into())
+        .unwrap();
+    assert_coo_close(
+        mhs.sparse_matrix_superoperator_coo(&[]).unwrap(),
+        shs.sparse_matrix_superoperator_coo(None).unwrap(),
+    );
+}
+
+// Test that a mixed system with a single fermionic subsystem reproduces the fermionic matrices
+#[test]
+fn sparse_matrix_fermion_subsystem() {
+    let mut ms = MixedSystem::new([], [], [Some(3)]);
+    let mut fs = FermionSystem::new(Some(3));
+    for (fp, value) in [
+        (
+            FermionProduct::new([2], [0]).unwrap(),
+            CalculatorComplex::new(1.0, -1.0),
+        ),
+        (
+            FermionProduct::new([0, 1], [1]).unwrap(),
+            CalculatorComplex::from(0.5),
+        ),
+    ] {
+        ms.add_operator_product(
+            MixedProduct::new([], [], [fp.clone()]).unwrap(),
+            value.clone(),
+        )
+        .unwrap();
+        fs.add_operator_product(fp, value).unwrap();
+    }
+    assert_coo_close(
+        ms.sparse_matrix_coo(&[]).unwrap(),
+        fs.sparse_matrix_coo(None).unwrap(),
+    );
+}
+
+// Test the ordering of the subsystems in the tensor product
+#[test]
+fn sparse_matrix_subsystem_ordering() {
+    let mut ms = MixedSystem::new([Some(1)], [Some(1)], []);
+    ms.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().x(0)],
+            [BosonProduct::new([0], []).unwrap()],
+            [],
+        )
+        .unwrap(),
+        CalculatorComplex::from(1.0),
+    )
+    .unwrap();
+
+    // The spin is the fastest changing index: |s, n> has the index s + 2 * n
+    let matrix = coo_to_map(ms.sparse_matrix_coo(&[BosonCutoff::Global(2)]).unwrap());
+    let expected: HashMap<(usize, usize), Complex64> = [
+        ((3, 0), Complex64::new(1.0, 0.0)),
+        ((2, 1), Complex64::new(1.0, 0.0)),
+        ((5, 2), Complex64::new(2.0_f64.sqrt(), 0.0)),
+        ((4, 3), Complex64::new(2.0_f64.sqrt(), 0.0)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(matrix, expected);
+
+    let hilbert_space = ms.mixed_hilbert_space(&[BosonCutoff::Global(2)]).unwrap();
+    assert_eq!(
+        hilbert_space,
+        MixedHilbertSpace::new(vec![1], vec![vec![2]], vec![])
+    );
+    assert_eq!(hilbert_space.subsystem_dimensions(), vec![2, 3]);
+}
+
+// Test that the noise superoperator with a single spin subsystem reproduces the spin superoperator
+#[test]
+fn sparse_matrix_superoperator_spin_noise() {
+    let mut mns = MixedLindbladNoiseSystem::new([Some(2)], [], []);
+    let mut sns = SpinLindbladNoiseSystem::new(Some(2));
+    for (left, right, rate) in [
+        (
+            DecoherenceProduct::new().x(0),
+            DecoherenceProduct::new().iy(0),
+            CalculatorComplex::new(0.5, 0.2),
+        ),
+        (
+            DecoherenceProduct::new().iy(0),
+            DecoherenceProduct::new().x(0),
+            CalculatorComplex::new(0.5, -0.2),
+        ),
+        (
+            DecoherenceProduct::new().z(1),
+            DecoherenceProduct::new().z(1),
+            CalculatorComplex::from(1.0),
+        ),
+    ] {
+        mns.add_operator_product(
+            (
+                MixedDecoherenceProduct::new([left.clone()], [], []).unwrap(),
+                MixedDecoherenceProduct::new([right.clone()], [], []).unwrap(),
+            ),
+            rate.clone(),
+        )
+        .unwrap();
+        sns.add_operator_product((left, right), rate).unwrap();
+    }
+    assert_coo_close(
+        mns.sparse_matrix_superoperator_coo(&[]).unwrap(),
+        sns.sparse_matrix_superoperator_coo(None).unwrap(),
+    );
+}
+
+// Test the superoperator of a damped spin-boson open system
+#[test]
+fn sparse_matrix_superoperator_open_system() {
+    let mut system = MixedHamiltonianSystem::new([Some(1)], [Some(1)], []);
+    system
+        .add_operator_product(
+            HermitianMixedProduct::new(
+                [PauliProduct::new().z(0)],
+                [BosonProduct::new([], []).unwrap()],
+                [],
+            )
+            .unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut noise = MixedLindbladNoiseSystem::new([Some(1)], [Some(1)], []);
+    let decay = MixedDecoherenceProduct::new(
+        [DecoherenceProduct::new()],
+        [BosonProduct::new([], [0]).unwrap()],
+        [],
+    )
+    .unwrap();
+    noise
+        .add_operator_product((decay.clone(), decay), CalculatorComplex::from(1.0))
+        .unwrap();
+    let open_system = MixedLindbladOpenSystem::group(system, noise).unwrap();
+
+    let matrix = coo_to_map(
+        open_system
+            .sparse_matrix_superoperator_coo(&[BosonCutoff::Global(1)])
+            .unwrap(),
+    );
+    // rho_{(s=0, n=1), (s=0, n=1)} decays into rho_{(s=0, n=0), (s=0, n=0)}
+    assert_eq!(matrix.get(&(0, 2 * 4 + 2)), Some(&Complex64::new(1.0, 0.0)));
+    assert_eq!(
+        matrix.get(&(2 * 4 + 2, 2 * 4 + 2)),
+        Some(&Complex64::new(-1.0, 0.0))
+    );
+    // rho_{(s=0, n=0), (s=1, n=0)} only precesses
+    assert_eq!(matrix.get(&(1, 1)), Some(&Complex64::new(0.0, -2.0)));
+}
+
+// Test the failure of the sparse matrix construction with a wrong number of bosonic cutoffs
+#[test]
+fn sparse_matrix_missmatched_cutoffs() {
+    let ms = MixedSystem::new([Some(1)], [Some(1)], [Some(1)]);
+    assert_eq!(
+        ms.sparse_matrix_coo(&[]),
+        Err(StruqtureError::MissmatchedNumberSubsystems {
+            target_number_spin_subsystems: 1,
+            target_number_boson_subsystems: 0,
+            target_number_fermion_subsystems: 1,
+            actual_number_spin_subsystems: 1,
+            actual_number_boson_subsystems: 1,
+            actual_number_fermion_subsystems: 1,
+        })
+    );
+}
+
+// Test the evolve_density_matrix functions against the analytic decay of a damped spin-boson system
+#[test]
+fn evolve_density_matrix() {
+    let mut system = MixedHamiltonianSystem::new([Some(1)], [Some(1)], []);
+    system
+        .add_operator_product(
+            HermitianMixedProduct::new(
+                [PauliProduct::new().z(0)],
+                [BosonProduct::new([], []).unwrap()],
+                [],
+            )
+            .unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut noise = MixedLindbladNoiseSystem::new([Some(1)], [Some(1)], []);
+    let decay = MixedDecoherenceProduct::new(
+        [DecoherenceProduct::new()],
+        [BosonProduct::new([], [0]).unwrap()],
+        [],
+    )
+    .unwrap();
+    noise
+        .add_operator_product((decay.clone(), decay), CalculatorComplex::from(1.0))
+        .unwrap();
+    let open_system = MixedLindbladOpenSystem::group(system, noise).unwrap();
+    let cutoffs = [BosonCutoff::Global(1)];
+
+    // The state |s=0, n=1> decays into |s=0, n=0>
+    let mut density_matrix = vec![Complex64::new(0.0, 0.0); 16];
+    density_matrix[2 * 4 + 2] = Complex64::new(1.0, 0.0);
+    let time: f64 = 0.8;
+    let mut expected = vec![Complex64::new(0.0, 0.0); 16];
+    expected[0] = Complex64::new(1.0 - (-time).exp(), 0.0);
+    expected[2 * 4 + 2] = Complex64::new((-time).exp(), 0.0);
+
+    let runge_kutta = open_system
+        .evolve_density_matrix(&density_matrix, &cutoffs, time, 1e-10)
+        .unwrap();
+    let krylov = open_system
+        .evolve_density_matrix_krylov(&density_matrix, &cutoffs, time, 8, 1e-10)
+        .unwrap();
+    for index in 0..16 {
+        assert!((runge_kutta[index] - expected[index]).norm() < 1e-8);
+        assert!((krylov[index] - expected[index]).norm() < 1e-8);
+    }
+
+    assert_eq!(
+        open_system.evolve_density_matrix(&density_matrix[..4], &cutoffs, time, 1e-10),
+        Err(StruqtureError::MissmatchedDensityMatrixLength {
+            length: 4,
+            dimension: 4
+        })
+    );
+
+    for tolerance in [0.0, -1e-10, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            open_system.evolve_density_matrix(&density_matrix, &cutoffs, time, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+        assert!(matches!(
+            open_system.evolve_density_matrix_krylov(&density_matrix, &cutoffs, time, 8, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+    }
+}
+
+// Test that the dense matrices agree with the sparse matrices and fail for too large Hilbert spaces
+#[test]
+fn dense_matrix() {
+    let mut ms = MixedSystem::new([Some(1)], [Some(1)], [Some(1)]);
+    ms.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().x(0)],
+            [BosonProduct::new([0], []).unwrap()],
+            [FermionProduct::new([0], [0]).unwrap()],
+        )
+        .unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    let cutoffs = [BosonCutoff::Global(2)];
+
+    let dense = ms.dense_matrix(&cutoffs).unwrap();
+    assert_eq!(dense.dim(), (12, 12));
+    let sparse = coo_to_map(ms.sparse_matrix_coo(&cutoffs).unwrap());
+    for ((row, column), value) in dense.indexed_iter() {
+        assert_eq!(
+            *value,
+            sparse
+                .get(&(row, column))
+                .copied()
+                .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+        );
+    }
+    let dense_superoperator = ms.dense_superoperator(&cutoffs).unwrap();
+    let sparse_superoperator = coo_to_map(ms.sparse_matrix_superoperator_coo(&cutoffs).unwrap());
+    for ((row, column), value) in dense_superoperator.indexed_iter() {
+        assert_eq!(
+            *value,
+            sparse_superoperator
+                .get(&(row, column))
+                .copied()
+                .unwrap_or_else(|| Complex64::new(0.0, 0.0))
+        );
+    }
+
+    assert_eq!(
+        ms.dense_superoperator(&[BosonCutoff::Global(20)]),
+        Err(StruqtureError::DimensionTooLarge {
+            dimension: 7056,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+    assert_eq!(
+        ms.dense_matrix(&[BosonCutoff::Global(usize::MAX)]),
+        Err(StruqtureError::HilbertSpaceTooLarge {
+            number_spins: 1,
+            number_modes: 2,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+}
+
+// Test that the parallel construction of the COO output is identical to the serial construction
+#[cfg(feature = "rayon")]
+#[test]
+fn sparse_matrix_coo_parallel() {
+    let mut ms = MixedSystem::new([Some(2)], [Some(1)], [Some(2)]);
+    ms.add_operator_product(
+        MixedProduct::new(
+            [PauliProduct::new().x(0).z(1)],
+            [BosonProduct::new([0], []).unwrap()],
+            [FermionProduct::new([1], [0]).unwrap()],
+        )
+        .unwrap(),
+        CalculatorComplex::new(1.0, 0.5),
+    )
+    .unwrap();
+    let cutoffs = [BosonCutoff::Global(2)];
+
+    assert_eq!(
+        ms.sparse_matrix_coo_parallel(&cutoffs),
+        ms.sparse_matrix_coo(&cutoffs)
+    );
+    assert_eq!(
+        ms.sparse_matrix_superoperator_coo_parallel(&cutoffs),
+        ms.sparse_matrix_superoperator_coo(&cutoffs)
+    );
+}
+
+// Test that the parallel construction of the superoperator of an open system returns the same matrix as the serial construction
+#[cfg(feature = "rayon")]
+#[test]
+fn sparse_matrix_superoperator_coo_parallel_open_system() {
+    let mut system = MixedHamiltonianSystem::new([Some(3)], [Some(2)], []);
+    system
+        .add_operator_product(
+            HermitianMixedProduct::new(
+                [PauliProduct::new().x(0)],
+                [BosonProduct::new([0], [1]).unwrap()],
+                [],
+            )
+            .unwrap(),
+            CalculatorComplex::new(1.0, 0.5),
+        )
+        .unwrap();
+    let mut noise = MixedLindbladNoiseSystem::new([Some(3)], [Some(2)], []);
+    let decay = MixedDecoherenceProduct::new(
+        [DecoherenceProduct::new().z(2)],
+        [BosonProduct::new([], [1]).unwrap()],
+        [],
+    )
+    .unwrap();
+    noise
+        .add_operator_product((decay.clone(), decay), CalculatorComplex::from(0.3))
+        .unwrap();
+    let open_system = MixedLindbladOpenSystem::group(system, noise).unwrap();
+    let cutoffs = [BosonCutoff::Global(4)];
+
+    // The 200^2 rows span several chunks of the parallel construction
+    assert_eq!(
+        open_system.sparse_matrix_superoperator_coo_parallel(&cutoffs),
+        open_system.sparse_matrix_superoperator_coo(&cutoffs)
+    );
+}
+
+// Test the steady_state function of a damped spin-boson system with spin dephasing
+#[test]
+fn steady_state() {
+    let mut system = MixedHamiltonianSystem::new([Some(1)], [Some(1)], []);
+    system
+        .add_operator_product(
+            HermitianMixedProduct::new(
+                [PauliProduct::new().z(0)],
+                [BosonProduct::new([], []).unwrap()],
+                [],
+            )
+            .unwrap(),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    let mut noise = MixedLindbladNoiseSystem::new([Some(1)], [Some(1)], []);
+    for decoherence in [
+        MixedDecoherenceProduct::new(
+            [DecoherenceProduct::new()],
+            [BosonProduct::new([], [0]).unwrap()],
+            [],
+        )
+        .unwrap(),
+        MixedDecoherenceProduct::new(
+            [DecoherenceProduct::new().x(0)],
+            [BosonProduct::new([], []).unwrap()],
+            [],
+        )
+        .unwrap(),
+    ] {
+        noise
+            .add_operator_product(
+                (decoherence.clone(), decoherence),
+                CalculatorComplex::from(1.0),
+            )
+            .unwrap();
+    }
+    let open_system = MixedLindbladOpenSystem::group(system, noise).unwrap();
+
+    // The boson decays into the vacuum and the spin is maximally mixed
+    let steady_state = open_system
+        .steady_state(&[BosonCutoff::Global(2)], 1e-12, 500)
+        .unwrap();
+    assert_eq!(steady_state.dim(), (6, 6));
+    for ((row, column), value) in steady_state.indexed_iter() {
+        let expected = if row == column && row < 2 { 0.5 } else { 0.0 };
+        assert!((value - Complex64::new(expected, 0.0)).norm() < 1e-10);
+    }
+}
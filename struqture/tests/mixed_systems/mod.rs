@@ -39,3 +39,6 @@ pub use mixed_noise_operator::*;
 
 mod mixed_open_system;
 pub use mixed_open_system::*;
+
+mod mixed_sparse_matrices;
+pub use mixed_sparse_matrices::*;
@@ -0,0 +1,46 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the boson model builders
+
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use struqture::bosons::{BosonHamiltonianSystem, HermitianBosonProduct};
+use struqture::models::{bose_hubbard, Boundary, Lattice};
+use struqture::prelude::*;
+
+// Test the bose_hubbard function
+#[test]
+fn bose_hubbard_ladder() {
+    let lattice = Lattice::ladder(2, Boundary::Open);
+    let system = bose_hubbard(&lattice, 1.0.into(), CalculatorFloat::from("U"), 0.0.into());
+
+    let mut expected = BosonHamiltonianSystem::new(Some(4));
+    for (first, second) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+        expected
+            .add_operator_product(
+                HermitianBosonProduct::new([first], [second]).unwrap(),
+                CalculatorComplex::from(-1.0),
+            )
+            .unwrap();
+    }
+    for site in 0..4 {
+        expected
+            .add_operator_product(
+                HermitianBosonProduct::new([site, site], [site, site]).unwrap(),
+                CalculatorComplex::from(CalculatorFloat::from("U") * 0.5),
+            )
+            .unwrap();
+    }
+    assert_eq!(lattice.edges(), &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+    assert_eq!(system, expected);
+    assert_eq!(system.number_modes(), 4);
+}
@@ -0,0 +1,75 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the fermion model builders
+
+use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use struqture::fermions::{FermionHamiltonianSystem, HermitianFermionProduct};
+use struqture::mappings::JordanWignerFermionToSpin;
+use struqture::models::{fermi_hubbard, Boundary, Lattice};
+use struqture::prelude::*;
+use struqture::spins::{PauliProduct, SpinHamiltonianSystem};
+
+// Test the fermi_hubbard function
+#[test]
+fn fermi_hubbard_chain() {
+    let lattice = Lattice::chain(2, Boundary::Open);
+    let system = fermi_hubbard(&lattice, CalculatorFloat::from("t"), 4.0.into(), 0.5.into());
+
+    let mut expected = FermionHamiltonianSystem::new(Some(4));
+    for (creator, annihilator) in [(0, 2), (1, 3)] {
+        expected
+            .add_operator_product(
+                HermitianFermionProduct::new([creator], [annihilator]).unwrap(),
+                CalculatorComplex::from(CalculatorFloat::from("t") * -1.0),
+            )
+            .unwrap();
+    }
+    for site in 0..2 {
+        expected
+            .add_operator_product(
+                HermitianFermionProduct::new([2 * site, 2 * site + 1], [2 * site, 2 * site + 1])
+                    .unwrap(),
+                CalculatorComplex::from(-4.0),
+            )
+            .unwrap();
+        for mode in [2 * site, 2 * site + 1] {
+            expected
+                .add_operator_product(
+                    HermitianFermionProduct::new([mode], [mode]).unwrap(),
+                    CalculatorComplex::from(-0.5),
+                )
+                .unwrap();
+        }
+    }
+    assert_eq!(system, expected);
+    assert_eq!(system.number_modes(), 4);
+}
+
+// Test the sign of the on-site interaction of the fermi_hubbard function
+#[test]
+fn fermi_hubbard_interaction() {
+    let lattice = Lattice::chain(1, Boundary::Open);
+    let system = fermi_hubbard(&lattice, 1.0.into(), 4.0.into(), 0.0.into());
+
+    // U n_up n_down = U/4 (1 - Z_0) (1 - Z_1)
+    let mut expected = SpinHamiltonianSystem::new(Some(2));
+    for (pp, value) in [
+        (PauliProduct::new(), 1.0),
+        (PauliProduct::new().z(0), -1.0),
+        (PauliProduct::new().z(1), -1.0),
+        (PauliProduct::new().z(0).z(1), 1.0),
+    ] {
+        expected.add_operator_product(pp, value.into()).unwrap();
+    }
+    assert_eq!(system.jordan_wigner(), expected);
+}
@@ -0,0 +1,78 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for public API of Lattice
+
+use struqture::models::{Boundary, Lattice};
+use struqture::StruqtureError;
+use test_case::test_case;
+
+// Test the edges of the predefined lattices
+#[test_case(Lattice::chain(1, Boundary::Periodic), 1, &[]; "chain_single")]
+#[test_case(Lattice::chain(2, Boundary::Periodic), 2, &[(0, 1)]; "chain_two_periodic")]
+#[test_case(Lattice::chain(4, Boundary::Open), 4, &[(0, 1), (1, 2), (2, 3)]; "chain_open")]
+#[test_case(Lattice::chain(4, Boundary::Periodic), 4, &[(0, 1), (0, 3), (1, 2), (2, 3)]; "chain_periodic")]
+#[test_case(Lattice::ladder(3, Boundary::Open), 6, &[(0, 1), (0, 3), (1, 2), (1, 4), (2, 5), (3, 4), (4, 5)]; "ladder")]
+#[test_case(Lattice::square(2, 2, Boundary::Open), 4, &[(0, 1), (0, 2), (1, 3), (2, 3)]; "square")]
+#[test_case(Lattice::triangular(2, 2, Boundary::Open), 4, &[(0, 1), (0, 2), (0, 3), (1, 3), (2, 3)]; "triangular")]
+fn edges(lattice: Lattice, number_sites: usize, edges: &[(usize, usize)]) {
+    assert_eq!(lattice.number_sites(), number_sites);
+    assert_eq!(lattice.edges(), edges);
+}
+
+// Test the coordination numbers of periodic two-dimensional lattices
+#[test]
+fn periodic_coordination() {
+    let square = Lattice::square(3, 4, Boundary::Periodic);
+    assert_eq!(square.number_sites(), 12);
+    assert_eq!(square.edges().len(), 12 * 4 / 2);
+
+    let triangular = Lattice::triangular(3, 3, Boundary::Periodic);
+    assert_eq!(triangular.edges().len(), 9 * 6 / 2);
+    for site in 0..9 {
+        let coordination = triangular
+            .edges()
+            .iter()
+            .filter(|(first, second)| *first == site || *second == site)
+            .count();
+        assert_eq!(coordination, 6);
+    }
+
+    let ladder = Lattice::ladder(4, Boundary::Periodic);
+    assert_eq!(ladder.edges().len(), 2 * 4 + 4);
+}
+
+// Test the from_edges function of the Lattice
+#[test]
+fn from_edges() {
+    let lattice = Lattice::from_edges(3, [(2, 0), (0, 2), (1, 0)]).unwrap();
+    assert_eq!(lattice.edges(), &[(0, 1), (0, 2)]);
+    assert_eq!(
+        lattice,
+        Lattice::from_edges(3, vec![(0, 1), (0, 2)]).unwrap()
+    );
+
+    assert_eq!(
+        Lattice::from_edges(3, [(0, 3)]),
+        Err(StruqtureError::InvalidLatticeEdge {
+            first: 0,
+            second: 3
+        })
+    );
+    assert_eq!(
+        Lattice::from_edges(3, [(1, 1)]),
+        Err(StruqtureError::InvalidLatticeEdge {
+            first: 1,
+            second: 1
+        })
+    );
+}
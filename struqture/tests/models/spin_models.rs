@@ -0,0 +1,78 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for the spin model builders
+
+use qoqo_calculator::CalculatorFloat;
+use struqture::models::{heisenberg, transverse_field_ising, xxz, Boundary, Lattice};
+use struqture::prelude::*;
+use struqture::spins::{PauliProduct, SpinHamiltonianSystem};
+
+// Test the heisenberg and xxz functions
+#[test]
+fn heisenberg_xxz() {
+    let lattice = Lattice::chain(3, Boundary::Periodic);
+    let system = xxz(&lattice, CalculatorFloat::from("J"), 0.5.into());
+
+    let mut expected = SpinHamiltonianSystem::new(Some(3));
+    for (first, second) in [(0, 1), (0, 2), (1, 2)] {
+        expected
+            .add_operator_product(
+                PauliProduct::new().x(first).x(second),
+                CalculatorFloat::from("J"),
+            )
+            .unwrap();
+        expected
+            .add_operator_product(
+                PauliProduct::new().y(first).y(second),
+                CalculatorFloat::from("J"),
+            )
+            .unwrap();
+        expected
+            .add_operator_product(PauliProduct::new().z(first).z(second), 0.5.into())
+            .unwrap();
+    }
+    assert_eq!(system, expected);
+    assert_eq!(system.number_spins(), 3);
+
+    let system = heisenberg(&lattice, 2.0.into());
+    assert_eq!(system.len(), 9);
+    assert!(system
+        .values()
+        .all(|value| value == &CalculatorFloat::from(2.0)));
+}
+
+// Test the transverse_field_ising function
+#[test]
+fn transverse_field_ising_square() {
+    let lattice = Lattice::square(2, 2, Boundary::Open);
+    let system = transverse_field_ising(&lattice, (-1.0).into(), CalculatorFloat::from("h"));
+
+    assert_eq!(system.number_spins(), 4);
+    assert_eq!(system.len(), 8);
+    for (first, second) in lattice.edges() {
+        assert_eq!(
+            system.get(&PauliProduct::new().z(*first).z(*second)),
+            &CalculatorFloat::from(-1.0)
+        );
+    }
+    for site in 0..4 {
+        assert_eq!(
+            system.get(&PauliProduct::new().x(site)),
+            &CalculatorFloat::from("h")
+        );
+    }
+
+    // Vanishing couplings do not create terms
+    let system = transverse_field_ising(&lattice, 1.0.into(), 0.0.into());
+    assert_eq!(system.len(), 4);
+}
@@ -0,0 +1,240 @@
+// Copyright © 2021-2022 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for public API of CliffordTableau
+
+use nalgebra as na;
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorComplex;
+use std::f64::consts::FRAC_1_SQRT_2;
+use struqture::prelude::*;
+use struqture::spins::{
+    CliffordTableau, PauliProduct, SingleSpinOperator, SpinOperator, ToSparseMatrixOperator,
+};
+use struqture::StruqtureError;
+use test_case::test_case;
+
+/// Returns the dense matrix of a SpinOperator on a number of spins.
+fn dense(operator: &SpinOperator, number_spins: usize) -> na::DMatrix<Complex64> {
+    let dimension = 2usize.pow(number_spins as u32);
+    let mut matrix = na::DMatrix::zeros(dimension, dimension);
+    for ((row, column), value) in operator.sparse_matrix(Some(number_spins)).unwrap() {
+        matrix[(row, column)] = value;
+    }
+    matrix
+}
+
+/// Returns the SpinOperator sum of PauliProducts with coefficients.
+fn spin_operator(terms: &[(PauliProduct, Complex64)]) -> SpinOperator {
+    let mut operator = SpinOperator::new();
+    for (pp, value) in terms {
+        operator
+            .add_operator_product(pp.clone(), CalculatorComplex::new(value.re, value.im))
+            .unwrap();
+    }
+    operator
+}
+
+// Test the new function of the CliffordTableau
+#[test]
+fn new() {
+    let tableau = CliffordTableau::new(2);
+    assert_eq!(tableau.number_spins(), 2);
+    assert_eq!(tableau.x_image(1), Some(&(PauliProduct::new().x(1), 1.0)));
+    assert_eq!(tableau.z_image(0), Some(&(PauliProduct::new().z(0), 1.0)));
+    assert_eq!(tableau.x_image(2), None);
+
+    let pp = PauliProduct::new().x(0).y(1).z(4);
+    assert_eq!(pp.conjugate_by_clifford(&tableau), (pp.clone(), 1.0));
+}
+
+// Test the single- and two-spin gates on the Pauli operators
+#[test_case(CliffordTableau::new(1).hadamard(0), "0Y", "0Y", -1.0; "hadamard")]
+#[test_case(CliffordTableau::new(1).s(0), "0Y", "0X", -1.0; "s")]
+#[test_case(CliffordTableau::new(1).s(0).s(0), "0X", "0X", -1.0; "z")]
+#[test_case(CliffordTableau::new(2).cnot(0, 1).unwrap(), "1Z", "0Z1Z", 1.0; "cnot")]
+#[test_case(CliffordTableau::new(2).cnot(0, 1).unwrap(), "0Y1Y", "0X1Z", -1.0; "cnot_yy")]
+#[test_case(CliffordTableau::new(2).cz(0, 1).unwrap(), "0Y", "0Y1Z", 1.0; "cz")]
+#[test_case(CliffordTableau::new(2).swap(0, 1).unwrap(), "0X1Y", "0Y1X", 1.0; "swap")]
+#[test_case(CliffordTableau::new(0).hadamard(3), "1X3X", "1X3Z", 1.0; "extended")]
+fn gate_images(tableau: CliffordTableau, input: &str, output: &str, sign: f64) {
+    let pp: PauliProduct = input.parse().unwrap();
+    let expected: PauliProduct = output.parse().unwrap();
+    assert_eq!(pp.conjugate_by_clifford(&tableau), (expected, sign));
+}
+
+// Test the conjugation with a Clifford circuit against the dense unitary of the circuit
+#[test]
+fn conjugate_against_dense_unitary() {
+    let one = Complex64::new(1.0, 0.0);
+    let half = Complex64::new(0.5, 0.0);
+    let hadamard = |spin: usize| {
+        spin_operator(&[
+            (PauliProduct::new().x(spin), one * FRAC_1_SQRT_2),
+            (PauliProduct::new().z(spin), one * FRAC_1_SQRT_2),
+        ])
+    };
+    let s = |spin: usize| {
+        spin_operator(&[
+            (PauliProduct::new(), Complex64::new(0.5, 0.5)),
+            (PauliProduct::new().z(spin), Complex64::new(0.5, -0.5)),
+        ])
+    };
+    let cnot = |control: usize, target: usize| {
+        spin_operator(&[
+            (PauliProduct::new(), half),
+            (PauliProduct::new().z(control), half),
+            (PauliProduct::new().x(target), half),
+            (PauliProduct::new().z(control).x(target), -half),
+        ])
+    };
+    let cz = |first: usize, second: usize| {
+        spin_operator(&[
+            (PauliProduct::new(), half),
+            (PauliProduct::new().z(first), half),
+            (PauliProduct::new().z(second), half),
+            (PauliProduct::new().z(first).z(second), -half),
+        ])
+    };
+    let swap = |first: usize, second: usize| {
+        spin_operator(&[
+            (PauliProduct::new(), half),
+            (PauliProduct::new().x(first).x(second), half),
+            (PauliProduct::new().y(first).y(second), half),
+            (PauliProduct::new().z(first).z(second), half),
+        ])
+    };
+
+    let tableau = CliffordTableau::new(3)
+        .hadamard(0)
+        .s(1)
+        .cnot(0, 2)
+        .unwrap()
+        .cz(1, 2)
+        .unwrap()
+        .swap(0, 1)
+        .unwrap()
+        .hadamard(2)
+        .s(0)
+        .cnot(2, 1)
+        .unwrap();
+    let gates = [
+        hadamard(0),
+        s(1),
+        cnot(0, 2),
+        cz(1, 2),
+        swap(0, 1),
+        hadamard(2),
+        s(0),
+        cnot(2, 1),
+    ];
+    let mut unitary: na::DMatrix<Complex64> = na::DMatrix::identity(8, 8);
+    for gate in gates.iter() {
+        unitary = dense(gate, 3) * unitary;
+    }
+    assert!((&unitary * unitary.adjoint() - na::DMatrix::identity(8, 8)).norm() < 1e-12);
+
+    let paulis = [
+        SingleSpinOperator::Identity,
+        SingleSpinOperator::X,
+        SingleSpinOperator::Y,
+        SingleSpinOperator::Z,
+    ];
+    for index in 0..64 {
+        let pp = (0..3).fold(PauliProduct::new(), |pp, spin| {
+            pp.set_pauli(spin, paulis[(index >> (2 * spin)) & 3])
+        });
+        let (image, sign) = pp.conjugate_by_clifford(&tableau);
+        let expected = &unitary * dense(&spin_operator(&[(pp, one)]), 3) * unitary.adjoint();
+        let result = dense(&spin_operator(&[(image, one * sign)]), 3);
+        assert!((expected - result).norm() < 1e-12);
+    }
+}
+
+// Test the then function of the CliffordTableau
+#[test]
+fn then() {
+    let first = CliffordTableau::new(2).hadamard(0).s(1);
+    let second = CliffordTableau::new(3).cnot(0, 2).unwrap().s(0);
+    let combined = first.then(&second);
+    let sequential = CliffordTableau::new(2)
+        .hadamard(0)
+        .s(1)
+        .cnot(0, 2)
+        .unwrap()
+        .s(0);
+    assert_eq!(combined, sequential);
+    assert_eq!(combined.number_spins(), 3);
+}
+
+// Test the from_images function of the CliffordTableau
+#[test]
+fn from_images() {
+    let gates = CliffordTableau::new(2).hadamard(1).cz(0, 1).unwrap().s(0);
+    let tableau = CliffordTableau::from_images(
+        vec![
+            (PauliProduct::new().y(0).z(1), 1.0),
+            (PauliProduct::new().z(1), 1.0),
+        ],
+        vec![
+            (PauliProduct::new().z(0), 1.0),
+            (PauliProduct::new().z(0).x(1), 1.0),
+        ],
+    )
+    .unwrap();
+    assert_eq!(tableau, gates);
+
+    let tableau = CliffordTableau::from_images(
+        vec![(PauliProduct::new().z(0), -1.0)],
+        vec![(PauliProduct::new().x(0), 1.0)],
+    )
+    .unwrap();
+    assert_eq!(
+        PauliProduct::new().y(0).conjugate_by_clifford(&tableau),
+        (PauliProduct::new().y(0), 1.0)
+    );
+}
+
+// Test the errors of the from_images function of the CliffordTableau
+#[test_case(vec![("0X", 1.0)], vec![]; "lengths")]
+#[test_case(vec![("0X", 0.5)], vec![("0Z", 1.0)]; "sign")]
+#[test_case(vec![("0X", 1.0)], vec![("0Z1Z", 1.0)]; "outside")]
+#[test_case(vec![("0X", 1.0)], vec![("0X", 1.0)]; "commuting")]
+#[test_case(vec![("0X", 1.0), ("0Y", 1.0)], vec![("0Z", 1.0), ("1Z", 1.0)]; "anticommuting")]
+fn from_images_error(x_images: Vec<(&str, f64)>, z_images: Vec<(&str, f64)>) {
+    let parse = |images: Vec<(&str, f64)>| -> Vec<(PauliProduct, f64)> {
+        images
+            .into_iter()
+            .map(|(pp, sign)| (pp.parse().unwrap(), sign))
+            .collect()
+    };
+    let result = CliffordTableau::from_images(parse(x_images), parse(z_images));
+    assert!(matches!(
+        result,
+        Err(StruqtureError::InvalidClifford { .. })
+    ));
+}
+
+// Test the errors of the two-spin gates of the CliffordTableau
+#[test]
+fn two_spin_gate_error() {
+    for result in [
+        CliffordTableau::new(2).cnot(1, 1),
+        CliffordTableau::new(2).cz(0, 0),
+        CliffordTableau::new(2).swap(3, 3),
+    ] {
+        assert!(matches!(
+            result,
+            Err(StruqtureError::InvalidClifford { .. })
+        ));
+    }
+}
@@ -374,6 +374,55 @@ fn mul_so_so_same_index() {
     assert_eq!(so_0 * so_1, so_0_1);
 }
 
+// Test the commutator and anticommutator of DecoherenceOperators against the explicit products
+#[test]
+fn commutator_anticommutator() {
+    let mut so_0 = DecoherenceOperator::new();
+    so_0.add_operator_product(DecoherenceProduct::new().z(0), CalculatorComplex::from(2.0))
+        .unwrap();
+    so_0.add_operator_product(
+        DecoherenceProduct::new().x(1).iy(2),
+        CalculatorComplex::new(0.0, 0.5),
+    )
+    .unwrap();
+    let mut so_1 = DecoherenceOperator::new();
+    so_1.add_operator_product(
+        DecoherenceProduct::new().x(0).z(1),
+        CalculatorComplex::from(1.5),
+    )
+    .unwrap();
+    so_1.add_operator_product(
+        DecoherenceProduct::new().iy(2),
+        CalculatorComplex::from(0.25),
+    )
+    .unwrap();
+    so_1.add_operator_product(
+        DecoherenceProduct::new().z(3),
+        CalculatorComplex::from(-1.0),
+    )
+    .unwrap();
+
+    let forward = so_0.clone() * so_1.clone();
+    let backward = so_1.clone() * so_0.clone();
+    assert_eq!(so_0.commutator(&so_1), forward.clone() - backward.clone());
+    assert_eq!(so_0.anticommutator(&so_1), forward + backward);
+
+    let mut z = DecoherenceOperator::new();
+    z.add_operator_product(DecoherenceProduct::new().z(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    let mut x = DecoherenceOperator::new();
+    x.add_operator_product(DecoherenceProduct::new().x(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    let mut iy = DecoherenceOperator::new();
+    iy.add_operator_product(
+        DecoherenceProduct::new().iy(0),
+        CalculatorComplex::from(2.0),
+    )
+    .unwrap();
+    assert_eq!(z.commutator(&x), iy);
+    assert_eq!(z.anticommutator(&x), DecoherenceOperator::new());
+}
+
 // Test the multiplication: DecoherenceOperator * Calculatorcomplex
 #[test]
 fn mul_so_cc() {
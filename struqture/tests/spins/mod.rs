@@ -43,6 +43,9 @@ pub use spin_noise_system::*;
 mod spin_open_system;
 pub use spin_open_system::*;
 
+mod clifford_tableau;
+pub use clifford_tableau::*;
+
 fn create_na_matrix_from_operator_list(operators: &[&str]) -> na::DMatrix<Complex64> {
     let cc1 = Complex64::new(1.0, 0.0);
     let cc0 = Complex64::new(0.0, 0.0);
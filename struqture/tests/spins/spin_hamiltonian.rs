@@ -14,7 +14,7 @@
 
 use super::create_na_matrix_from_operator_list;
 use num_complex::Complex64;
-use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use qoqo_calculator::{Calculator, CalculatorComplex, CalculatorFloat};
 use serde_test::{assert_tokens, Configure, Token};
 use std::collections::{BTreeMap, HashMap};
 use std::iter::{FromIterator, IntoIterator};
@@ -22,8 +22,8 @@ use std::ops::{Add, Sub};
 use std::str::FromStr;
 use struqture::prelude::*;
 use struqture::spins::{
-    OperateOnSpins, PauliProduct, SingleSpinOperator, SpinHamiltonian, SpinOperator,
-    ToSparseMatrixOperator,
+    CliffordTableau, OperateOnSpins, PauliProduct, SingleSpinOperator, SpinHamiltonian,
+    SpinOperator, ToSparseMatrixOperator,
 };
 use struqture::{OperateOnDensityMatrix, SpinIndex, StruqtureError};
 use test_case::test_case;
@@ -340,6 +340,29 @@ fn mul_so_so_same_index() {
     assert_eq!(so_0 * so_1, so_0_1);
 }
 
+// Test the commutator and anticommutator of SpinHamiltonians against the explicit products
+#[test]
+fn commutator_anticommutator() {
+    let mut sh_0 = SpinHamiltonian::new();
+    sh_0.add_operator_product(PauliProduct::new().x(0), CalculatorFloat::from(2.0))
+        .unwrap();
+    sh_0.add_operator_product(PauliProduct::new().z(1).y(2), CalculatorFloat::from(0.5))
+        .unwrap();
+    let mut sh_1 = SpinHamiltonian::new();
+    sh_1.add_operator_product(PauliProduct::new().y(0).z(1), CalculatorFloat::from(1.5))
+        .unwrap();
+    sh_1.add_operator_product(PauliProduct::new().x(2), CalculatorFloat::from(0.25))
+        .unwrap();
+    sh_1.add_operator_product(PauliProduct::new().z(3), CalculatorFloat::from(-1.0))
+        .unwrap();
+
+    let forward = sh_0.clone() * sh_1.clone();
+    let backward = sh_1.clone() * sh_0.clone();
+    assert_eq!(sh_0.commutator(&sh_1), forward.clone() - backward.clone());
+    assert_eq!(sh_0.anticommutator(&sh_1), forward + backward);
+    assert_eq!(sh_0.commutator(&sh_0), SpinOperator::new());
+}
+
 // Test the multiplication: SpinHamiltonian * Calculatorcomplex
 #[test]
 fn mul_so_cc() {
@@ -756,3 +779,188 @@ fn sparse_lindblad_entries() {
         Complex64::default()
     );
 }
+
+// Test the symbolic derivative of the SpinHamiltonian
+#[test]
+fn derivative() {
+    let mut sh = SpinHamiltonian::new();
+    sh.set(PauliProduct::new().x(0), CalculatorFloat::from("theta"))
+        .unwrap();
+    sh.set(
+        PauliProduct::new().z(1),
+        CalculatorFloat::from("sin(2 * theta) + phi / theta"),
+    )
+    .unwrap();
+    sh.set(PauliProduct::new().y(2), CalculatorFloat::from("phi"))
+        .unwrap();
+    sh.set(PauliProduct::new().z(0), CalculatorFloat::from(0.5))
+        .unwrap();
+
+    let derivative = sh.derivative("theta").unwrap();
+    assert_eq!(derivative.len(), 2);
+    assert_eq!(
+        derivative.get(&PauliProduct::new().x(0)),
+        &CalculatorFloat::from(1.0)
+    );
+
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", 0.3);
+    calculator.set_variable("phi", 2.0);
+    let value = derivative
+        .substitute_parameters(&calculator)
+        .unwrap()
+        .get(&PauliProduct::new().z(1))
+        .clone();
+    let expected = 2.0 * (0.6_f64).cos() - 2.0 / (0.3 * 0.3);
+    assert!((*value.float().unwrap() - expected).abs() < 1e-12);
+
+    assert!(sh.derivative("psi").unwrap().is_empty());
+
+    let mut sh = SpinHamiltonian::new();
+    sh.set(PauliProduct::new().x(0), CalculatorFloat::from("theta +"))
+        .unwrap();
+    assert!(matches!(
+        sh.derivative("theta"),
+        Err(StruqtureError::ParsingError { .. })
+    ));
+    sh.set(
+        PauliProduct::new().x(0),
+        CalculatorFloat::from("pow(theta)"),
+    )
+    .unwrap();
+    assert!(matches!(
+        sh.derivative("theta"),
+        Err(StruqtureError::ParsingError { .. })
+    ));
+    sh.set(
+        PauliProduct::new().x(0),
+        CalculatorFloat::from("floor(theta) + 1.0"),
+    )
+    .unwrap();
+    assert_eq!(
+        sh.derivative("theta"),
+        Err(StruqtureError::NonDifferentiableFunction {
+            function: "floor".to_string()
+        })
+    );
+    // Functions that do not depend on the parameter need not be differentiable
+    assert!(sh.derivative("phi").unwrap().is_empty());
+
+    // Numbers are printed without a trailing fractional part
+    sh.set(PauliProduct::new().x(0), CalculatorFloat::from("theta^3"))
+        .unwrap();
+    assert_eq!(
+        sh.derivative("theta")
+            .unwrap()
+            .get(&PauliProduct::new().x(0)),
+        &CalculatorFloat::from("3 * (theta)^(2)")
+    );
+}
+
+// Test that symbolic derivatives can be evaluated by the Calculator
+#[test_case("x^theta", 0.5, 3.0_f64.powf(0.5) * 3.0_f64.ln(); "power_parameter_exponent")]
+#[test_case("theta^theta", 1.5, 1.5_f64.powf(1.5) * (1.5_f64.ln() + 1.0); "power_parameter_base_exponent")]
+#[test_case("abs(theta)", -0.7, -1.0; "abs")]
+#[test_case("log(2 * theta)", 2.0, 0.5; "log")]
+#[test_case("pow(theta, 3)", 2.0, 12.0; "pow")]
+#[test_case("pow(2, theta)", 1.5, 2.0_f64.powf(1.5) * 2.0_f64.ln(); "pow_parameter_exponent")]
+#[test_case("atan2(theta, x)", 0.5, 3.0 / 9.25; "atan2_first")]
+#[test_case("atan2(x, theta)", 0.5, -3.0 / 9.25; "atan2_second")]
+#[test_case("hypot(theta, x)", 4.0, 0.8; "hypot")]
+#[test_case("max(theta, x)", 4.0, 1.0; "max_first")]
+#[test_case("max(theta, x)", 2.0, 0.0; "max_second")]
+#[test_case("min(theta * theta, x)", 1.0, 2.0; "min")]
+#[test_case("acosh(theta)", 2.0, 1.0 / 3.0_f64.sqrt(); "acosh")]
+#[test_case("asinh(2 * theta)", 0.5, 2.0 / 2.0_f64.sqrt(); "asinh")]
+#[test_case("atanh(theta)", 0.5, 1.0 / 0.75; "atanh")]
+#[test_case("erf(theta)", 0.5, 2.0 / std::f64::consts::PI.sqrt() * (-0.25_f64).exp(); "erf")]
+#[test_case("exp2(theta)", 1.5, 2.0_f64.powf(1.5) * 2.0_f64.ln(); "exp2")]
+#[test_case("expm1(theta)", 0.3, 0.3_f64.exp(); "expm1")]
+#[test_case("log10(theta)", 2.0, 1.0 / (2.0 * 10.0_f64.ln()); "log10")]
+#[test_case("cbrt(theta)", 8.0, 1.0 / 12.0; "cbrt")]
+fn derivative_evaluation(expression: &str, theta: f64, expected: f64) {
+    let mut sh = SpinHamiltonian::new();
+    sh.set(PauliProduct::new().x(0), CalculatorFloat::from(expression))
+        .unwrap();
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", theta);
+    calculator.set_variable("x", 3.0);
+    let value = sh
+        .derivative("theta")
+        .unwrap()
+        .substitute_parameters(&calculator)
+        .unwrap()
+        .get(&PauliProduct::new().x(0))
+        .clone();
+    assert!((*value.float().unwrap() - expected).abs() < 1e-12);
+}
+
+// Test the conjugate_by_clifford function of the SpinHamiltonian
+#[test]
+fn conjugate_by_clifford() {
+    let tableau = CliffordTableau::new(2).s(0).swap(0, 1).unwrap();
+    let mut sh = SpinHamiltonian::new();
+    sh.add_operator_product(PauliProduct::new().x(0).z(1), CalculatorFloat::from(0.3))
+        .unwrap();
+    sh.add_operator_product(PauliProduct::new().y(0), CalculatorFloat::from("b"))
+        .unwrap();
+
+    let mut expected = SpinHamiltonian::new();
+    expected
+        .add_operator_product(PauliProduct::new().z(0).y(1), CalculatorFloat::from(0.3))
+        .unwrap();
+    expected
+        .add_operator_product(PauliProduct::new().x(1), CalculatorFloat::from("b") * -1.0)
+        .unwrap();
+    assert_eq!(sh.conjugate_by_clifford(&tableau), expected);
+}
+
+// Test the qubit_wise_commuting_groups and commuting_groups functions of the SpinHamiltonian
+#[test]
+fn commuting_groups() {
+    let hamiltonian_from = |terms: &[(&str, f64)]| {
+        let mut sh = SpinHamiltonian::new();
+        for (pp, value) in terms {
+            sh.add_operator_product(PauliProduct::from_str(pp).unwrap(), (*value).into())
+                .unwrap();
+        }
+        sh
+    };
+
+    // Transverse field Ising chain: Z and X terms form two qubit-wise commuting groups
+    let sh = hamiltonian_from(&[
+        ("0Z1Z", 1.0),
+        ("1Z2Z", 1.0),
+        ("0X", 0.5),
+        ("1X", 0.5),
+        ("2X", 0.5),
+        ("0X1X", 0.2),
+    ]);
+    let groups = sh.qubit_wise_commuting_groups();
+    assert_eq!(
+        groups,
+        vec![
+            (
+                hamiltonian_from(&[("0Z1Z", 1.0), ("1Z2Z", 1.0)]),
+                PauliProduct::new().z(0).z(1).z(2)
+            ),
+            (
+                hamiltonian_from(&[("0X", 0.5), ("1X", 0.5), ("2X", 0.5), ("0X1X", 0.2)]),
+                PauliProduct::new().x(0).x(1).x(2)
+            ),
+        ]
+    );
+
+    // Heisenberg coupling: the terms commute, but not qubit-wise
+    let sh = hamiltonian_from(&[("0X1X", 1.0), ("0Y1Y", 2.0), ("0Z1Z", 3.0)]);
+    assert_eq!(sh.commuting_groups(), vec![sh.clone()]);
+    let groups = sh.qubit_wise_commuting_groups();
+    assert_eq!(groups.len(), 3);
+    for ((group, basis), pp) in groups.iter().zip(["0X1X", "0Y1Y", "0Z1Z"]) {
+        assert_eq!(group.len(), 1);
+        assert_eq!(basis, &PauliProduct::from_str(pp).unwrap());
+        assert_eq!(group.get(basis), sh.get(basis));
+    }
+
+    assert!(SpinHamiltonian::new().commuting_groups().is_empty());
+}
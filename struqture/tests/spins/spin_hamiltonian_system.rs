@@ -13,17 +13,18 @@
 //! Integration test for public API of SpinHamiltonianSystem
 
 use super::create_na_matrix_from_operator_list;
+use nalgebra as na;
 use num_complex::Complex64;
-use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use qoqo_calculator::{Calculator, CalculatorComplex, CalculatorError, CalculatorFloat};
 use serde_test::{assert_tokens, Configure, Token};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::iter::{FromIterator, IntoIterator};
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 use struqture::prelude::*;
 use struqture::spins::{
-    OperateOnSpins, PauliProduct, SpinHamiltonian, SpinHamiltonianSystem, SpinSystem,
-    ToSparseMatrixOperator, ToSparseMatrixSuperOperator,
+    OperateOnSpins, PauliProduct, SpinHamiltonian, SpinHamiltonianSystem, SpinSymmetrySector,
+    SpinSystem, ToSparseMatrixOperator, ToSparseMatrixSuperOperator,
 };
 use struqture::{CooSparseMatrix, OperateOnDensityMatrix, SpinIndex, StruqtureError};
 use test_case::test_case;
@@ -1006,3 +1007,323 @@ fn test_truncate() {
     let comparison_system2 = system.truncate(0.5);
     assert_eq!(test_system2, comparison_system2);
 }
+
+// Test the substitution of symbolic parameters in the SpinHamiltonianSystem
+#[test]
+fn substitute_parameters() {
+    let mut system = SpinHamiltonianSystem::new(Some(3));
+    system
+        .set(PauliProduct::new().x(0), CalculatorFloat::from("theta"))
+        .unwrap();
+    system
+        .set(
+            PauliProduct::new().z(1).z(2),
+            CalculatorFloat::from("2 * theta + phi"),
+        )
+        .unwrap();
+    system
+        .set(PauliProduct::new().y(2), CalculatorFloat::from(0.3))
+        .unwrap();
+
+    let mut calculator = Calculator::new();
+    calculator.set_variable("theta", 0.5);
+    assert_eq!(
+        system.substitute_parameters(&calculator),
+        Err(StruqtureError::CalculatorError(
+            CalculatorError::VariableNotSet {
+                name: "phi".to_string()
+            }
+        ))
+    );
+
+    calculator.set_variable("phi", -1.0);
+    let mut expected = SpinHamiltonianSystem::new(Some(3));
+    expected
+        .set(PauliProduct::new().x(0), CalculatorFloat::from(0.5))
+        .unwrap();
+    expected
+        .set(PauliProduct::new().y(2), CalculatorFloat::from(0.3))
+        .unwrap();
+    let substituted = system.substitute_parameters(&calculator).unwrap();
+    assert_eq!(substituted, expected);
+    assert_eq!(substituted.number_spins(), 3);
+}
+
+// Test the enumeration of the symbolic parameters in the SpinHamiltonianSystem
+#[test]
+fn free_parameters() {
+    let mut system = SpinHamiltonianSystem::new(Some(3));
+    assert!(system.free_parameters().is_empty());
+    system
+        .set(PauliProduct::new().x(0), CalculatorFloat::from("theta"))
+        .unwrap();
+    system
+        .set(
+            PauliProduct::new().z(1).z(2),
+            CalculatorFloat::from("2.5e-3 * sin(theta_1) + pi * exp(phi2)"),
+        )
+        .unwrap();
+    system
+        .set(PauliProduct::new().y(2), CalculatorFloat::from(0.3))
+        .unwrap();
+    // Names followed by an opening parenthesis are functions, all other names are parameters
+    system
+        .set(
+            PauliProduct::new().x(1),
+            CalculatorFloat::from("pow(e, 2) + hypot(x, theta) * erf(1.0)"),
+        )
+        .unwrap();
+
+    // Assigned names are local variables, unless they are used before the assignment
+    system
+        .set(
+            PauliProduct::new().z(0),
+            CalculatorFloat::from("a = 2 * alpha; b = beta + b_0; a * b * gamma"),
+        )
+        .unwrap();
+    system
+        .set(
+            PauliProduct::new().y(0),
+            CalculatorFloat::from("delta = delta * 2; delta"),
+        )
+        .unwrap();
+
+    let expected: HashSet<String> = [
+        "theta", "theta_1", "phi2", "pi", "e", "x", "alpha", "beta", "b_0", "gamma", "delta",
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect();
+    assert_eq!(system.free_parameters(), expected);
+}
+
+// Test the expectation values of a SpinHamiltonianSystem with a symbolic coefficient
+#[test]
+fn expectation_value() {
+    let mut system = SpinHamiltonianSystem::new(Some(2));
+    system
+        .add_operator_product(PauliProduct::new().z(0), CalculatorFloat::from("a"))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().x(1), CalculatorFloat::from(0.5))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().y(0).y(1), CalculatorFloat::from(-2.0))
+        .unwrap();
+
+    // |psi> = (|00> + |11>) / sqrt(2) has <Z0> = 0, <X1> = 0 and <Y0 Y1> = -1
+    let amplitude = Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0);
+    let state = vec![
+        amplitude,
+        Complex64::default(),
+        Complex64::default(),
+        amplitude,
+    ];
+    let density_matrix: Vec<Complex64> = (0..16)
+        .map(|k| state[k / 4] * state[k % 4].conj())
+        .collect();
+    let mut calculator = Calculator::new();
+    calculator.set_variable("a", 3.0);
+    for result in [
+        system.expectation_value(&state).unwrap(),
+        system
+            .expectation_value_density_matrix(&density_matrix)
+            .unwrap(),
+    ] {
+        let value = result.substitute_parameters(&calculator).unwrap();
+        assert!((*value.float().unwrap() - 2.0).abs() < 1e-12);
+    }
+
+    // |psi> = |00> has <Z0> = 1
+    let state = vec![
+        Complex64::new(1.0, 0.0),
+        Complex64::default(),
+        Complex64::default(),
+        Complex64::default(),
+    ];
+    let value = system
+        .expectation_value(&state)
+        .unwrap()
+        .substitute_parameters(&calculator)
+        .unwrap();
+    assert!((*value.float().unwrap() - 3.0).abs() < 1e-12);
+}
+
+// Test the lowest_eigenvalues and lowest_eigenstates functions of the SpinHamiltonianSystem
+#[test]
+fn lowest_eigenvalues() {
+    // Open Heisenberg chain with a singlet ground state and a degenerate triplet above it
+    let mut system = SpinHamiltonianSystem::new(None);
+    for spin in 0..3 {
+        for operator in ["X", "Y", "Z"] {
+            let pp =
+                PauliProduct::from_str(&format!("{}{}{}{}", spin, operator, spin + 1, operator))
+                    .unwrap();
+            system.add_operator_product(pp, 1.0.into()).unwrap();
+        }
+    }
+
+    let mut dense: na::DMatrix<Complex64> = na::DMatrix::zeros(16, 16);
+    for ((row, column), value) in system.sparse_matrix(None).unwrap() {
+        dense[(row, column)] = value;
+    }
+    let mut expected: Vec<f64> = dense
+        .symmetric_eigen()
+        .eigenvalues
+        .iter()
+        .copied()
+        .collect();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let eigenvalues = system.lowest_eigenvalues(5, None, 1e-10, 10000).unwrap();
+    assert_eq!(eigenvalues.len(), 5);
+    for (value, reference) in eigenvalues.iter().zip(expected.iter()) {
+        assert!((value - reference).abs() < 1e-8);
+    }
+
+    let eigenstates = system.lowest_eigenstates(3, Some(5), 1e-10, 10000).unwrap();
+    assert_eq!(eigenstates.len(), 3);
+    // On five spins the decoupled fifth spin makes every level doubly degenerate
+    let dense_five_spins: na::DMatrix<Complex64> = {
+        let mut matrix = na::DMatrix::zeros(32, 32);
+        for ((row, column), value) in system.sparse_matrix(Some(5)).unwrap() {
+            matrix[(row, column)] = value;
+        }
+        matrix
+    };
+    let mut expected_five_spins: Vec<f64> = dense_five_spins
+        .clone()
+        .symmetric_eigen()
+        .eigenvalues
+        .iter()
+        .copied()
+        .collect();
+    expected_five_spins.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (index, (value, vector)) in eigenstates.iter().enumerate() {
+        assert!((value - expected_five_spins[index]).abs() < 1e-8);
+        let vector = na::DVector::from_vec(vector.clone());
+        assert!((vector.norm() - 1.0).abs() < 1e-10);
+        let residual = &dense_five_spins * &vector - &vector * Complex64::new(*value, 0.0);
+        assert!(residual.norm() < 1e-6);
+        for (_, other) in eigenstates.iter().skip(index + 1) {
+            let other = na::DVector::from_vec(other.clone());
+            assert!(vector.dotc(&other).norm() < 1e-8);
+        }
+    }
+
+    let error = system.lowest_eigenvalues(1, Some(3), 1e-10, 10000);
+    assert_eq!(error, Err(StruqtureError::NumberSpinsExceeded));
+}
+
+// Test the qubit_wise_commuting_groups and commuting_groups functions of the SpinHamiltonianSystem
+#[test]
+fn commuting_groups() {
+    let mut system = SpinHamiltonianSystem::new(Some(3));
+    system
+        .add_operator_product(PauliProduct::new().z(0).z(1), 1.0.into())
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().x(1), 0.5.into())
+        .unwrap();
+
+    let groups = system.qubit_wise_commuting_groups();
+    assert_eq!(groups.len(), 2);
+    for (group, basis) in groups.iter() {
+        assert_eq!(group.number_spins(), 3);
+        assert_eq!(group.len(), 1);
+        assert_eq!(group.get(basis), system.get(basis));
+    }
+
+    let groups = system.commuting_groups();
+    assert_eq!(groups.len(), 2);
+    assert!(groups.iter().all(|group| group.number_spins() == 3));
+}
+
+// Test the sparse_matrix_coo_sector function of the SpinHamiltonianSystem
+#[test]
+fn sparse_matrix_coo_sector() {
+    // XXZ chain with a magnetic field conserves the number of spins up
+    let mut system = SpinHamiltonianSystem::new(Some(4));
+    for spin in 0..3 {
+        for (operator, value) in [("X", 1.0), ("Y", 1.0), ("Z", 0.5)] {
+            let pp =
+                PauliProduct::from_str(&format!("{}{}{}{}", spin, operator, spin + 1, operator))
+                    .unwrap();
+            system.add_operator_product(pp, value.into()).unwrap();
+        }
+        system
+            .add_operator_product(PauliProduct::new().z(spin), 0.3.into())
+            .unwrap();
+    }
+    let full = system.sparse_matrix(None).unwrap();
+
+    let mut total_dimension = 0;
+    for number_up in 0..=4 {
+        let sector = SpinSymmetrySector::NumberUp(number_up);
+        let ((values, (rows, columns)), basis) =
+            system.sparse_matrix_coo_sector(sector, None).unwrap();
+        assert_eq!(basis, sector.basis(4));
+        assert!(basis
+            .iter()
+            .all(|state| 4 - state.count_ones() as usize == number_up));
+        total_dimension += basis.len();
+        let restricted: HashMap<(usize, usize), Complex64> = rows
+            .iter()
+            .zip(columns.iter())
+            .zip(values.iter())
+            .map(|((row, column), value)| ((*row, *column), *value))
+            .collect();
+        for (row, row_state) in basis.iter().enumerate() {
+            for (column, column_state) in basis.iter().enumerate() {
+                let expected = full
+                    .get(&(*row_state, *column_state))
+                    .copied()
+                    .unwrap_or_default();
+                let value = restricted.get(&(row, column)).copied().unwrap_or_default();
+                assert!((value - expected).norm() < 1e-12);
+            }
+        }
+    }
+    assert_eq!(total_dimension, 16);
+    assert_eq!(SpinSymmetrySector::NumberUp(2).basis(4).len(), 6);
+
+    // A transverse field only conserves the parity
+    system
+        .add_operator_product(PauliProduct::new().x(0).x(3), 0.2.into())
+        .unwrap();
+    let ((_, (rows, _)), basis) = system
+        .sparse_matrix_coo_sector(SpinSymmetrySector::EvenParity, None)
+        .unwrap();
+    assert_eq!(basis.len(), 8);
+    assert!(rows.iter().all(|row| *row < 8));
+    assert!(matches!(
+        system.sparse_matrix_coo_sector(SpinSymmetrySector::NumberUp(2), None),
+        Err(StruqtureError::SymmetryNotConserved { .. })
+    ));
+
+    system
+        .add_operator_product(PauliProduct::new().x(1), 0.1.into())
+        .unwrap();
+    assert!(matches!(
+        system.sparse_matrix_coo_sector(SpinSymmetrySector::OddParity, None),
+        Err(StruqtureError::SymmetryNotConserved { .. })
+    ));
+}
+
+// Test that the directly enumerated basis of a symmetry sector matches the filtered full basis
+#[test]
+fn symmetry_sector_basis() {
+    for number_spins in 0..=6 {
+        let mut sectors: Vec<SpinSymmetrySector> = (0..=number_spins + 1)
+            .map(SpinSymmetrySector::NumberUp)
+            .collect();
+        sectors.push(SpinSymmetrySector::EvenParity);
+        sectors.push(SpinSymmetrySector::OddParity);
+        for sector in sectors {
+            let expected: Vec<usize> = (0..2usize.pow(number_spins as u32))
+                .filter(|state| sector.contains(*state, number_spins))
+                .collect();
+            assert_eq!(sector.basis(number_spins), expected);
+        }
+    }
+}
@@ -23,7 +23,9 @@ use std::iter::{FromIterator, IntoIterator};
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 use struqture::prelude::*;
-use struqture::spins::{DecoherenceProduct, SpinLindbladNoiseOperator, SpinLindbladNoiseSystem};
+use struqture::spins::{
+    DecoherenceOperator, DecoherenceProduct, SpinLindbladNoiseOperator, SpinLindbladNoiseSystem,
+};
 use struqture::{CooSparseMatrix, OperateOnDensityMatrix, SpinIndex, StruqtureError};
 use test_case::test_case;
 
@@ -757,3 +759,114 @@ fn unitary_matrix() {
     let unitary_matrix: CooSparseMatrix = (vec![], (vec![], vec![]));
     assert_eq!(system.unitary_sparse_matrix_coo().unwrap(), unitary_matrix);
 }
+
+// Test the jump_operators function of the SpinLindbladNoiseSystem for a diagonal rate matrix
+#[test]
+fn jump_operators_diagonal() {
+    let x0 = DecoherenceProduct::new().x(0);
+    let z1 = DecoherenceProduct::new().z(1);
+    let mut system = SpinLindbladNoiseSystem::new(Some(2));
+    system
+        .set((x0.clone(), x0.clone()), CalculatorComplex::from(2.0))
+        .unwrap();
+    system
+        .set((z1.clone(), z1.clone()), CalculatorComplex::from(0.5))
+        .unwrap();
+
+    let mut jump_x = DecoherenceOperator::new();
+    jump_x
+        .set(x0, CalculatorComplex::from(2.0_f64.sqrt()))
+        .unwrap();
+    let mut jump_z = DecoherenceOperator::new();
+    jump_z
+        .set(z1, CalculatorComplex::from(0.5_f64.sqrt()))
+        .unwrap();
+
+    let jump_operators = system.jump_operators().unwrap();
+    assert_eq!(jump_operators.len(), 2);
+    assert!(jump_operators.contains(&jump_x));
+    assert!(jump_operators.contains(&jump_z));
+}
+
+// Test the jump_operators function of the SpinLindbladNoiseSystem for a correlated rate matrix
+#[test]
+fn jump_operators_correlated() {
+    let x0 = DecoherenceProduct::new().x(0);
+    let iy0 = DecoherenceProduct::new().iy(0);
+    let mut system = SpinLindbladNoiseSystem::new(Some(1));
+    // Amplitude damping with the jump operator (X + iY) / 2
+    for left in [x0.clone(), iy0.clone()] {
+        for right in [x0.clone(), iy0.clone()] {
+            system
+                .set((left.clone(), right), CalculatorComplex::from(0.25))
+                .unwrap();
+        }
+    }
+
+    let jump_operators = system.jump_operators().unwrap();
+    assert_eq!(jump_operators.len(), 1);
+    let coefficient_x = Complex64::new(
+        *jump_operators[0].get(&x0).re.float().unwrap(),
+        *jump_operators[0].get(&x0).im.float().unwrap(),
+    );
+    let coefficient_iy = Complex64::new(
+        *jump_operators[0].get(&iy0).re.float().unwrap(),
+        *jump_operators[0].get(&iy0).im.float().unwrap(),
+    );
+    // The jump operator is unique up to a global phase
+    assert!((coefficient_x.norm() - 0.5).abs() < 1e-12);
+    assert!((coefficient_x - coefficient_iy).norm() < 1e-12);
+
+    let mut reconstructed = SpinLindbladNoiseOperator::new();
+    reconstructed
+        .add_noise_from_full_operators(
+            &jump_operators[0],
+            &jump_operators[0],
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    for (key, value) in system.iter() {
+        let reconstructed_value = reconstructed.get(key);
+        assert!(
+            (*value.re.float().unwrap() - *reconstructed_value.re.float().unwrap()).abs() < 1e-12
+        );
+        assert!(
+            (*value.im.float().unwrap() - *reconstructed_value.im.float().unwrap()).abs() < 1e-12
+        );
+    }
+}
+
+// Test the jump_operators function of the SpinLindbladNoiseSystem for invalid rate matrices
+#[test]
+fn jump_operators_error() {
+    let x0 = DecoherenceProduct::new().x(0);
+    let z0 = DecoherenceProduct::new().z(0);
+
+    let mut system = SpinLindbladNoiseSystem::new(Some(1));
+    system
+        .set((x0.clone(), x0.clone()), CalculatorComplex::from(-1.0))
+        .unwrap();
+    assert_eq!(
+        system.jump_operators(),
+        Err(StruqtureError::NegativeRate { eigenvalue: -1.0 })
+    );
+
+    let mut system = SpinLindbladNoiseSystem::new(Some(1));
+    system.set((x0, z0), CalculatorComplex::from(1.0)).unwrap();
+    assert_eq!(
+        system.jump_operators(),
+        Err(StruqtureError::NonHermitianRateMatrix)
+    );
+
+    let mut system = SpinLindbladNoiseSystem::new(Some(1));
+    system
+        .set(
+            (
+                DecoherenceProduct::new().z(0),
+                DecoherenceProduct::new().z(0),
+            ),
+            CalculatorComplex::from("rate"),
+        )
+        .unwrap();
+    assert!(system.jump_operators().is_err());
+}
@@ -16,16 +16,18 @@ use super::create_na_matrix_from_decoherence_list;
 use super::create_na_matrix_from_operator_list;
 use nalgebra as na;
 use num_complex::Complex64;
-use qoqo_calculator::{CalculatorComplex, CalculatorFloat};
+use qoqo_calculator::{Calculator, CalculatorComplex, CalculatorFloat};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde_test::{assert_tokens, Configure, Token};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 use struqture::prelude::*;
 use struqture::spins::{
     DecoherenceProduct, PauliProduct, SpinHamiltonianSystem, SpinLindbladNoiseSystem,
-    SpinLindbladOpenSystem,
+    SpinLindbladOpenSystem, SpinOperator, SpinSuperoperatorSector,
 };
-use struqture::SpinIndex;
+use struqture::{SpinIndex, StruqtureError};
 use test_case::test_case;
 
 // Test the new function of the SpinLindbladOpenSystem
@@ -991,6 +993,73 @@ fn test_operator(pauli_representation: &str, pauli_operators: &[&str]) {
     }
 }
 
+// Test the matrix-free application of the superoperator against the sparse superoperator matrix
+#[test]
+fn apply_superoperator() {
+    let mut system = SpinHamiltonianSystem::new(Some(3));
+    system
+        .add_operator_product(PauliProduct::new().x(0).y(1), CalculatorFloat::from(0.7))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(1).z(2), CalculatorFloat::from(-0.2))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().y(2), CalculatorFloat::from(0.3))
+        .unwrap();
+    let mut noise = SpinLindbladNoiseSystem::new(Some(3));
+    noise
+        .add_operator_product(
+            (
+                DecoherenceProduct::new().z(0),
+                DecoherenceProduct::new().z(0),
+            ),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+    noise
+        .add_operator_product(
+            (
+                DecoherenceProduct::new().x(0).iy(2),
+                DecoherenceProduct::new().iy(1),
+            ),
+            CalculatorComplex::new(0.3, 0.1),
+        )
+        .unwrap();
+    noise
+        .add_operator_product(
+            (
+                DecoherenceProduct::new().iy(1),
+                DecoherenceProduct::new().x(0).iy(2),
+            ),
+            CalculatorComplex::new(0.3, -0.1),
+        )
+        .unwrap();
+    let slos = SpinLindbladOpenSystem::group(system, noise).unwrap();
+
+    let density_matrix: Vec<Complex64> = (0..64)
+        .map(|k| Complex64::new((0.37 * k as f64).sin(), (0.71 * k as f64).cos()))
+        .collect();
+    let mut expected: Vec<Complex64> = vec![Complex64::default(); 64];
+    let (values, (rows, columns)) = slos.sparse_matrix_superoperator_coo(None).unwrap();
+    for ((value, row), column) in values.into_iter().zip(rows).zip(columns) {
+        expected[row] += value * density_matrix[column];
+    }
+
+    let result = slos.apply_superoperator(&density_matrix).unwrap();
+    assert_eq!(result.len(), 64);
+    for (value, expected_value) in result.iter().zip(expected.iter()) {
+        assert!((value - expected_value).norm() < 1e-12);
+    }
+
+    assert_eq!(
+        slos.apply_superoperator(&density_matrix[..16]),
+        Err(StruqtureError::MissmatchedStateLength {
+            length: 16,
+            number_spins: 3
+        })
+    );
+}
+
 #[test]
 fn test_truncate() {
     let mut system = SpinLindbladOpenSystem::new(None);
@@ -1104,3 +1173,500 @@ fn test_truncate() {
     let comparison_system2 = system.truncate(0.5);
     assert_eq!(test_system2, comparison_system2);
 }
+
+// Test the substitution of symbolic parameters in the SpinLindbladOpenSystem
+#[test]
+fn substitute_parameters() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(2));
+    slos.system_mut()
+        .set(PauliProduct::new().z(0), CalculatorFloat::from("omega"))
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().z(1),
+                DecoherenceProduct::new().z(1),
+            ),
+            CalculatorComplex::new("gamma", 0.0),
+        )
+        .unwrap();
+
+    let mut calculator = Calculator::new();
+    calculator.set_variable("omega", 1.5);
+    calculator.set_variable("gamma", 0.1);
+
+    let mut expected = SpinLindbladOpenSystem::new(Some(2));
+    expected
+        .system_mut()
+        .set(PauliProduct::new().z(0), CalculatorFloat::from(1.5))
+        .unwrap();
+    expected
+        .noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().z(1),
+                DecoherenceProduct::new().z(1),
+            ),
+            CalculatorComplex::from(0.1),
+        )
+        .unwrap();
+    let parameters: HashSet<String> = ["omega".to_string(), "gamma".to_string()]
+        .into_iter()
+        .collect();
+    assert_eq!(slos.free_parameters(), parameters);
+    assert_eq!(slos.substitute_parameters(&calculator).unwrap(), expected);
+    assert!(expected.free_parameters().is_empty());
+
+    assert!(slos.substitute_parameters(&Calculator::new()).is_err());
+}
+
+// Test the effective non-hermitian Hamiltonian of the SpinLindbladOpenSystem
+#[test]
+fn effective_hamiltonian() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(1));
+    slos.system_mut()
+        .set(PauliProduct::new().z(0), CalculatorFloat::from(0.5))
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().x(0),
+                DecoherenceProduct::new().x(0),
+            ),
+            CalculatorComplex::from(2.0),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().iy(0),
+                DecoherenceProduct::new().x(0),
+            ),
+            CalculatorComplex::from(1.0),
+        )
+        .unwrap();
+
+    // X^† X = 1 and X^† iY = i X Y = -Z
+    let mut expected = SpinOperator::new();
+    expected
+        .set(PauliProduct::new().z(0), CalculatorComplex::new(0.5, 0.5))
+        .unwrap();
+    expected
+        .set(PauliProduct::new(), CalculatorComplex::new(0.0, -1.0))
+        .unwrap();
+    assert_eq!(slos.effective_hamiltonian(), expected);
+}
+
+// Test the monte_carlo_wavefunction function of the SpinLindbladOpenSystem without noise
+#[test]
+fn monte_carlo_wavefunction_unitary() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(1));
+    slos.system_mut()
+        .set(PauliProduct::new().x(0), CalculatorFloat::from(1.0))
+        .unwrap();
+    let mut observable = SpinOperator::new();
+    observable
+        .set(PauliProduct::new().z(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    let initial_state = [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)];
+    let times: Vec<f64> = (0..11).map(|step| 0.1 * step as f64).collect();
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let result = slos
+        .monte_carlo_wavefunction(&initial_state, &times, &[observable], 3, 100, &mut rng)
+        .unwrap();
+    assert_eq!(result.len(), times.len());
+    for (time, expectation) in times.iter().zip(result.iter()) {
+        assert!((expectation[0] - Complex64::new((2.0 * time).cos(), 0.0)).norm() < 1e-6);
+    }
+}
+
+// Test the monte_carlo_wavefunction function of the SpinLindbladOpenSystem for amplitude damping
+#[test]
+fn monte_carlo_wavefunction_damping() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(1));
+    // Decay from |1> to |0> with rate 1 and the jump operator (X + iY) / 2
+    for left in [
+        DecoherenceProduct::new().x(0),
+        DecoherenceProduct::new().iy(0),
+    ] {
+        for right in [
+            DecoherenceProduct::new().x(0),
+            DecoherenceProduct::new().iy(0),
+        ] {
+            slos.noise_mut()
+                .set((left.clone(), right), CalculatorComplex::from(0.25))
+                .unwrap();
+        }
+    }
+    let mut observable = SpinOperator::new();
+    observable
+        .set(PauliProduct::new().z(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    let initial_state = [Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)];
+    let times = [0.0, 0.5, 1.0];
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let result = slos
+        .monte_carlo_wavefunction(
+            &initial_state,
+            &times,
+            &[observable.clone()],
+            1000,
+            50,
+            &mut rng,
+        )
+        .unwrap();
+    for (time, expectation) in times.iter().zip(result.iter()) {
+        let expected = 1.0 - 2.0 * (-time).exp();
+        assert!((expectation[0].re - expected).abs() < 0.1);
+        assert!(expectation[0].im.abs() < 1e-12);
+    }
+
+    // The same seed gives the same trajectories
+    let mut rng = StdRng::seed_from_u64(42);
+    let repeated = slos
+        .monte_carlo_wavefunction(&initial_state, &times, &[observable], 1000, 50, &mut rng)
+        .unwrap();
+    assert_eq!(result, repeated);
+}
+
+// Test the errors of the monte_carlo_wavefunction function of the SpinLindbladOpenSystem
+#[test]
+fn monte_carlo_wavefunction_error() {
+    let slos = SpinLindbladOpenSystem::new(Some(2));
+    let mut rng = StdRng::seed_from_u64(0);
+    let state = [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)];
+    assert_eq!(
+        slos.monte_carlo_wavefunction(&state, &[0.0, 1.0], &[], 1, 1, &mut rng),
+        Err(StruqtureError::MissmatchedStateLength {
+            length: 2,
+            number_spins: 2
+        })
+    );
+    let state = [Complex64::new(1.0, 0.0); 4];
+    assert_eq!(
+        slos.monte_carlo_wavefunction(&state, &[1.0, 0.0], &[], 1, 1, &mut rng),
+        Err(StruqtureError::UnsortedTimeGrid)
+    );
+}
+
+// Test the evolve_density_matrix functions of the SpinLindbladOpenSystem against the exponential of the superoperator
+#[test]
+fn evolve_density_matrix() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(2));
+    slos.system_mut()
+        .set(PauliProduct::new().x(0), CalculatorFloat::from(1.0))
+        .unwrap();
+    slos.system_mut()
+        .set(PauliProduct::new().z(0).z(1), CalculatorFloat::from(0.5))
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().z(1),
+                DecoherenceProduct::new().z(1),
+            ),
+            CalculatorComplex::from(0.3),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().x(0),
+                DecoherenceProduct::new().iy(0),
+            ),
+            CalculatorComplex::new(0.1, 0.1),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().iy(0),
+                DecoherenceProduct::new().x(0),
+            ),
+            CalculatorComplex::new(0.1, -0.1),
+        )
+        .unwrap();
+
+    let mut density_matrix = vec![Complex64::new(0.0, 0.0); 16];
+    density_matrix[0] = Complex64::new(1.0, 0.0);
+    let time = 0.8;
+    let superoperator = slos.dense_superoperator(None).unwrap();
+    let exponential =
+        na::DMatrix::from_fn(16, 16, |row, column| superoperator[[row, column]] * time).exp();
+    let expected = exponential * na::DVector::from_vec(density_matrix.clone());
+
+    let runge_kutta = slos
+        .evolve_density_matrix(&density_matrix, time, 1e-10)
+        .unwrap();
+    let krylov = slos
+        .evolve_density_matrix_krylov(&density_matrix, time, 8, 1e-10)
+        .unwrap();
+    for index in 0..16 {
+        assert!((runge_kutta[index] - expected[index]).norm() < 1e-8);
+        assert!((krylov[index] - expected[index]).norm() < 1e-8);
+    }
+    // The trace of the density matrix is conserved
+    let trace: Complex64 = (0..4).map(|index| krylov[index * 4 + index]).sum();
+    assert!((trace - Complex64::new(1.0, 0.0)).norm() < 1e-8);
+
+    // An invariant Krylov subspace gives the exact exponential in a single step
+    let krylov_full = slos
+        .evolve_density_matrix_krylov(&density_matrix, time, 16, 1e-10)
+        .unwrap();
+    for index in 0..16 {
+        assert!((krylov_full[index] - expected[index]).norm() < 1e-10);
+    }
+
+    let wrong_length = vec![Complex64::new(1.0, 0.0); 4];
+    assert!(matches!(
+        slos.evolve_density_matrix(&wrong_length, time, 1e-10),
+        Err(StruqtureError::MissmatchedStateLength { .. })
+    ));
+    assert!(matches!(
+        slos.evolve_density_matrix_krylov(&wrong_length, time, 8, 1e-10),
+        Err(StruqtureError::MissmatchedStateLength { .. })
+    ));
+
+    for tolerance in [0.0, -1e-10, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            slos.evolve_density_matrix(&density_matrix, time, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+        assert!(matches!(
+            slos.evolve_density_matrix_krylov(&density_matrix, time, 8, tolerance),
+            Err(StruqtureError::InvalidTolerance { .. })
+        ));
+    }
+}
+
+// Test that the parallel construction of the superoperator returns the same matrix as the serial construction
+#[cfg(feature = "rayon")]
+#[test]
+fn sparse_matrix_superoperator_coo_parallel() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(8));
+    slos.system_mut()
+        .add_operator_product(PauliProduct::new().x(0).x(7), CalculatorFloat::from(1.0))
+        .unwrap();
+    slos.system_mut()
+        .add_operator_product(PauliProduct::new().z(3), CalculatorFloat::from(0.4))
+        .unwrap();
+    slos.noise_mut()
+        .add_operator_product(
+            (
+                DecoherenceProduct::new().iy(2),
+                DecoherenceProduct::new().iy(2),
+            ),
+            CalculatorComplex::from(0.3),
+        )
+        .unwrap();
+    slos.noise_mut()
+        .add_operator_product(
+            (
+                DecoherenceProduct::new().x(5),
+                DecoherenceProduct::new().z(5),
+            ),
+            CalculatorComplex::new(0.1, 0.2),
+        )
+        .unwrap();
+
+    // The 4^8 rows span several chunks of the parallel construction
+    assert_eq!(
+        slos.sparse_matrix_superoperator_coo_parallel(None),
+        slos.sparse_matrix_superoperator_coo(None)
+    );
+}
+
+// Test the steady_state function of the SpinLindbladOpenSystem
+#[test]
+fn steady_state() {
+    // Amplitude damping with the jump operator (X + iY) / 2 relaxes to |0><0|
+    let mut slos = SpinLindbladOpenSystem::new(Some(1));
+    for left in [
+        DecoherenceProduct::new().x(0),
+        DecoherenceProduct::new().iy(0),
+    ] {
+        for right in [
+            DecoherenceProduct::new().x(0),
+            DecoherenceProduct::new().iy(0),
+        ] {
+            slos.noise_mut()
+                .set((left.clone(), right), CalculatorComplex::from(0.25))
+                .unwrap();
+        }
+    }
+    let steady_state = slos.steady_state(1e-12, 100).unwrap();
+    assert_eq!(steady_state.dim(), (2, 2));
+    assert!((steady_state[[0, 0]] - Complex64::new(1.0, 0.0)).norm() < 1e-10);
+    assert!(steady_state[[0, 1]].norm() < 1e-10);
+    assert!(steady_state[[1, 0]].norm() < 1e-10);
+    assert!(steady_state[[1, 1]].norm() < 1e-10);
+
+    // Driven and damped spins with dephasing
+    let mut slos = SpinLindbladOpenSystem::new(Some(2));
+    slos.system_mut()
+        .set(PauliProduct::new().x(0), CalculatorFloat::from(0.7))
+        .unwrap();
+    slos.system_mut()
+        .set(PauliProduct::new().x(1), CalculatorFloat::from(0.4))
+        .unwrap();
+    slos.system_mut()
+        .set(PauliProduct::new().z(0).z(1), CalculatorFloat::from(0.3))
+        .unwrap();
+    for spin in 0..2 {
+        for left in [
+            DecoherenceProduct::new().x(spin),
+            DecoherenceProduct::new().iy(spin),
+        ] {
+            for right in [
+                DecoherenceProduct::new().x(spin),
+                DecoherenceProduct::new().iy(spin),
+            ] {
+                slos.noise_mut()
+                    .set((left.clone(), right), CalculatorComplex::from(0.25))
+                    .unwrap();
+            }
+        }
+    }
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().z(1),
+                DecoherenceProduct::new().z(1),
+            ),
+            CalculatorComplex::from(0.2),
+        )
+        .unwrap();
+    let steady_state = slos.steady_state(1e-12, 1000).unwrap();
+    let flattened: Vec<Complex64> = steady_state.iter().cloned().collect();
+    let derivative = slos.apply_superoperator(&flattened).unwrap();
+    for entry in derivative {
+        assert!(entry.norm() < 1e-9);
+    }
+    let trace: Complex64 = (0..4).map(|index| steady_state[[index, index]]).sum();
+    assert!((trace - Complex64::new(1.0, 0.0)).norm() < 1e-12);
+    for row in 0..4 {
+        for column in 0..4 {
+            assert!(
+                (steady_state[[row, column]] - steady_state[[column, row]].conj()).norm() < 1e-12
+            );
+        }
+    }
+
+    assert_eq!(
+        slos.steady_state(1e-12, 0),
+        Err(StruqtureError::NoConvergence { iterations: 0 })
+    );
+}
+
+// Test the sparse_matrix_superoperator_coo_sector function of the SpinLindbladOpenSystem
+#[test]
+fn sparse_matrix_superoperator_coo_sector() {
+    let mut slos = SpinLindbladOpenSystem::new(Some(2));
+    for (pp, value) in [
+        (PauliProduct::new().x(0).x(1), 1.0),
+        (PauliProduct::new().y(0).y(1), 1.0),
+        (PauliProduct::new().z(0), 0.4),
+    ] {
+        slos.system_mut()
+            .add_operator_product(pp, value.into())
+            .unwrap();
+    }
+    // Relaxation of spin 0 with the jump operator (X + iY) / 2 and dephasing of spin 1
+    for left in [
+        DecoherenceProduct::new().x(0),
+        DecoherenceProduct::new().iy(0),
+    ] {
+        for right in [
+            DecoherenceProduct::new().x(0),
+            DecoherenceProduct::new().iy(0),
+        ] {
+            slos.noise_mut()
+                .set((left.clone(), right), CalculatorComplex::from(0.25))
+                .unwrap();
+        }
+    }
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().z(1),
+                DecoherenceProduct::new().z(1),
+            ),
+            CalculatorComplex::from(0.5),
+        )
+        .unwrap();
+    let full = slos.sparse_matrix_superoperator(None).unwrap();
+
+    let mut total_dimension = 0;
+    for difference in -2..=2 {
+        let sector = SpinSuperoperatorSector::NumberUpDifference(difference);
+        let ((values, (rows, columns)), basis) = slos
+            .sparse_matrix_superoperator_coo_sector(sector, None)
+            .unwrap();
+        assert_eq!(basis, sector.basis(2));
+        total_dimension += basis.len();
+        let restricted: HashMap<(usize, usize), Complex64> = rows
+            .iter()
+            .zip(columns.iter())
+            .zip(values.iter())
+            .map(|((row, column), value)| ((*row, *column), *value))
+            .collect();
+        for (row, row_entry) in basis.iter().enumerate() {
+            for (column, column_entry) in basis.iter().enumerate() {
+                let expected = full
+                    .get(&(*row_entry, *column_entry))
+                    .copied()
+                    .unwrap_or_default();
+                let value = restricted.get(&(row, column)).copied().unwrap_or_default();
+                assert!((value - expected).norm() < 1e-12);
+            }
+        }
+    }
+    assert_eq!(total_dimension, 16);
+    assert_eq!(
+        SpinSuperoperatorSector::NumberUpDifference(0).basis(2),
+        vec![0, 5, 6, 9, 10, 15]
+    );
+
+    // Bit flips change the number of spins up but conserve the parity
+    slos.noise_mut()
+        .set(
+            (
+                DecoherenceProduct::new().x(1),
+                DecoherenceProduct::new().x(1),
+            ),
+            CalculatorComplex::from(0.1),
+        )
+        .unwrap();
+    let (_, basis) = slos
+        .sparse_matrix_superoperator_coo_sector(SpinSuperoperatorSector::EqualParity, None)
+        .unwrap();
+    assert_eq!(basis.len(), 8);
+    assert!(matches!(
+        slos.sparse_matrix_superoperator_coo_sector(
+            SpinSuperoperatorSector::NumberUpDifference(0),
+            None
+        ),
+        Err(StruqtureError::SymmetryNotConserved { .. })
+    ));
+}
+
+// Test that the directly enumerated entries of a superoperator sector match the filtered flattened density matrix
+#[test]
+fn superoperator_sector_basis() {
+    for number_spins in 0..=4 {
+        let dimension = 2usize.pow(number_spins as u32);
+        let mut sectors: Vec<SpinSuperoperatorSector> = (-5..=5)
+            .map(SpinSuperoperatorSector::NumberUpDifference)
+            .collect();
+        sectors.push(SpinSuperoperatorSector::EqualParity);
+        sectors.push(SpinSuperoperatorSector::OppositeParity);
+        for sector in sectors {
+            let expected: Vec<usize> = (0..dimension.pow(2))
+                .filter(|entry| sector.contains(entry / dimension, entry % dimension))
+                .collect();
+            assert_eq!(sector.basis(number_spins), expected);
+        }
+    }
+}
@@ -24,9 +24,10 @@ use std::ops::{Add, Sub};
 use std::str::FromStr;
 use struqture::prelude::*;
 use struqture::spins::{
-    OperateOnSpins, PauliProduct, SpinHamiltonian, SpinOperator, ToSparseMatrixOperator,
+    CliffordTableau, OperateOnSpins, PauliProduct, SpinHamiltonian, SpinOperator,
+    SpinSymmetrySector, ToSparseMatrixOperator,
 };
-use struqture::{CooSparseMatrix, OperateOnDensityMatrix, SpinIndex};
+use struqture::{CooSparseMatrix, OperateOnDensityMatrix, SpinIndex, StruqtureError};
 use test_case::test_case;
 
 // Test the new function of the SpinOperator
@@ -350,6 +351,43 @@ fn mul_so_so_same_index() {
     assert_eq!(so_0 * so_1, so_0_1);
 }
 
+// Test the commutator and anticommutator of SpinOperators against the explicit products
+#[test]
+fn commutator_anticommutator() {
+    let mut so_0 = SpinOperator::new();
+    so_0.add_operator_product(PauliProduct::new().x(0), CalculatorComplex::from(2.0))
+        .unwrap();
+    so_0.add_operator_product(
+        PauliProduct::new().z(1).y(2),
+        CalculatorComplex::new(0.0, 0.5),
+    )
+    .unwrap();
+    let mut so_1 = SpinOperator::new();
+    so_1.add_operator_product(PauliProduct::new().y(0).z(1), CalculatorComplex::from(1.5))
+        .unwrap();
+    so_1.add_operator_product(PauliProduct::new().x(2), CalculatorComplex::from(0.25))
+        .unwrap();
+    so_1.add_operator_product(PauliProduct::new().z(3), CalculatorComplex::from(-1.0))
+        .unwrap();
+
+    let forward = so_0.clone() * so_1.clone();
+    let backward = so_1.clone() * so_0.clone();
+    assert_eq!(so_0.commutator(&so_1), forward.clone() - backward.clone());
+    assert_eq!(so_0.anticommutator(&so_1), forward + backward);
+
+    let mut x = SpinOperator::new();
+    x.add_operator_product(PauliProduct::new().x(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    let mut y = SpinOperator::new();
+    y.add_operator_product(PauliProduct::new().y(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    let mut z = SpinOperator::new();
+    z.add_operator_product(PauliProduct::new().z(0), CalculatorComplex::new(0.0, 2.0))
+        .unwrap();
+    assert_eq!(x.commutator(&y), z);
+    assert_eq!(x.anticommutator(&y), SpinOperator::new());
+}
+
 // Test the multiplication: SpinOperator * Calculatorcomplex
 #[test]
 fn mul_so_cc() {
@@ -806,3 +844,101 @@ fn test_spin_operator_pauli_multiplication() {
     let prod_3 = (spin_op_5 * pauli_2) * pauli_3;
     assert_eq!(prod_3, spin_op_4 * CalculatorComplex::from((0.0, 1.0)));
 }
+
+// Test the conjugate_by_clifford function of the SpinOperator
+#[test]
+fn conjugate_by_clifford() {
+    let tableau = CliffordTableau::new(2).hadamard(0).cnot(0, 1).unwrap();
+    let mut so = SpinOperator::new();
+    so.add_operator_product(PauliProduct::new().z(0), CalculatorComplex::new(1.0, 2.0))
+        .unwrap();
+    so.add_operator_product(PauliProduct::new().y(0), CalculatorComplex::from("a"))
+        .unwrap();
+    so.add_operator_product(PauliProduct::new(), CalculatorComplex::from(0.5))
+        .unwrap();
+
+    let mut expected = SpinOperator::new();
+    expected
+        .add_operator_product(
+            PauliProduct::new().x(0).x(1),
+            CalculatorComplex::new(1.0, 2.0),
+        )
+        .unwrap();
+    expected
+        .add_operator_product(
+            PauliProduct::new().y(0).x(1),
+            CalculatorComplex::from("a") * -1.0,
+        )
+        .unwrap();
+    expected
+        .add_operator_product(PauliProduct::new(), CalculatorComplex::from(0.5))
+        .unwrap();
+    assert_eq!(so.conjugate_by_clifford(&tableau), expected);
+}
+
+// Test the qubit_wise_commuting_groups and commuting_groups functions of the SpinOperator
+#[test]
+fn commuting_groups() {
+    let mut so = SpinOperator::new();
+    so.add_operator_product(PauliProduct::new().x(0), CalculatorComplex::new(1.0, 1.0))
+        .unwrap();
+    so.add_operator_product(PauliProduct::new().z(0).z(1), CalculatorComplex::from(2.0))
+        .unwrap();
+    so.add_operator_product(PauliProduct::new().y(0).y(1), CalculatorComplex::from(3.0))
+        .unwrap();
+
+    // Y0Y1 commutes with both X0 and Z0Z1, which anticommute with each other
+    let groups = so.commuting_groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups.iter().map(|group| group.len()).sum::<usize>(), 3);
+    for group in groups.iter() {
+        for (pp, value) in group.iter() {
+            assert_eq!(so.get(pp), value);
+        }
+    }
+
+    // No pair of products commutes qubit-wise
+    let groups = so.qubit_wise_commuting_groups();
+    assert_eq!(groups.len(), 3);
+    for (group, basis) in groups.iter() {
+        assert_eq!(group.len(), 1);
+        assert_eq!(group.get(basis), so.get(basis));
+    }
+}
+
+// Test that sparse_matrix_coo_sector detects couplings into the sector for a non-hermitian SpinOperator
+#[test]
+fn sparse_matrix_coo_sector_non_hermitian() {
+    // X + iY = 2 |0><1| only couples the state 1 with spin 0 down to the state 0 with spin 0 up
+    let mut so = SpinOperator::new();
+    so.add_operator_product(PauliProduct::new().x(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    so.add_operator_product(PauliProduct::new().y(0), CalculatorComplex::new(0.0, 1.0))
+        .unwrap();
+
+    // The row of the down state vanishes, the coupling into it is in the row of the up state
+    assert_eq!(
+        so.sparse_matrix_coo_sector(SpinSymmetrySector::NumberUp(0), Some(1)),
+        Err(StruqtureError::SymmetryNotConserved { row: 0, column: 1 })
+    );
+    assert_eq!(
+        so.sparse_matrix_coo_sector(SpinSymmetrySector::NumberUp(1), Some(1)),
+        Err(StruqtureError::SymmetryNotConserved { row: 0, column: 1 })
+    );
+
+    // The product with Z(1) conserves the state of spin 1
+    let mut conserving = SpinOperator::new();
+    conserving
+        .add_operator_product(PauliProduct::new().z(1), CalculatorComplex::new(0.0, 1.0))
+        .unwrap();
+    let ((values, (rows, columns)), basis) = conserving
+        .sparse_matrix_coo_sector(SpinSymmetrySector::NumberUp(1), Some(2))
+        .unwrap();
+    assert_eq!(basis, vec![1, 2]);
+    assert_eq!(rows, vec![0, 1]);
+    assert_eq!(columns, vec![0, 1]);
+    assert_eq!(
+        values,
+        vec![Complex64::new(0.0, 1.0), Complex64::new(0.0, -1.0)]
+    );
+}
@@ -24,7 +24,9 @@ use struqture::prelude::*;
 use struqture::spins::{
     OperateOnSpins, PauliProduct, SpinOperator, SpinSystem, ToSparseMatrixOperator,
 };
-use struqture::{CooSparseMatrix, OperateOnDensityMatrix, SpinIndex, StruqtureError};
+use struqture::{
+    CooSparseMatrix, OperateOnDensityMatrix, SpinIndex, StruqtureError, MAXIMAL_DENSE_DIMENSION,
+};
 use test_case::test_case;
 
 // Test the new function of the SpinSystem
@@ -840,6 +842,14 @@ fn test_superoperator(pauli_representation: &str, pauli_operators: &[&str]) {
         let val = test_matrix[(*row, *column)];
         assert_eq!(&val, second_val);
     }
+
+    let dense_matrix = system.dense_superoperator(None).unwrap();
+    assert_eq!(dense_matrix.dim(), (dimension, dimension));
+    for row in 0..dimension {
+        for column in 0..dimension {
+            assert_eq!(test_matrix[(row, column)], dense_matrix[[row, column]]);
+        }
+    }
 }
 
 #[test_case("0Z", &["Z"]; "0Z")]
@@ -905,6 +915,321 @@ fn test_operator(pauli_representation: &str, pauli_operators: &[&str]) {
             }
         }
     }
+
+    let dense_matrix = system.dense_matrix(None).unwrap();
+    assert_eq!(dense_matrix.dim(), (dimension, dimension));
+    for row in 0..dimension {
+        for column in 0..dimension {
+            assert_eq!(test_matrix[(row, column)], dense_matrix[[row, column]]);
+        }
+    }
+}
+
+// Test the failure of the dense matrix construction for too many spins
+#[test]
+fn dense_matrix_dimension_too_large() {
+    let mut system = SpinSystem::new(Some(7));
+    system
+        .add_operator_product(PauliProduct::new().z(0), CalculatorComplex::from(1.0))
+        .unwrap();
+
+    assert!(system.dense_matrix(None).is_ok());
+    assert_eq!(
+        system.dense_superoperator(None),
+        Err(StruqtureError::DimensionTooLarge {
+            dimension: 16384,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+    assert_eq!(
+        system.dense_matrix(Some(13)),
+        Err(StruqtureError::DimensionTooLarge {
+            dimension: 8192,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+    assert_eq!(
+        system.dense_matrix(Some(0)),
+        Err(StruqtureError::NumberSpinsExceeded)
+    );
+    assert_eq!(
+        system.dense_matrix(Some(200)),
+        Err(StruqtureError::HilbertSpaceTooLarge {
+            number_spins: 200,
+            number_modes: 0,
+            maximal_dimension: MAXIMAL_DENSE_DIMENSION
+        })
+    );
+}
+
+// Helper function collecting a CSR or CSC matrix into a map, checking that the inner indices are sorted
+fn compressed_to_map(
+    values: Vec<Complex64>,
+    indices: Vec<usize>,
+    pointers: Vec<usize>,
+    transposed: bool,
+) -> HashMap<(usize, usize), Complex64> {
+    let mut map: HashMap<(usize, usize), Complex64> = HashMap::new();
+    for (outer, bounds) in pointers.windows(2).enumerate() {
+        let inner_indices = &indices[bounds[0]..bounds[1]];
+        assert!(inner_indices.windows(2).all(|pair| pair[0] < pair[1]));
+        for (inner, value) in inner_indices
+            .iter()
+            .zip(values[bounds[0]..bounds[1]].iter())
+        {
+            let key = if transposed {
+                (*inner, outer)
+            } else {
+                (outer, *inner)
+            };
+            map.insert(key, *value);
+        }
+    }
+    map
+}
+
+// Test the CSR and CSC output of operator and superoperator against the COO output
+#[test]
+fn compressed_sparse_matrices() {
+    let mut system = SpinSystem::new(Some(3));
+    system
+        .add_operator_product(PauliProduct::new().x(0).y(1), CalculatorComplex::from(1.0))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(2), CalculatorComplex::from(0.5))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().y(0), CalculatorComplex::new(0.0, 2.0))
+        .unwrap();
+
+    let (values, (rows, columns)) = system.sparse_matrix_coo(None).unwrap();
+    let coo: HashMap<(usize, usize), Complex64> =
+        rows.into_iter().zip(columns).zip(values).collect();
+
+    let (values, columns, row_pointers) = system.sparse_matrix_csr(None).unwrap();
+    assert_eq!(row_pointers.len(), 9);
+    assert_eq!(values.len(), coo.len());
+    assert_eq!(compressed_to_map(values, columns, row_pointers, false), coo);
+    let (values, rows, column_pointers) = system.sparse_matrix_csc(None).unwrap();
+    assert_eq!(column_pointers.len(), 9);
+    assert_eq!(values.len(), coo.len());
+    assert_eq!(compressed_to_map(values, rows, column_pointers, true), coo);
+
+    let (values, (rows, columns)) = system.sparse_matrix_superoperator_coo(None).unwrap();
+    let coo: HashMap<(usize, usize), Complex64> =
+        rows.into_iter().zip(columns).zip(values).collect();
+
+    let (values, columns, row_pointers) = system.sparse_matrix_superoperator_csr(None).unwrap();
+    assert_eq!(row_pointers.len(), 65);
+    assert_eq!(compressed_to_map(values, columns, row_pointers, false), coo);
+    let (values, rows, column_pointers) = system.sparse_matrix_superoperator_csc(None).unwrap();
+    assert_eq!(column_pointers.len(), 65);
+    assert_eq!(compressed_to_map(values, rows, column_pointers, true), coo);
+
+    assert_eq!(
+        system.sparse_matrix_csr(Some(1)),
+        Err(StruqtureError::NumberSpinsExceeded)
+    );
+}
+
+// Test that the COO output is ordered by row and column
+#[test]
+fn sparse_matrix_coo_ordered() {
+    let mut system = SpinSystem::new(Some(3));
+    system
+        .add_operator_product(PauliProduct::new().x(0).x(2), CalculatorComplex::from(1.0))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().y(1), CalculatorComplex::from(0.5))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(0), CalculatorComplex::from(-0.5))
+        .unwrap();
+
+    for (_, (rows, columns)) in [
+        system.sparse_matrix_coo(None).unwrap(),
+        system.sparse_matrix_superoperator_coo(None).unwrap(),
+    ] {
+        let keys: Vec<(usize, usize)> = rows.into_iter().zip(columns).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    let (values, (_, columns)) = system.sparse_matrix_coo(None).unwrap();
+    let (csr_values, csr_columns, _) = system.sparse_matrix_csr(None).unwrap();
+    assert_eq!(values, csr_values);
+    assert_eq!(columns, csr_columns);
+}
+
+// Test that the parallel construction of the COO output is identical to the serial construction
+#[cfg(feature = "rayon")]
+#[test]
+fn sparse_matrix_coo_parallel() {
+    let mut system = SpinSystem::new(Some(4));
+    system
+        .add_operator_product(PauliProduct::new().x(0).x(2), CalculatorComplex::from(1.0))
+        .unwrap();
+    system
+        .add_operator_product(
+            PauliProduct::new().y(1).z(3),
+            CalculatorComplex::new(0.5, 0.2),
+        )
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(0), CalculatorComplex::from(-0.5))
+        .unwrap();
+
+    assert_eq!(
+        system.sparse_matrix_coo_parallel(None),
+        system.sparse_matrix_coo(None)
+    );
+    assert_eq!(
+        system.sparse_matrix_superoperator_coo_parallel(None),
+        system.sparse_matrix_superoperator_coo(None)
+    );
+}
+
+// Test the CSR and CSC output for a matrix with known structure
+#[test]
+fn compressed_sparse_matrices_structure() {
+    let mut system = SpinSystem::new(Some(2));
+    system
+        .add_operator_product(PauliProduct::new().x(0), CalculatorComplex::from(1.0))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(1), CalculatorComplex::from(2.0))
+        .unwrap();
+
+    let one = Complex64::new(1.0, 0.0);
+    let two = Complex64::new(2.0, 0.0);
+    let expected = (
+        vec![two, one, one, two, -two, one, one, -two],
+        vec![0, 1, 0, 1, 2, 3, 2, 3],
+        vec![0, 2, 4, 6, 8],
+    );
+    assert_eq!(system.sparse_matrix_csr(None).unwrap(), expected);
+    assert_eq!(system.sparse_matrix_csc(None).unwrap(), expected);
+}
+
+// Test the matrix-free application of the operator against the sparse matrix
+#[test]
+fn apply_to_state() {
+    let mut system = SpinSystem::new(Some(3));
+    system
+        .add_operator_product(
+            PauliProduct::new().x(0).y(2),
+            CalculatorComplex::new(0.5, 0.2),
+        )
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().y(1).z(2), CalculatorComplex::from(-1.0))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(0), CalculatorComplex::new(0.0, 0.3))
+        .unwrap();
+
+    let state: Vec<Complex64> = (0..8)
+        .map(|k| Complex64::new((0.37 * k as f64).sin(), (0.71 * k as f64).cos()))
+        .collect();
+    let mut expected: Vec<Complex64> = vec![Complex64::default(); 8];
+    let (values, (rows, columns)) = system.sparse_matrix_coo(None).unwrap();
+    for ((value, row), column) in values.iter().zip(rows.iter()).zip(columns.iter()) {
+        expected[*row] += value * state[*column];
+    }
+    let result = system.apply_to_state(&state).unwrap();
+    for (value, expected_value) in result.iter().zip(expected.iter()) {
+        assert!((value - expected_value).norm() < 1e-12);
+    }
+
+    let density_matrix: Vec<Complex64> = (0..64)
+        .map(|k| Complex64::new((0.13 * k as f64).cos(), (0.29 * k as f64).sin()))
+        .collect();
+    let mut expected: Vec<Complex64> = vec![Complex64::default(); 64];
+    let (values, (rows, columns)) = system.sparse_matrix_superoperator_coo(None).unwrap();
+    for ((value, row), column) in values.iter().zip(rows.iter()).zip(columns.iter()) {
+        expected[*row] += value * density_matrix[*column];
+    }
+    let result = system.apply_superoperator(&density_matrix).unwrap();
+    for (value, expected_value) in result.iter().zip(expected.iter()) {
+        assert!((value - expected_value).norm() < 1e-12);
+    }
+
+    // A larger Hilbert space than the operator acts on is allowed
+    assert_eq!(
+        system
+            .apply_to_state(&[Complex64::default(); 16])
+            .unwrap()
+            .len(),
+        16
+    );
+    assert_eq!(
+        system.apply_to_state(&state[..4]),
+        Err(StruqtureError::MissmatchedStateLength {
+            length: 4,
+            number_spins: 3
+        })
+    );
+    assert_eq!(
+        system.apply_superoperator(&density_matrix[..32]),
+        Err(StruqtureError::MissmatchedStateLength {
+            length: 32,
+            number_spins: 3
+        })
+    );
+}
+
+// Test the term-by-term expectation values against the sparse matrix
+#[test]
+fn expectation_value() {
+    let mut system = SpinSystem::new(Some(3));
+    system
+        .add_operator_product(
+            PauliProduct::new().x(0).y(2),
+            CalculatorComplex::new(0.5, 0.2),
+        )
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().y(1).z(2), CalculatorComplex::from(-1.0))
+        .unwrap();
+    system
+        .add_operator_product(PauliProduct::new().z(0), CalculatorComplex::new(0.0, 0.3))
+        .unwrap();
+
+    let state: Vec<Complex64> = (0..8)
+        .map(|k| Complex64::new((0.37 * k as f64).sin(), (0.71 * k as f64).cos()))
+        .collect();
+    let mut expected = Complex64::default();
+    let (values, (rows, columns)) = system.sparse_matrix_coo(None).unwrap();
+    for ((value, row), column) in values.iter().zip(rows.iter()).zip(columns.iter()) {
+        expected += state[*row].conj() * value * state[*column];
+    }
+    let result = system.expectation_value(&state).unwrap();
+    assert!((result.re.float().unwrap() - expected.re).abs() < 1e-12);
+    assert!((result.im.float().unwrap() - expected.im).abs() < 1e-12);
+
+    // The density matrix |psi><psi| gives the same expectation value
+    let density_matrix: Vec<Complex64> = (0..64)
+        .map(|k| state[k / 8] * state[k % 8].conj())
+        .collect();
+    let result = system
+        .expectation_value_density_matrix(&density_matrix)
+        .unwrap();
+    assert!((result.re.float().unwrap() - expected.re).abs() < 1e-12);
+    assert!((result.im.float().unwrap() - expected.im).abs() < 1e-12);
+
+    assert_eq!(
+        system.expectation_value(&state[..4]),
+        Err(StruqtureError::MissmatchedStateLength {
+            length: 4,
+            number_spins: 3
+        })
+    );
+    assert_eq!(
+        system.expectation_value_density_matrix(&density_matrix[..32]),
+        Err(StruqtureError::MissmatchedStateLength {
+            length: 32,
+            number_spins: 3
+        })
+    );
 }
 
 #[test]